@@ -1,14 +1,20 @@
 // Binance WebSocket feed handler
 
 use super::error::{FeedError, FeedResult};
+use super::handler::{FeedHandler, FeedHealth};
+use super::reconnect::{ReconnectPolicy, Reconnector};
 use crate::config::Settings;
-use chrono::Utc;
-use futures::StreamExt;
-use kairos_domain::{Exchange, MarketTick};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use kairos_domain::{Exchange, Kline, MarketEvent, MarketTick, OrderSide, Quote};
 use serde::{Deserialize, Serialize};
-use tokio::sync::broadcast;
-use tokio::time::{sleep, Duration};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, mpsc};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 /// Binance API credentials
@@ -63,34 +69,145 @@ impl BinanceCredentials {
     }
 }
 
-/// Binance trade stream message structure
-#[derive(Debug, Deserialize)]
-struct BinanceTradeMessage {
-    #[serde(rename = "e")]
-    event_type: String, // Event type (e.g., "trade")
-    #[serde(rename = "s")]
-    symbol: String, // Trading pair (e.g., "BTCUSDT")
-    #[serde(rename = "p")]
-    price: String, // Trade price
-    #[serde(rename = "q")]
-    quantity: String, // Trade quantity
-    #[serde(rename = "T")]
-    trade_time: i64, // Trade timestamp
+/// Binance configuration (non-sensitive)
+///
+/// This struct holds non-sensitive configuration like WebSocket and REST URLs.
+#[derive(Debug, Clone)]
+pub struct BinanceConfig {
+    pub ws_url: String,
+    pub rest_url: String,
+}
+
+impl BinanceConfig {
+    /// Extract Binance configuration from Settings
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let settings = Settings::new()?;
+    /// let config = BinanceConfig::from_settings(&settings);
+    /// ```
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            ws_url: settings.exchange.binance_ws_url.clone(),
+            rest_url: settings.exchange.binance_rest_url.clone(),
+        }
+    }
+}
+
+/// Market data streams subscribed for every symbol
+#[derive(Debug, Clone)]
+pub struct BinanceStreams {
+    /// `<symbol>@aggTrade` - aggregated trades
+    pub agg_trade: bool,
+    /// `<symbol>@bookTicker` - best bid/ask updates
+    pub book_ticker: bool,
+    /// `<symbol>@kline_<interval>` - one stream per interval
+    pub kline_intervals: Vec<String>,
+}
+
+impl Default for BinanceStreams {
+    fn default() -> Self {
+        Self {
+            agg_trade: true,
+            book_ticker: false,
+            kline_intervals: Vec::new(),
+        }
+    }
+}
+
+impl BinanceStreams {
+    /// Build the stream selection from `[trading]` settings
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            agg_trade: true,
+            book_ticker: settings.trading.stream_book_ticker,
+            kline_intervals: if settings.trading.stream_klines {
+                settings.get_kline_intervals()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// Stream names for a single symbol (e.g. "btcusdt@aggTrade")
+    pub fn names_for(&self, symbol: &str) -> Vec<String> {
+        let symbol = symbol.to_lowercase();
+        let mut names = Vec::new();
+        if self.agg_trade {
+            names.push(format!("{}@aggTrade", symbol));
+        }
+        if self.book_ticker {
+            names.push(format!("{}@bookTicker", symbol));
+        }
+        for interval in &self.kline_intervals {
+            names.push(format!("{}@kline_{}", symbol, interval));
+        }
+        names
+    }
 }
 
 /// Binance aggregated trade stream message
 #[derive(Debug, Deserialize)]
 struct BinanceAggTradeMessage {
-    #[serde(rename = "e")]
-    event_type: String,
     #[serde(rename = "s")]
     symbol: String,
+    #[serde(rename = "a")]
+    agg_trade_id: u64, // Aggregate trade ID
     #[serde(rename = "p")]
     price: String,
     #[serde(rename = "q")]
     quantity: String,
     #[serde(rename = "T")]
     trade_time: i64,
+    #[serde(rename = "m")]
+    buyer_is_maker: bool, // True when the seller was the aggressor
+}
+
+/// Binance best bid/ask stream message
+#[derive(Debug, Deserialize)]
+struct BinanceBookTickerMessage {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "b")]
+    bid_price: String,
+    #[serde(rename = "B")]
+    bid_quantity: String,
+    #[serde(rename = "a")]
+    ask_price: String,
+    #[serde(rename = "A")]
+    ask_quantity: String,
+}
+
+/// Binance kline stream message
+#[derive(Debug, Deserialize)]
+struct BinanceKlineMessage {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "k")]
+    kline: BinanceKline,
+}
+
+/// Candle payload inside a kline message
+#[derive(Debug, Deserialize)]
+struct BinanceKline {
+    #[serde(rename = "t")]
+    open_time: i64,
+    #[serde(rename = "T")]
+    close_time: i64,
+    #[serde(rename = "i")]
+    interval: String,
+    #[serde(rename = "o")]
+    open: String,
+    #[serde(rename = "h")]
+    high: String,
+    #[serde(rename = "l")]
+    low: String,
+    #[serde(rename = "c")]
+    close: String,
+    #[serde(rename = "v")]
+    volume: String,
+    #[serde(rename = "x")]
+    is_closed: bool,
 }
 
 /// Subscribe message structure for Binance WebSocket
@@ -101,200 +218,487 @@ struct SubscribeMessage {
     id: u64,
 }
 
-pub struct BinanceFeedHandler {
-    market_data_tx: broadcast::Sender<MarketTick>,
-    symbols: Vec<String>,
-    api_key: String,
-    api_secret: String,
+/// Reply to a SUBSCRIBE/UNSUBSCRIBE request
+#[derive(Debug, Deserialize)]
+struct BinanceErrorDetail {
+    code: i64,
+    msg: String,
 }
 
-impl BinanceFeedHandler {
-    /// Create a new Binance feed handler with API credentials
-    ///
-    /// # Arguments
-    /// * `credentials` - Validated Binance API credentials
-    /// * `market_data_tx` - Broadcast channel sender for market data
-    /// * `symbols` - Optional list of trading pairs (e.g., ["btcusdt", "ethusdt"])
-    ///               If None, defaults to ["btcusdt", "ethusdt"]
-    ///
-    /// # Returns
-    /// * Successfully created handler with credentials
-    ///
-    /// # Example
-    /// ```rust,ignore
-    /// let settings = Settings::new()?;
-    /// let credentials = BinanceCredentials::from_settings(&settings)?;
-    /// let handler = BinanceFeedHandler::new(credentials, market_data_tx, None);
-    /// ```
-    pub fn new(
-        credentials: BinanceCredentials,
-        market_data_tx: broadcast::Sender<MarketTick>,
-        symbols: Option<Vec<String>>,
-    ) -> Self {
-        let symbols = symbols.unwrap_or_else(|| vec!["btcusdt".to_string(), "ethusdt".to_string()]);
+/// Messages received on the combined stream endpoint
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BinanceWsMessage {
+    Stream {
+        stream: String,
+        data: serde_json::Value,
+    },
+    Error {
+        error: BinanceErrorDetail,
+        id: Option<u64>,
+    },
+    Response {
+        id: u64,
+    },
+}
 
-        tracing::info!(
-            "✅ Binance Feed Handler initialized with API credentials (key: {}...)",
-            &credentials.api_key[..credentials.api_key.len().min(8)]
-        );
+/// Runtime change to the live subscription set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionCommand {
+    Subscribe(Vec<String>),
+    Unsubscribe(Vec<String>),
+}
 
-        Self {
-            market_data_tx,
-            symbols,
-            api_key: credentials.api_key,
-            api_secret: credentials.api_secret,
-        }
+/// Cloneable handle for changing a running [`BinanceFeedHandler`]'s streams
+///
+/// Changes are sent as `SUBSCRIBE`/`UNSUBSCRIBE` on the open connection and
+/// are kept in the handler's subscription set, which is re-applied on reconnect.
+#[derive(Debug, Clone)]
+pub struct BinanceSubscriptionHandle {
+    command_tx: mpsc::UnboundedSender<SubscriptionCommand>,
+    streams: BinanceStreams,
+}
+
+impl BinanceSubscriptionHandle {
+    /// Subscribe to the configured stream types for a symbol (e.g. "BTCUSDT")
+    pub fn add_symbol(&self, symbol: &str) -> FeedResult<()> {
+        self.subscribe(self.streams.names_for(symbol))
+    }
+
+    /// Stream names `add_symbol`/`remove_symbol` change for a symbol
+    pub fn streams_for(&self, symbol: &str) -> Vec<String> {
+        self.streams.names_for(symbol)
+    }
+
+    /// Drop every stream of a symbol
+    pub fn remove_symbol(&self, symbol: &str) -> FeedResult<()> {
+        self.unsubscribe(self.streams.names_for(symbol))
+    }
+
+    /// Subscribe to raw stream names (e.g. "solusdt@bookTicker")
+    pub fn subscribe(&self, streams: Vec<String>) -> FeedResult<()> {
+        self.send(SubscriptionCommand::Subscribe(streams))
+    }
+
+    /// Unsubscribe from raw stream names
+    pub fn unsubscribe(&self, streams: Vec<String>) -> FeedResult<()> {
+        self.send(SubscriptionCommand::Unsubscribe(streams))
+    }
+
+    fn send(&self, command: SubscriptionCommand) -> FeedResult<()> {
+        self.command_tx
+            .send(command)
+            .map_err(|_| FeedError::HandlerStopped {
+                exchange: "Binance".to_string(),
+            })
     }
+}
 
+pub struct BinanceFeedHandler {
+    market_data_tx: broadcast::Sender<MarketEvent>,
+    symbols: Vec<String>,
+    streams: BinanceStreams,
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+    command_tx: mpsc::UnboundedSender<SubscriptionCommand>,
+    command_rx: tokio::sync::Mutex<mpsc::UnboundedReceiver<SubscriptionCommand>>,
+    ws_url: String,
+    reconnect: ReconnectPolicy,
+    health: Arc<FeedHealth>,
+    shutdown: CancellationToken,
+}
+
+impl BinanceFeedHandler {
     /// Create a public-only handler (no authentication, public streams only)
     ///
     /// Use this for market data that doesn't require authentication
     ///
     /// # Arguments
+    /// * `config` - Binance configuration (WebSocket and REST URLs)
     /// * `market_data_tx` - Broadcast channel sender for market data
-    /// * `symbols` - Optional list of trading pairs (e.g., ["btcusdt", "ethusdt"])
-    ///               If None, defaults to ["btcusdt", "ethusdt"]
+    /// * `symbols` - Binance trading pairs from `[markets]` (e.g., ["BTCUSDT", "ETHUSDT"])
     pub fn new_public(
-        market_data_tx: broadcast::Sender<MarketTick>,
-        symbols: Option<Vec<String>>,
+        config: BinanceConfig,
+        market_data_tx: broadcast::Sender<MarketEvent>,
+        symbols: Vec<String>,
     ) -> Self {
-        let symbols = symbols.unwrap_or_else(|| vec!["btcusdt".to_string(), "ethusdt".to_string()]);
-
         tracing::info!("Binance Feed Handler initialized in PUBLIC mode (no authentication)");
 
+        let streams = BinanceStreams::default();
+        let subscriptions = Arc::new(Mutex::new(stream_set(&symbols, &streams)));
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+
         Self {
             market_data_tx,
             symbols,
-            api_key: String::new(),
-            api_secret: String::new(),
+            streams,
+            subscriptions,
+            command_tx,
+            command_rx: tokio::sync::Mutex::new(command_rx),
+            ws_url: config.ws_url,
+            reconnect: ReconnectPolicy::default(),
+            health: Arc::new(FeedHealth::new()),
+            shutdown: CancellationToken::new(),
         }
     }
 
-    /// Start the WebSocket connection and begin streaming market data
-    pub async fn start(&self) -> FeedResult<()> {
-        loop {
-            match self.connect_and_stream().await {
-                Ok(_) => {
-                    tracing::warn!("Binance WebSocket connection closed normally, reconnecting...");
-                }
-                Err(e) => {
-                    tracing::error!("Binance WebSocket error: {:?}, reconnecting in 5s...", e);
-                    sleep(Duration::from_secs(5)).await;
-                }
-            }
+    /// Select which streams to subscribe per symbol (defaults to `@aggTrade` only)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let handler = BinanceFeedHandler::new_public(config, market_data_tx, symbols)
+    ///     .with_streams(BinanceStreams::from_settings(&settings));
+    /// ```
+    pub fn with_streams(mut self, streams: BinanceStreams) -> Self {
+        *self.subscriptions.lock().unwrap() = stream_set(&self.symbols, &streams);
+        self.streams = streams;
+        self
+    }
+
+    /// Override the reconnect/keep-alive policy (defaults to [`ReconnectPolicy::default`])
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Handle for adding/removing symbols and streams while the feed runs
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let control = handler.subscription_handle();
+    /// tokio::spawn(async move { handler.start().await });
+    /// control.add_symbol("SOLUSDT")?;
+    /// ```
+    pub fn subscription_handle(&self) -> BinanceSubscriptionHandle {
+        BinanceSubscriptionHandle {
+            command_tx: self.command_tx.clone(),
+            streams: self.streams.clone(),
         }
     }
 
     /// Internal method to handle connection and streaming
-    async fn connect_and_stream(&self) -> FeedResult<()> {
-        // Build WebSocket URL for combined streams
-        let streams: Vec<String> = self
-            .symbols
-            .iter()
-            .map(|s| format!("{}@aggTrade", s.to_lowercase()))
-            .collect();
-
-        let stream_names = streams.join("/");
-        let ws_url = format!(
-            "wss://stream.binance.com:9443/stream?streams={}",
-            stream_names
-        );
+    async fn connect_and_stream(&self, reconnector: &mut Reconnector) -> FeedResult<()> {
+        // Commands are only consumed by the live connection
+        let mut command_rx = self.command_rx.lock().await;
 
+        let ws_url = format!("{}/stream", self.ws_url);
         tracing::info!("Connecting to Binance WebSocket: {}", ws_url);
 
         let (ws_stream, _) = connect_async(&ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // Re-apply the full subscription set (initial symbols + runtime changes)
+        let mut request_id = 0;
+        let current: Vec<String> = self.subscriptions.lock().unwrap().iter().cloned().collect();
+        if !current.is_empty() {
+            request_id += 1;
+            write
+                .send(subscription_request(
+                    "SUBSCRIBE",
+                    current.clone(),
+                    request_id,
+                )?)
+                .await?;
+        }
 
         tracing::info!(
-            "✅ Connected to Binance WebSocket, subscribed to {} symbols: {:?}",
-            self.symbols.len(),
-            self.symbols
+            "✅ Connected to Binance WebSocket, subscribed to {} streams: {:?}",
+            current.len(),
+            current
         );
 
-        let (mut _write, mut read) = ws_stream.split();
-
-        // Process incoming messages
-        while let Some(message) = read.next().await {
-            match message {
-                Ok(Message::Text(text)) => {
-                    if let Err(e) = self.process_message(&text).await {
-                        tracing::warn!("Failed to process message: {:?}", e);
+        let mut ping = reconnector.ping_timer();
+        loop {
+            tokio::select! {
+                message = reconnector.next_message(&mut read) => {
+                    let Some(message) = message? else { break };
+                    match message {
+                        Message::Text(text) => {
+                            if let Err(e) = self.process_message(&text).await {
+                                tracing::warn!("Failed to process message: {:?}", e);
+                            }
+                        }
+                        Message::Ping(payload) => {
+                            tracing::debug!("Received ping, replying with pong");
+                            write.send(Message::Pong(payload)).await?;
+                        }
+                        Message::Pong(_) => {
+                            tracing::debug!("Received pong");
+                        }
+                        Message::Close(frame) => {
+                            tracing::info!("WebSocket closed: {:?}", frame);
+                            break;
+                        }
+                        _ => {}
                     }
                 }
-                Ok(Message::Ping(ping)) => {
-                    tracing::debug!("Received ping: {:?}", ping);
-                }
-                Ok(Message::Pong(_)) => {
-                    tracing::debug!("Received pong");
-                }
-                Ok(Message::Close(frame)) => {
-                    tracing::info!("WebSocket closed: {:?}", frame);
-                    break;
+                _ = ping.tick() => {
+                    write.send(Message::Ping(Vec::new())).await?;
                 }
-                Err(e) => {
-                    tracing::error!("WebSocket error: {:?}", e);
-                    return Err(e.into());
+                Some(command) = command_rx.recv() => {
+                    let (method, streams) = self.apply_command(command);
+                    if streams.is_empty() {
+                        continue;
+                    }
+                    request_id += 1;
+                    tracing::info!("📡 Binance {} {:?} (id {})", method, streams, request_id);
+                    write
+                        .send(subscription_request(method, streams, request_id)?)
+                        .await?;
                 }
-                _ => {}
             }
         }
 
         Ok(())
     }
 
-    /// Process a single message from Binance WebSocket
-    async fn process_message(&self, text: &str) -> FeedResult<()> {
-        // Binance combined streams wrap messages in a data field
-        #[derive(Debug, Deserialize)]
-        struct StreamWrapper {
-            stream: String,
-            data: serde_json::Value,
+    /// Update the subscription set, returning the method and the streams that changed
+    fn apply_command(&self, command: SubscriptionCommand) -> (&'static str, Vec<String>) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        match command {
+            SubscriptionCommand::Subscribe(streams) => (
+                "SUBSCRIBE",
+                streams
+                    .into_iter()
+                    .filter(|stream| subscriptions.insert(stream.clone()))
+                    .collect(),
+            ),
+            SubscriptionCommand::Unsubscribe(streams) => (
+                "UNSUBSCRIBE",
+                streams
+                    .into_iter()
+                    .filter(|stream| subscriptions.remove(stream))
+                    .collect(),
+            ),
         }
+    }
 
-        let wrapper: StreamWrapper = serde_json::from_str(text)?;
-
-        // Parse the aggregated trade message
-        let agg_trade: BinanceAggTradeMessage = serde_json::from_value(wrapper.data)?;
+    /// Process a single message from Binance WebSocket
+    async fn process_message(&self, text: &str) -> FeedResult<()> {
+        let received_at = Utc::now();
 
-        // Convert to MarketTick
-        let market_tick = self.convert_to_market_tick(agg_trade)?;
+        // Binance combined streams wrap messages in a data field
+        let (stream, data) = match serde_json::from_str::<BinanceWsMessage>(text)? {
+            BinanceWsMessage::Stream { stream, data } => (stream, data),
+            BinanceWsMessage::Response { id } => {
+                tracing::debug!("Binance subscription request {} acknowledged", id);
+                return Ok(());
+            }
+            BinanceWsMessage::Error { error, id } => {
+                return Err(FeedError::SubscriptionFailed {
+                    symbol: format!("request {}", id.unwrap_or_default()),
+                    reason: format!("code {}: {}", error.code, error.msg),
+                });
+            }
+        };
+
+        // Stream names look like "btcusdt@aggTrade", "btcusdt@kline_1m", ...
+        let stream_type = stream.split('@').nth(1).unwrap_or_default();
+
+        let event = if stream_type == "aggTrade" {
+            let agg_trade: BinanceAggTradeMessage = serde_json::from_value(data)?;
+            MarketEvent::Trade(self.convert_to_market_tick(agg_trade, received_at)?)
+        } else if stream_type == "bookTicker" {
+            let ticker: BinanceBookTickerMessage = serde_json::from_value(data)?;
+            MarketEvent::Quote(Quote {
+                exchange: Exchange::Binance,
+                symbol: ticker.symbol,
+                bid_price: parse_decimal("bid price", &ticker.bid_price)?,
+                bid_quantity: parse_decimal("bid quantity", &ticker.bid_quantity)?,
+                ask_price: parse_decimal("ask price", &ticker.ask_price)?,
+                ask_quantity: parse_decimal("ask quantity", &ticker.ask_quantity)?,
+                // Spot bookTicker carries no event time
+                timestamp: received_at,
+                received_at,
+            })
+        } else if stream_type.starts_with("kline_") {
+            let msg: BinanceKlineMessage = serde_json::from_value(data)?;
+            MarketEvent::Kline(Kline {
+                exchange: Exchange::Binance,
+                symbol: msg.symbol,
+                open_time: parse_millis(msg.kline.open_time)?,
+                close_time: parse_millis(msg.kline.close_time)?,
+                interval: msg.kline.interval,
+                open: parse_decimal("open", &msg.kline.open)?,
+                high: parse_decimal("high", &msg.kline.high)?,
+                low: parse_decimal("low", &msg.kline.low)?,
+                close: parse_decimal("close", &msg.kline.close)?,
+                volume: parse_decimal("volume", &msg.kline.volume)?,
+                is_closed: msg.kline.is_closed,
+            })
+        } else {
+            tracing::debug!("Ignoring unhandled Binance stream: {}", stream);
+            return Ok(());
+        };
+
+        tracing::debug!("📊 Market event: {:?}", event);
 
         // Broadcast to all subscribers
-        let _ = self.market_data_tx.send(market_tick.clone());
-
-        tracing::debug!(
-            "📊 Market tick: {} @ ${} (vol: {})",
-            market_tick.symbol,
-            market_tick.price,
-            market_tick.volume
-        );
+        let _ = self.market_data_tx.send(event);
 
         Ok(())
     }
 
     /// Convert Binance message to internal MarketTick format
-    fn convert_to_market_tick(&self, msg: BinanceAggTradeMessage) -> FeedResult<MarketTick> {
-        let price = msg
-            .price
-            .parse::<f64>()
-            .map_err(|source| FeedError::NumberParseError {
-                field: "price".to_string(),
-                source,
-            })?;
-        let volume = msg
-            .quantity
-            .parse::<f64>()
-            .map_err(|source| FeedError::NumberParseError {
-                field: "quantity".to_string(),
-                source,
-            })?;
-
+    fn convert_to_market_tick(
+        &self,
+        msg: BinanceAggTradeMessage,
+        received_at: DateTime<Utc>,
+    ) -> FeedResult<MarketTick> {
         Ok(MarketTick {
             id: Uuid::new_v4(),
             symbol: msg.symbol,
-            price,
-            volume,
-            timestamp: Utc::now(),
+            price: parse_decimal("price", &msg.price)?,
+            volume: parse_decimal("quantity", &msg.quantity)?,
+            timestamp: parse_millis(msg.trade_time)?,
+            received_at,
             exchange: Exchange::Binance,
+            trade_id: Some(msg.agg_trade_id.to_string()),
+            side: Some(if msg.buyer_is_maker {
+                OrderSide::Sell
+            } else {
+                OrderSide::Buy
+            }),
         })
     }
 }
+
+#[async_trait]
+impl FeedHandler for BinanceFeedHandler {
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    /// Start the WebSocket connection and begin streaming market data
+    ///
+    /// Returns `FeedError::ReconnectAttemptsExhausted` once the reconnect policy gives up.
+    async fn start(&self) -> FeedResult<()> {
+        let mut reconnector = Reconnector::new(
+            "Binance",
+            self.reconnect.clone(),
+            self.health.clone(),
+            self.shutdown.clone(),
+        );
+        while !self.shutdown.is_cancelled() {
+            let outcome = tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                outcome = self.connect_and_stream(&mut reconnector) => outcome,
+            };
+            reconnector.backoff(outcome).await?;
+        }
+
+        self.health.set_connected(false);
+        Ok(())
+    }
+
+    fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    fn health(&self) -> &FeedHealth {
+        &self.health
+    }
+
+    fn subscriptions(&self) -> Vec<String> {
+        self.subscriptions.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Parse a Binance decimal string into a fixed-point price or quantity
+pub(super) fn parse_decimal<T: FromStr>(field: &str, value: &str) -> FeedResult<T> {
+    value
+        .parse::<T>()
+        .map_err(|_| FeedError::DecimalParseError {
+            field: field.to_string(),
+            value: value.to_string(),
+        })
+}
+
+/// Parse a Binance millisecond timestamp
+fn parse_millis(ms: i64) -> FeedResult<DateTime<Utc>> {
+    DateTime::from_timestamp_millis(ms)
+        .ok_or_else(|| FeedError::InvalidData(format!("invalid Binance timestamp: {}", ms)))
+}
+
+/// All stream names for a set of symbols
+fn stream_set(symbols: &[String], streams: &BinanceStreams) -> BTreeSet<String> {
+    symbols
+        .iter()
+        .flat_map(|symbol| streams.names_for(symbol))
+        .collect()
+}
+
+/// Build a `SUBSCRIBE`/`UNSUBSCRIBE` request frame
+fn subscription_request(method: &str, params: Vec<String>, id: u64) -> FeedResult<Message> {
+    Ok(Message::Text(serde_json::to_string(&SubscribeMessage {
+        method: method.to_string(),
+        params,
+        id,
+    })?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testnet() -> BinanceConfig {
+        BinanceConfig {
+            ws_url: "wss://testnet.binance.vision".to_string(),
+            rest_url: "https://testnet.binance.vision".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ws_url_comes_from_config() {
+        let (tx, _rx) = broadcast::channel(16);
+        let handler = BinanceFeedHandler::new_public(testnet(), tx, Vec::new());
+        assert_eq!(handler.ws_url, "wss://testnet.binance.vision");
+    }
+
+    #[test]
+    fn test_subscription_commands_update_set() {
+        let (tx, _rx) = broadcast::channel(16);
+        let handler = BinanceFeedHandler::new_public(testnet(), tx, vec!["BTCUSDT".to_string()])
+            .with_streams(BinanceStreams {
+                agg_trade: true,
+                book_ticker: true,
+                kline_intervals: Vec::new(),
+            });
+        assert_eq!(
+            handler.subscriptions(),
+            vec!["btcusdt@aggTrade", "btcusdt@bookTicker"]
+        );
+
+        // Already-subscribed streams are not requested twice
+        let (method, streams) = handler.apply_command(SubscriptionCommand::Subscribe(vec![
+            "btcusdt@aggTrade".to_string(),
+            "ethusdt@aggTrade".to_string(),
+        ]));
+        assert_eq!(method, "SUBSCRIBE");
+        assert_eq!(streams, vec!["ethusdt@aggTrade"]);
+
+        let (method, streams) = handler.apply_command(SubscriptionCommand::Unsubscribe(
+            BinanceStreams::default().names_for("BTCUSDT"),
+        ));
+        assert_eq!(method, "UNSUBSCRIBE");
+        assert_eq!(streams, vec!["btcusdt@aggTrade"]);
+        assert_eq!(
+            handler.subscriptions(),
+            vec!["btcusdt@bookTicker", "ethusdt@aggTrade"]
+        );
+    }
+
+    #[test]
+    fn test_subscription_replies_parsed() {
+        assert!(matches!(
+            serde_json::from_str::<BinanceWsMessage>(r#"{"result":null,"id":3}"#).unwrap(),
+            BinanceWsMessage::Response { id: 3 }
+        ));
+        assert!(matches!(
+            serde_json::from_str::<BinanceWsMessage>(
+                r#"{"error":{"code":2,"msg":"Invalid request"},"id":4}"#
+            )
+            .unwrap(),
+            BinanceWsMessage::Error { id: Some(4), .. }
+        ));
+    }
+}
@@ -0,0 +1,619 @@
+// Binance order book depth feed handler (diff stream + REST snapshot)
+
+use super::binance::{parse_decimal, BinanceConfig};
+use super::error::FeedResult;
+use super::handler::{FeedHandler, FeedHealth};
+use super::reconnect::{ReconnectPolicy, Reconnector};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use futures::{SinkExt, StreamExt};
+use kairos_domain::{Exchange, MarketEvent, OrderBook, OrderBookDelta, OrderBookLevel};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
+
+/// Depth of the REST snapshot used to bootstrap each local book
+const SNAPSHOT_LIMIT: u32 = 1000;
+
+/// Maximum number of diff events buffered while waiting for a snapshot
+const MAX_BUFFERED_UPDATES: usize = 1000;
+
+/// A book that stayed in sync this long starts its resync backoff over
+const STABLE_SYNC: Duration = Duration::from_secs(60);
+
+/// Shared, per-symbol view of the top of each local order book
+pub type OrderBookStore = Arc<DashMap<String, OrderBook>>;
+
+/// REST depth snapshot (`GET /api/v3/depth`)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DepthSnapshot {
+    last_update_id: u64,
+    bids: Vec<[String; 2]>,
+    asks: Vec<[String; 2]>,
+}
+
+/// Diff depth stream event (`<symbol>@depth@100ms`)
+#[derive(Debug, Clone, Deserialize)]
+struct DepthUpdate {
+    #[serde(rename = "E")]
+    event_time: i64,
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "U")]
+    first_update_id: u64,
+    #[serde(rename = "u")]
+    final_update_id: u64,
+    #[serde(rename = "b")]
+    bids: Vec<[String; 2]>,
+    #[serde(rename = "a")]
+    asks: Vec<[String; 2]>,
+}
+
+/// Result of feeding a diff event into a [`DepthSync`]
+#[derive(Debug, Clone)]
+enum SyncOutcome {
+    /// No snapshot yet, event kept for replay
+    Buffered,
+    /// Event is older than the book and was dropped
+    Stale,
+    /// Event was applied to the book; carries the delta to publish
+    /// (a full-book snapshot after a resync)
+    Applied(OrderBookDelta),
+    /// Update IDs are not contiguous, the book must be resynced
+    Gap,
+}
+
+/// Snapshot + diff synchronisation state for a single symbol
+///
+/// Follows Binance's "how to manage a local order book correctly" procedure:
+/// buffer diffs until a snapshot arrives, drop events with `u <= lastUpdateId`,
+/// and resync whenever an event's `U` skips past `lastUpdateId + 1`.
+#[derive(Debug)]
+struct DepthSync {
+    book: OrderBook,
+    synced: bool,
+    buffer: Vec<DepthUpdate>,
+    /// Consecutive resyncs without a stable book, driving the resync backoff
+    resyncs: u32,
+    synced_at: Option<Instant>,
+    /// A REST snapshot has been requested and not delivered yet
+    snapshot_pending: bool,
+}
+
+impl DepthSync {
+    fn new(symbol: &str) -> Self {
+        Self {
+            book: OrderBook::new(Exchange::Binance, symbol),
+            synced: false,
+            buffer: Vec::new(),
+            resyncs: 0,
+            synced_at: None,
+            snapshot_pending: false,
+        }
+    }
+
+    /// Claim the snapshot request slot; false while one is already in flight
+    fn begin_snapshot(&mut self) -> bool {
+        !std::mem::replace(&mut self.snapshot_pending, true)
+    }
+
+    /// Count a resync, returning its 1-based attempt number
+    fn next_resync(&mut self) -> u32 {
+        if self.synced_at.is_some_and(|at| at.elapsed() >= STABLE_SYNC) {
+            self.resyncs = 0;
+        }
+        self.resyncs += 1;
+        self.resyncs
+    }
+
+    fn reset(&mut self) {
+        self.book.clear();
+        self.synced = false;
+        self.buffer.clear();
+    }
+
+    fn on_update(&mut self, update: DepthUpdate) -> FeedResult<SyncOutcome> {
+        if !self.synced {
+            if self.buffer.len() >= MAX_BUFFERED_UPDATES {
+                self.buffer.remove(0);
+            }
+            self.buffer.push(update);
+            return Ok(SyncOutcome::Buffered);
+        }
+
+        let last = self.book.sequence;
+        if update.final_update_id <= last {
+            return Ok(SyncOutcome::Stale);
+        }
+        if update.first_update_id > last + 1 {
+            return Ok(SyncOutcome::Gap);
+        }
+
+        let delta = OrderBookDelta {
+            exchange: Exchange::Binance,
+            symbol: update.symbol,
+            bids: parse_levels(&update.bids)?,
+            asks: parse_levels(&update.asks)?,
+            sequence: update.final_update_id,
+            is_snapshot: false,
+            timestamp: DateTime::from_timestamp_millis(update.event_time).unwrap_or_else(Utc::now),
+        };
+        self.book.apply_delta(&delta);
+
+        Ok(SyncOutcome::Applied(delta))
+    }
+
+    /// Load a REST snapshot and replay buffered diffs on top of it
+    fn apply_snapshot(&mut self, snapshot: DepthSnapshot) -> FeedResult<SyncOutcome> {
+        self.book.apply_delta(&OrderBookDelta {
+            exchange: Exchange::Binance,
+            symbol: self.book.symbol.clone(),
+            bids: parse_levels(&snapshot.bids)?,
+            asks: parse_levels(&snapshot.asks)?,
+            sequence: snapshot.last_update_id,
+            is_snapshot: true,
+            timestamp: Utc::now(),
+        });
+        self.synced = true;
+
+        for update in std::mem::take(&mut self.buffer) {
+            if let SyncOutcome::Gap = self.on_update(update)? {
+                return Ok(SyncOutcome::Gap);
+            }
+        }
+
+        self.synced_at = Some(Instant::now());
+        Ok(SyncOutcome::Applied(self.book.snapshot()))
+    }
+}
+
+/// Feed handler maintaining a local L2 order book per symbol
+pub struct BinanceDepthFeedHandler {
+    market_data_tx: broadcast::Sender<MarketEvent>,
+    books: OrderBookStore,
+    symbols: Vec<String>,
+    depth: usize,
+    ws_url: String,
+    rest_url: String,
+    http: reqwest::Client,
+    reconnect: ReconnectPolicy,
+    health: Arc<FeedHealth>,
+    shutdown: CancellationToken,
+}
+
+impl BinanceDepthFeedHandler {
+    /// Create a new depth feed handler
+    ///
+    /// # Arguments
+    /// * `config` - Binance configuration (WebSocket and REST URLs)
+    /// * `market_data_tx` - Bus receiving `MarketEvent::BookDelta` for every applied update
+    /// * `symbols` - Binance trading pairs from `[markets]` (e.g., ["BTCUSDT", "ETHUSDT"])
+    /// * `depth` - Number of levels per side published to the shared store
+    ///   (`TradingSettings::orderbook_depth`)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let config = BinanceConfig::from_settings(&settings);
+    /// let handler = BinanceDepthFeedHandler::new(
+    ///     config,
+    ///     market_data_tx,
+    ///     symbols,
+    ///     settings.trading.orderbook_depth as usize,
+    /// );
+    /// let books = handler.books();
+    /// ```
+    pub fn new(
+        config: BinanceConfig,
+        market_data_tx: broadcast::Sender<MarketEvent>,
+        symbols: Vec<String>,
+        depth: usize,
+    ) -> Self {
+        tracing::info!(
+            "Binance Depth Feed Handler initialized for {} symbols (depth: {})",
+            symbols.len(),
+            depth
+        );
+
+        Self {
+            market_data_tx,
+            books: Arc::new(DashMap::new()),
+            symbols,
+            depth,
+            ws_url: config.ws_url,
+            rest_url: config.rest_url,
+            http: reqwest::Client::new(),
+            reconnect: ReconnectPolicy::default(),
+            health: Arc::new(FeedHealth::new()),
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    /// Shared store with the latest top-of-book view per symbol (uppercase, e.g. "BTCUSDT")
+    pub fn books(&self) -> OrderBookStore {
+        self.books.clone()
+    }
+
+    /// Override the reconnect/keep-alive policy (defaults to [`ReconnectPolicy::default`])
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Internal method to handle connection, snapshot bootstrap and streaming
+    async fn connect_and_stream(&self, reconnector: &mut Reconnector) -> FeedResult<()> {
+        let streams: Vec<String> = self
+            .symbols
+            .iter()
+            .map(|s| format!("{}@depth@100ms", s.to_lowercase()))
+            .collect();
+        let ws_url = format!("{}/stream?streams={}", self.ws_url, streams.join("/"));
+
+        tracing::info!("Connecting to Binance depth WebSocket: {}", ws_url);
+
+        let (ws_stream, _) = connect_async(&ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // Every reconnect starts from scratch: buffer diffs, then fetch snapshots
+        let mut syncs: HashMap<String, DepthSync> = self
+            .symbols
+            .iter()
+            .map(|s| {
+                let symbol = s.to_uppercase();
+                (symbol.clone(), DepthSync::new(&symbol))
+            })
+            .collect();
+        self.books.clear();
+
+        let (snapshot_tx, mut snapshot_rx) = mpsc::channel(self.symbols.len().max(1));
+        for (symbol, sync) in syncs.iter_mut() {
+            sync.begin_snapshot();
+            self.request_snapshot(symbol.clone(), Duration::ZERO, snapshot_tx.clone());
+        }
+
+        tracing::info!(
+            "✅ Connected to Binance depth WebSocket for {} symbols",
+            self.symbols.len()
+        );
+
+        let mut ping = reconnector.ping_timer();
+        loop {
+            tokio::select! {
+                message = reconnector.next_message(&mut read) => {
+                    let Some(message) = message? else { break };
+                    match message {
+                        Message::Text(text) => {
+                            if let Err(e) = self.process_message(&text, &mut syncs, &snapshot_tx) {
+                                tracing::warn!("Failed to process depth message: {:?}", e);
+                            }
+                        }
+                        Message::Ping(payload) => {
+                            write.send(Message::Pong(payload)).await?;
+                        }
+                        Message::Close(frame) => {
+                            tracing::info!("Depth WebSocket closed: {:?}", frame);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                _ = ping.tick() => {
+                    write.send(Message::Ping(Vec::new())).await?;
+                }
+                Some((symbol, result)) = snapshot_rx.recv() => {
+                    let Some(sync) = syncs.get_mut(&symbol) else { continue };
+                    sync.snapshot_pending = false;
+                    let outcome = result.and_then(|snapshot| sync.apply_snapshot(snapshot));
+                    match outcome {
+                        Ok(SyncOutcome::Gap) | Err(_) => {
+                            if let Err(e) = outcome {
+                                tracing::warn!("Snapshot for {} failed: {:?}", symbol, e);
+                            } else {
+                                tracing::warn!("Buffered depth for {} does not bridge snapshot, resyncing", symbol);
+                            }
+                            self.resync(symbol, sync, snapshot_tx.clone());
+                        }
+                        Ok(SyncOutcome::Applied(snapshot)) => {
+                            tracing::info!(
+                                "📚 {} order book synced (lastUpdateId: {})",
+                                symbol,
+                                sync.book.sequence
+                            );
+                            self.publish(&symbol, sync, snapshot);
+                        }
+                        Ok(_) => {}
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process a single diff event from the combined stream
+    fn process_message(
+        &self,
+        text: &str,
+        syncs: &mut HashMap<String, DepthSync>,
+        snapshot_tx: &mpsc::Sender<(String, FeedResult<DepthSnapshot>)>,
+    ) -> FeedResult<()> {
+        #[derive(Debug, Deserialize)]
+        struct StreamWrapper {
+            data: DepthUpdate,
+        }
+
+        let update = serde_json::from_str::<StreamWrapper>(text)?.data;
+        let symbol = update.symbol.clone();
+        let Some(sync) = syncs.get_mut(&symbol) else {
+            return Ok(());
+        };
+
+        match sync.on_update(update)? {
+            SyncOutcome::Applied(delta) => self.publish(&symbol, sync, delta),
+            SyncOutcome::Gap => {
+                tracing::warn!("Depth update gap detected for {}, resyncing", symbol);
+                self.resync(symbol, sync, snapshot_tx.clone());
+            }
+            SyncOutcome::Buffered | SyncOutcome::Stale => {}
+        }
+
+        Ok(())
+    }
+
+    /// Publish the top `depth` levels of a synced book to the shared store
+    /// and broadcast the change on the market data bus
+    fn publish(&self, symbol: &str, sync: &DepthSync, delta: OrderBookDelta) {
+        let view = sync.book.truncated(self.depth);
+        if let (Some(bid), Some(ask)) = (view.best_bid(), view.best_ask()) {
+            tracing::trace!("📚 {} bid {} / ask {}", symbol, bid.price, ask.price);
+        }
+        self.books.insert(symbol.to_string(), view);
+
+        let _ = self.market_data_tx.send(MarketEvent::BookDelta(delta));
+    }
+
+    /// Drop a book that lost sync and fetch a new snapshot after a backoff
+    ///
+    /// Snapshots are weight-heavy REST calls, so repeated resyncs of the same
+    /// book back off like reconnects instead of hitting the rate limit, and a
+    /// book whose snapshot is still in flight waits for it: the diffs buffered
+    /// meanwhile are replayed on top, and a snapshot too old to bridge them
+    /// triggers the next resync.
+    fn resync(
+        &self,
+        symbol: String,
+        sync: &mut DepthSync,
+        snapshot_tx: mpsc::Sender<(String, FeedResult<DepthSnapshot>)>,
+    ) {
+        sync.reset();
+        self.books.remove(&symbol);
+        if !sync.begin_snapshot() {
+            tracing::debug!(
+                "{} snapshot already in flight, not requesting another",
+                symbol
+            );
+            return;
+        }
+        let delay = self.reconnect.backoff_delay(sync.next_resync());
+        tracing::info!("🔄 Requesting {} snapshot in {:?}", symbol, delay);
+        self.request_snapshot(symbol, delay, snapshot_tx);
+    }
+
+    /// Fetch a REST snapshot in the background and deliver it to the stream loop
+    fn request_snapshot(
+        &self,
+        symbol: String,
+        delay: Duration,
+        snapshot_tx: mpsc::Sender<(String, FeedResult<DepthSnapshot>)>,
+    ) {
+        let http = self.http.clone();
+        let url = format!(
+            "{}/api/v3/depth?symbol={}&limit={}",
+            self.rest_url, symbol, SNAPSHOT_LIMIT
+        );
+
+        tokio::spawn(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            let result = async {
+                let response = http.get(&url).send().await?.error_for_status()?;
+                Ok(response.json::<DepthSnapshot>().await?)
+            }
+            .await;
+            let _ = snapshot_tx.send((symbol, result)).await;
+        });
+    }
+}
+
+#[async_trait]
+impl FeedHandler for BinanceDepthFeedHandler {
+    fn name(&self) -> &'static str {
+        "binance_depth"
+    }
+
+    /// Start the WebSocket connection and keep the local books in sync
+    ///
+    /// Returns `FeedError::ReconnectAttemptsExhausted` once the reconnect policy gives up.
+    async fn start(&self) -> FeedResult<()> {
+        let mut reconnector = Reconnector::new(
+            "Binance depth",
+            self.reconnect.clone(),
+            self.health.clone(),
+            self.shutdown.clone(),
+        );
+        while !self.shutdown.is_cancelled() {
+            let outcome = tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                outcome = self.connect_and_stream(&mut reconnector) => outcome,
+            };
+            reconnector.backoff(outcome).await?;
+        }
+
+        self.health.set_connected(false);
+        Ok(())
+    }
+
+    fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    fn health(&self) -> &FeedHealth {
+        &self.health
+    }
+
+    fn subscriptions(&self) -> Vec<String> {
+        self.symbols
+            .iter()
+            .map(|symbol| format!("{}@depth@100ms", symbol.to_lowercase()))
+            .collect()
+    }
+}
+
+/// Parse `[price, quantity]` string pairs into book levels
+fn parse_levels(levels: &[[String; 2]]) -> FeedResult<Vec<OrderBookLevel>> {
+    levels
+        .iter()
+        .map(|[price, quantity]| {
+            Ok(OrderBookLevel::new(
+                parse_decimal("depth price", price)?,
+                parse_decimal("depth quantity", quantity)?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: &str, quantity: &str) -> [String; 2] {
+        [price.to_string(), quantity.to_string()]
+    }
+
+    fn update(
+        first: u64,
+        last: u64,
+        bids: Vec<[String; 2]>,
+        asks: Vec<[String; 2]>,
+    ) -> DepthUpdate {
+        DepthUpdate {
+            event_time: 1_700_000_000_000,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            bids,
+            asks,
+        }
+    }
+
+    fn book_level(price: &str, quantity: &str) -> OrderBookLevel {
+        OrderBookLevel::new(price.parse().unwrap(), quantity.parse().unwrap())
+    }
+
+    fn snapshot(last_update_id: u64) -> DepthSnapshot {
+        DepthSnapshot {
+            last_update_id,
+            bids: vec![level("100.0", "1.0"), level("99.0", "2.0")],
+            asks: vec![level("101.0", "1.5")],
+        }
+    }
+
+    #[test]
+    fn test_buffered_updates_replayed_after_snapshot() {
+        let mut sync = DepthSync::new("BTCUSDT");
+
+        // Older than snapshot: dropped on replay
+        let stale = update(90, 100, vec![level("98.0", "5.0")], vec![]);
+        // Bridges lastUpdateId + 1
+        let bridge = update(
+            101,
+            105,
+            vec![level("100.0", "0")],
+            vec![level("101.0", "3.0")],
+        );
+
+        assert!(matches!(
+            sync.on_update(stale).unwrap(),
+            SyncOutcome::Buffered
+        ));
+        assert!(matches!(
+            sync.on_update(bridge).unwrap(),
+            SyncOutcome::Buffered
+        ));
+
+        // The replayed book is published as a single snapshot
+        let SyncOutcome::Applied(published) = sync.apply_snapshot(snapshot(102)).unwrap() else {
+            panic!("expected snapshot to sync the book");
+        };
+        assert!(published.is_snapshot);
+        assert_eq!(published.sequence, 105);
+        assert_eq!(published.bids, vec![book_level("99", "2")]);
+
+        assert_eq!(sync.book.sequence, 105);
+        assert_eq!(sync.book.best_bid(), Some(book_level("99", "2")));
+        assert_eq!(sync.book.best_ask(), Some(book_level("101", "3")));
+    }
+
+    #[test]
+    fn test_stale_and_gap_detection() {
+        let mut sync = DepthSync::new("BTCUSDT");
+        sync.apply_snapshot(snapshot(200)).unwrap();
+
+        assert!(matches!(
+            sync.on_update(update(150, 200, vec![], vec![])).unwrap(),
+            SyncOutcome::Stale
+        ));
+        assert!(matches!(
+            sync.on_update(update(201, 203, vec![], vec![])).unwrap(),
+            SyncOutcome::Applied(delta) if !delta.is_snapshot && delta.sequence == 203
+        ));
+        assert!(matches!(
+            sync.on_update(update(210, 215, vec![], vec![])).unwrap(),
+            SyncOutcome::Gap
+        ));
+    }
+
+    #[test]
+    fn test_truncated_view() {
+        let mut sync = DepthSync::new("BTCUSDT");
+        sync.apply_snapshot(snapshot(1)).unwrap();
+
+        let view = sync.book.truncated(1);
+        assert_eq!(
+            view.bids().collect::<Vec<_>>(),
+            vec![book_level("100", "1")]
+        );
+        assert_eq!(view.asks().count(), 1);
+    }
+
+    #[test]
+    fn test_resync_backoff_restarts_after_stable_sync() {
+        let mut sync = DepthSync::new("BTCUSDT");
+        assert_eq!(sync.next_resync(), 1);
+        assert_eq!(sync.next_resync(), 2);
+
+        // A book that resyncs right away keeps backing off
+        sync.apply_snapshot(snapshot(1)).unwrap();
+        assert_eq!(sync.next_resync(), 3);
+
+        sync.apply_snapshot(snapshot(2)).unwrap();
+        sync.synced_at = Instant::now().checked_sub(STABLE_SYNC);
+        assert_eq!(sync.next_resync(), 1);
+    }
+    #[test]
+    fn test_one_snapshot_in_flight_per_symbol() {
+        let mut sync = DepthSync::new("BTCUSDT");
+        assert!(sync.begin_snapshot());
+        assert!(!sync.begin_snapshot());
+
+        // Delivery frees the slot for the next resync
+        sync.snapshot_pending = false;
+        assert!(sync.begin_snapshot());
+    }
+}
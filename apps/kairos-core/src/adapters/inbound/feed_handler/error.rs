@@ -1,10 +1,11 @@
 use thiserror::Error;
+use tokio_tungstenite::tungstenite;
 
 /// Feed handler errors
 #[derive(Error, Debug)]
 pub enum FeedError {
     #[error("WebSocket connection failed")]
-    ConnectionFailed(#[source] tokio_tungstenite::tungstenite::Error),
+    ConnectionFailed(#[source] Box<tungstenite::Error>),
 
     #[error("Failed to subscribe to {symbol}: {reason}")]
     SubscriptionFailed { symbol: String, reason: String },
@@ -18,18 +19,40 @@ pub enum FeedError {
     #[error("Missing API credentials for {exchange}")]
     MissingCredentials { exchange: String },
 
+    #[error("Authentication failed: {reason}")]
+    AuthenticationFailed { reason: String },
+
+    #[error("{exchange} feed handler is no longer running")]
+    HandlerStopped { exchange: String },
+
+    #[error("No data from {exchange} for {seconds}s")]
+    StaleConnection { exchange: String, seconds: u64 },
+
+    #[error("{exchange} WebSocket gave up after {attempts} reconnect attempts: {last_error}")]
+    ReconnectAttemptsExhausted {
+        exchange: String,
+        attempts: u32,
+        last_error: String,
+    },
+
+    /// Boxed: tungstenite's error is large and would bloat every `FeedResult`
     #[error("WebSocket error")]
-    WebSocketError(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocketError(#[source] Box<tungstenite::Error>),
 
     #[error("JSON error")]
     JsonError(#[from] serde_json::Error),
 
-    #[error("Failed to parse number: {field}")]
-    NumberParseError {
-        field: String,
-        #[source]
-        source: std::num::ParseFloatError,
-    },
+    #[error("REST request failed")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("Failed to parse decimal {field}: '{value}'")]
+    DecimalParseError { field: String, value: String },
+}
+
+impl From<tungstenite::Error> for FeedError {
+    fn from(error: tungstenite::Error) -> Self {
+        FeedError::WebSocketError(Box::new(error))
+    }
 }
 
 pub type FeedResult<T> = Result<T, FeedError>;
@@ -0,0 +1,146 @@
+// Common feed handler abstraction and connection health tracking
+
+use super::error::FeedResult;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A market or account data feed that can be run by the [`FeedSupervisor`]
+///
+/// [`FeedSupervisor`]: super::supervisor::FeedSupervisor
+#[async_trait]
+pub trait FeedHandler: Send + Sync {
+    /// Short identifier used in logs and health reports (e.g. "binance")
+    fn name(&self) -> &'static str;
+
+    /// Connect and stream until [`FeedHandler::stop`] is called (`Ok`) or the
+    /// reconnect policy gives up (`Err`)
+    async fn start(&self) -> FeedResult<()>;
+
+    /// Ask the running feed to disconnect; stopped feeds are not restarted
+    fn stop(&self);
+
+    /// Shared counters updated while the feed runs
+    fn health(&self) -> &FeedHealth;
+
+    /// Current connection health
+    fn status(&self) -> FeedStatus {
+        self.health().status(self.name())
+    }
+
+    /// Streams or channels the feed is subscribed to
+    fn subscriptions(&self) -> Vec<String>;
+}
+
+/// Point-in-time health of a single feed
+#[derive(Debug, Clone)]
+pub struct FeedStatus {
+    pub name: String,
+    pub connected: bool,
+    /// Time since the last frame, `None` if nothing was received yet
+    pub last_message_age: Option<Duration>,
+    pub messages_per_sec: f64,
+    pub total_messages: u64,
+    pub reconnects: u32,
+    pub restarts: u32,
+}
+
+/// Lock-free connection counters shared between a feed and its observers
+pub struct FeedHealth {
+    epoch: Instant,
+    connected: AtomicBool,
+    /// Milliseconds since `epoch` of the last frame, 0 = never
+    last_message_ms: AtomicU64,
+    messages: AtomicU64,
+    reconnects: AtomicU32,
+    restarts: AtomicU32,
+    rate: Mutex<RateSample>,
+}
+
+/// Message count at the previous rate computation
+struct RateSample {
+    at: Instant,
+    messages: u64,
+    rate: f64,
+}
+
+impl Default for FeedHealth {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            epoch: now,
+            connected: AtomicBool::new(false),
+            last_message_ms: AtomicU64::new(0),
+            messages: AtomicU64::new(0),
+            reconnects: AtomicU32::new(0),
+            restarts: AtomicU32::new(0),
+            rate: Mutex::new(RateSample {
+                at: now,
+                messages: 0,
+                rate: 0.0,
+            }),
+        }
+    }
+}
+
+impl FeedHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a received frame
+    pub fn record_message(&self) {
+        let elapsed = self.epoch.elapsed().as_millis() as u64;
+        self.last_message_ms
+            .store(elapsed.max(1), Ordering::Relaxed);
+        self.messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::Relaxed);
+    }
+
+    pub fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_restart(&self) {
+        self.restarts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Snapshot the counters; the message rate is averaged over at least one second
+    pub fn status(&self, name: &str) -> FeedStatus {
+        let messages = self.messages.load(Ordering::Relaxed);
+
+        let messages_per_sec = {
+            let mut sample = self.rate.lock().unwrap();
+            let elapsed = sample.at.elapsed();
+            if elapsed >= Duration::from_secs(1) {
+                sample.rate = (messages - sample.messages) as f64 / elapsed.as_secs_f64();
+                sample.at = Instant::now();
+                sample.messages = messages;
+            }
+            sample.rate
+        };
+
+        let last_message_age = match self.last_message_ms.load(Ordering::Relaxed) {
+            0 => None,
+            ms => Some(
+                self.epoch
+                    .elapsed()
+                    .saturating_sub(Duration::from_millis(ms)),
+            ),
+        };
+
+        FeedStatus {
+            name: name.to_string(),
+            connected: self.connected.load(Ordering::Relaxed),
+            last_message_age,
+            messages_per_sec,
+            total_messages: messages,
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            restarts: self.restarts.load(Ordering::Relaxed),
+        }
+    }
+}
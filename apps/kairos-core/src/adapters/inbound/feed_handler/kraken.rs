@@ -0,0 +1,523 @@
+// Kraken WebSocket v2 feed handler
+
+use super::error::{FeedError, FeedResult};
+use super::handler::{FeedHandler, FeedHealth};
+use super::reconnect::{ReconnectPolicy, Reconnector};
+use crate::config::Settings;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use kairos_domain::{
+    Exchange, MarketEvent, MarketTick, OrderBookDelta, OrderBookLevel, OrderSide, Price, Quantity,
+    Quote,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Public channels subscribed for every symbol
+const CHANNELS: [&str; 3] = ["trade", "ticker", "book"];
+
+/// Book depths accepted by the `book` channel
+const BOOK_DEPTHS: [usize; 5] = [10, 25, 100, 500, 1000];
+
+/// Kraken configuration (non-sensitive)
+#[derive(Debug, Clone)]
+pub struct KrakenConfig {
+    pub ws_url: String,
+}
+
+impl KrakenConfig {
+    /// Extract Kraken configuration from Settings
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let settings = Settings::new()?;
+    /// let config = KrakenConfig::from_settings(&settings);
+    /// ```
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            ws_url: settings.exchange.kraken_ws_url.clone(),
+        }
+    }
+}
+
+/// Request sent to the Kraken WebSocket (`subscribe`, `ping`)
+#[derive(Debug, Serialize)]
+struct KrakenRequest {
+    method: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<KrakenSubscription>,
+}
+
+#[derive(Debug, Serialize)]
+struct KrakenSubscription {
+    channel: &'static str,
+    symbol: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    depth: Option<usize>,
+}
+
+/// Incoming Kraken WebSocket message
+///
+/// Kraken sends either a channel push (`{"channel": "trade", "type": "update", "data": [...]}`)
+/// or a method response (`{"method": "subscribe", "success": true, ...}`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KrakenMessage {
+    Push {
+        channel: String,
+        #[serde(default)]
+        r#type: Option<String>,
+        #[serde(default)]
+        data: Vec<serde_json::Value>,
+    },
+    Response {
+        method: String,
+        #[serde(default)]
+        success: Option<bool>,
+        #[serde(default)]
+        error: Option<String>,
+        #[serde(default)]
+        result: Option<serde_json::Value>,
+    },
+}
+
+/// Kraken `trade` channel entry
+#[derive(Debug, Deserialize)]
+struct KrakenTrade {
+    symbol: String,
+    side: String,
+    price: Price,
+    qty: Quantity,
+    trade_id: u64,
+    timestamp: String,
+}
+
+/// Kraken `ticker` channel entry
+#[derive(Debug, Deserialize)]
+struct KrakenTicker {
+    symbol: String,
+    bid: Price,
+    bid_qty: Quantity,
+    ask: Price,
+    ask_qty: Quantity,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+/// Kraken `book` channel entry (snapshot or incremental update)
+#[derive(Debug, Deserialize)]
+struct KrakenBook {
+    symbol: String,
+    #[serde(default)]
+    bids: Vec<KrakenLevel>,
+    #[serde(default)]
+    asks: Vec<KrakenLevel>,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenLevel {
+    price: Price,
+    qty: Quantity,
+}
+
+pub struct KrakenFeedHandler {
+    market_data_tx: broadcast::Sender<MarketEvent>,
+    symbols: Vec<String>,
+    depth: usize,
+    ws_url: String,
+    /// Local sequence for book deltas (Kraken v2 books carry a checksum, not a sequence)
+    book_sequence: AtomicU64,
+    reconnect: ReconnectPolicy,
+    health: Arc<FeedHealth>,
+    shutdown: CancellationToken,
+}
+
+impl KrakenFeedHandler {
+    /// Create a new Kraken public feed handler (trades, ticker and book)
+    ///
+    /// # Arguments
+    /// * `config` - Kraken configuration (WebSocket URL)
+    /// * `market_data_tx` - Broadcast channel for market data
+    /// * `symbols` - Kraken pairs from `[markets]` (e.g., ["BTC/USDT", "ETH/USDT"])
+    /// * `depth` - Book depth, rounded up to a depth Kraken supports (10, 25, 100, 500, 1000)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let config = KrakenConfig::from_settings(&settings);
+    /// let handler = KrakenFeedHandler::new(
+    ///     config,
+    ///     market_data_tx,
+    ///     settings.markets.exchange_symbols(&Exchange::Kraken)?,
+    ///     settings.trading.orderbook_depth as usize,
+    /// );
+    /// ```
+    pub fn new(
+        config: KrakenConfig,
+        market_data_tx: broadcast::Sender<MarketEvent>,
+        symbols: Vec<String>,
+        depth: usize,
+    ) -> Self {
+        let depth = book_depth(depth);
+        tracing::info!(
+            "Kraken Feed Handler initialized for {} symbols (book depth: {})",
+            symbols.len(),
+            depth
+        );
+
+        Self {
+            market_data_tx,
+            symbols,
+            depth,
+            ws_url: config.ws_url,
+            book_sequence: AtomicU64::new(0),
+            reconnect: ReconnectPolicy::default(),
+            health: Arc::new(FeedHealth::new()),
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    /// Override the reconnect/keep-alive policy (defaults to [`ReconnectPolicy::default`])
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Internal method to handle connection, subscription and streaming
+    async fn connect_and_stream(&self, reconnector: &mut Reconnector) -> FeedResult<()> {
+        tracing::info!("Connecting to Kraken WebSocket: {}", self.ws_url);
+
+        let (ws_stream, _) = connect_async(&self.ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // One subscribe request per channel, covering every configured symbol
+        for channel in CHANNELS {
+            let subscribe = KrakenRequest {
+                method: "subscribe",
+                params: Some(KrakenSubscription {
+                    channel,
+                    symbol: self.symbols.clone(),
+                    depth: (channel == "book").then_some(self.depth),
+                }),
+            };
+            write
+                .send(Message::Text(serde_json::to_string(&subscribe)?))
+                .await?;
+        }
+
+        tracing::info!(
+            "✅ Connected to Kraken WebSocket, subscribed to {} symbols: {:?}",
+            self.symbols.len(),
+            self.symbols
+        );
+
+        let ping_request = serde_json::to_string(&KrakenRequest {
+            method: "ping",
+            params: None,
+        })?;
+        let mut ping = reconnector.ping_timer();
+        loop {
+            tokio::select! {
+                message = reconnector.next_message(&mut read) => {
+                    let Some(message) = message? else { break };
+                    match message {
+                        Message::Text(text) => {
+                            if let Err(e) = self.process_message(&text) {
+                                tracing::warn!("Failed to process message: {:?}", e);
+                            }
+                        }
+                        Message::Ping(payload) => {
+                            write.send(Message::Pong(payload)).await?;
+                        }
+                        Message::Close(frame) => {
+                            tracing::info!("WebSocket closed: {:?}", frame);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                _ = ping.tick() => {
+                    write.send(Message::Text(ping_request.clone())).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process a single message from Kraken WebSocket
+    fn process_message(&self, text: &str) -> FeedResult<()> {
+        for event in self.parse_message(text)? {
+            tracing::debug!("📊 Market event: {:?}", event);
+
+            // Broadcast to all subscribers
+            let _ = self.market_data_tx.send(event);
+        }
+        Ok(())
+    }
+
+    /// Decode a Kraken message into market events
+    fn parse_message(&self, text: &str) -> FeedResult<Vec<MarketEvent>> {
+        let received_at = Utc::now();
+
+        match serde_json::from_str::<KrakenMessage>(text)? {
+            KrakenMessage::Push {
+                channel,
+                r#type,
+                data,
+            } => {
+                let is_snapshot = r#type.as_deref() == Some("snapshot");
+                let mut events = Vec::with_capacity(data.len());
+                for entry in data {
+                    let event = match channel.as_str() {
+                        "trade" => MarketEvent::Trade(Self::convert_trade(
+                            serde_json::from_value(entry)?,
+                            received_at,
+                        )?),
+                        "ticker" => MarketEvent::Quote(Self::convert_ticker(
+                            serde_json::from_value(entry)?,
+                            received_at,
+                        )?),
+                        "book" => MarketEvent::BookDelta(self.convert_book(
+                            serde_json::from_value(entry)?,
+                            is_snapshot,
+                            received_at,
+                        )?),
+                        // heartbeat, status
+                        _ => continue,
+                    };
+                    events.push(event);
+                }
+                Ok(events)
+            }
+            KrakenMessage::Response {
+                method,
+                success,
+                error,
+                result,
+            } => match (method.as_str(), success) {
+                ("subscribe", Some(false)) => Err(FeedError::SubscriptionFailed {
+                    symbol: self.symbols.join(","),
+                    reason: error.unwrap_or_default(),
+                }),
+                ("subscribe", _) => {
+                    tracing::debug!("Kraken subscription confirmed: {:?}", result);
+                    Ok(Vec::new())
+                }
+                _ => Ok(Vec::new()),
+            },
+        }
+    }
+
+    /// Convert a Kraken trade to internal MarketTick format
+    fn convert_trade(msg: KrakenTrade, received_at: DateTime<Utc>) -> FeedResult<MarketTick> {
+        let side = match msg.side.as_str() {
+            "buy" => Some(OrderSide::Buy),
+            "sell" => Some(OrderSide::Sell),
+            _ => None,
+        };
+
+        Ok(MarketTick {
+            id: Uuid::new_v4(),
+            symbol: msg.symbol,
+            price: msg.price,
+            volume: msg.qty,
+            timestamp: parse_timestamp(&msg.timestamp)?,
+            received_at,
+            exchange: Exchange::Kraken,
+            trade_id: Some(msg.trade_id.to_string()),
+            side,
+        })
+    }
+
+    /// Convert a Kraken ticker to a top-of-book Quote
+    fn convert_ticker(msg: KrakenTicker, received_at: DateTime<Utc>) -> FeedResult<Quote> {
+        Ok(Quote {
+            exchange: Exchange::Kraken,
+            symbol: msg.symbol,
+            bid_price: msg.bid,
+            bid_quantity: msg.bid_qty,
+            ask_price: msg.ask,
+            ask_quantity: msg.ask_qty,
+            timestamp: match msg.timestamp {
+                Some(ts) => parse_timestamp(&ts)?,
+                None => received_at,
+            },
+            received_at,
+        })
+    }
+
+    /// Convert a Kraken book push to an OrderBookDelta (quantity 0 removes a level)
+    fn convert_book(
+        &self,
+        msg: KrakenBook,
+        is_snapshot: bool,
+        received_at: DateTime<Utc>,
+    ) -> FeedResult<OrderBookDelta> {
+        let levels = |levels: Vec<KrakenLevel>| {
+            levels
+                .into_iter()
+                .map(|level| OrderBookLevel::new(level.price, level.qty))
+                .collect()
+        };
+
+        Ok(OrderBookDelta {
+            exchange: Exchange::Kraken,
+            symbol: msg.symbol,
+            bids: levels(msg.bids),
+            asks: levels(msg.asks),
+            sequence: self.book_sequence.fetch_add(1, Ordering::Relaxed) + 1,
+            is_snapshot,
+            timestamp: match msg.timestamp {
+                Some(ts) => parse_timestamp(&ts)?,
+                None => received_at,
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl FeedHandler for KrakenFeedHandler {
+    fn name(&self) -> &'static str {
+        "kraken"
+    }
+
+    /// Start the WebSocket connection and begin streaming market data
+    ///
+    /// Returns `FeedError::ReconnectAttemptsExhausted` once the reconnect policy gives up.
+    async fn start(&self) -> FeedResult<()> {
+        tracing::info!("🚀 Kraken feed handler started (URL: {})", self.ws_url);
+
+        let mut reconnector = Reconnector::new(
+            "Kraken",
+            self.reconnect.clone(),
+            self.health.clone(),
+            self.shutdown.clone(),
+        );
+        while !self.shutdown.is_cancelled() {
+            let outcome = tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                outcome = self.connect_and_stream(&mut reconnector) => outcome,
+            };
+            reconnector.backoff(outcome).await?;
+        }
+
+        self.health.set_connected(false);
+        Ok(())
+    }
+
+    fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    fn health(&self) -> &FeedHealth {
+        &self.health
+    }
+
+    fn subscriptions(&self) -> Vec<String> {
+        self.symbols
+            .iter()
+            .flat_map(|symbol| {
+                CHANNELS
+                    .iter()
+                    .map(move |channel| format!("{}:{}", channel, symbol))
+            })
+            .collect()
+    }
+}
+
+/// Smallest supported book depth that covers the requested one
+fn book_depth(requested: usize) -> usize {
+    BOOK_DEPTHS
+        .into_iter()
+        .find(|depth| *depth >= requested)
+        .unwrap_or(BOOK_DEPTHS[BOOK_DEPTHS.len() - 1])
+}
+
+/// Parse a Kraken RFC 3339 timestamp (e.g. "2023-09-25T07:49:37.708706Z")
+fn parse_timestamp(ts: &str) -> FeedResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|_| FeedError::InvalidData(format!("invalid Kraken timestamp: {}", ts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handler() -> KrakenFeedHandler {
+        let (tx, _rx) = broadcast::channel(16);
+        KrakenFeedHandler::new(
+            KrakenConfig {
+                ws_url: String::new(),
+            },
+            tx,
+            vec!["BTC/USDT".to_string()],
+            20,
+        )
+    }
+
+    #[test]
+    fn test_parse_trade_ticker_and_book() {
+        let handler = handler();
+        assert_eq!(handler.depth, 25);
+
+        let trade = r#"{"channel":"trade","type":"update","data":[{"symbol":"BTC/USDT","side":"sell","price":26500.1,"qty":0.05,"ord_type":"market","trade_id":42,"timestamp":"2023-09-25T07:49:37.708706Z"}]}"#;
+        match handler.parse_message(trade).unwrap().as_slice() {
+            [MarketEvent::Trade(tick)] => {
+                assert_eq!(tick.symbol, "BTC/USDT");
+                assert_eq!(tick.price, "26500.1".parse().unwrap());
+                assert_eq!(tick.side, Some(OrderSide::Sell));
+                assert_eq!(tick.trade_id.as_deref(), Some("42"));
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+
+        let ticker = r#"{"channel":"ticker","type":"snapshot","data":[{"symbol":"BTC/USDT","bid":26500.0,"bid_qty":1.5,"ask":26500.2,"ask_qty":0.3,"last":26500.1,"volume":120.0}]}"#;
+        match handler.parse_message(ticker).unwrap().as_slice() {
+            [MarketEvent::Quote(quote)] => {
+                assert_eq!(quote.bid_price, "26500".parse().unwrap());
+                assert_eq!(quote.ask_quantity, "0.3".parse().unwrap());
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+
+        let book = r#"{"channel":"book","type":"snapshot","data":[{"symbol":"BTC/USDT","bids":[{"price":26500.0,"qty":1.5}],"asks":[{"price":26500.2,"qty":0.3},{"price":26501.0,"qty":0.0}],"checksum":123}]}"#;
+        match handler.parse_message(book).unwrap().as_slice() {
+            [MarketEvent::BookDelta(delta)] => {
+                assert!(delta.is_snapshot);
+                assert_eq!(delta.sequence, 1);
+                assert_eq!(delta.bids.len(), 1);
+                assert_eq!(delta.asks.len(), 2);
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_heartbeat_ack_and_error_responses() {
+        let handler = handler();
+
+        assert!(handler
+            .parse_message(r#"{"channel":"heartbeat"}"#)
+            .unwrap()
+            .is_empty());
+        assert!(handler
+            .parse_message(r#"{"method":"pong","time_in":"2023-09-25T07:49:37.708706Z"}"#)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            handler.parse_message(
+                r#"{"method":"subscribe","success":false,"error":"Currency pair not supported"}"#
+            ),
+            Err(FeedError::SubscriptionFailed { .. })
+        ));
+    }
+}
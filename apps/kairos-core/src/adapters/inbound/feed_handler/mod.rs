@@ -1,12 +1,22 @@
 // Feed Handler - WebSocket connections to exchanges
 
 pub mod binance;
+pub mod binance_depth;
 pub mod error;
+pub mod handler;
+pub mod kraken;
 pub mod okx;
+pub mod okx_private;
+pub mod reconnect;
+pub mod supervisor;
 
 // Re-export credential structs for convenience
 // pub use binance::BinanceCredentials;
+pub use handler::{FeedHandler, FeedHealth, FeedStatus};
+pub use kraken::KrakenConfig;
 pub use okx::{OkxConfig, OkxCredentials};
+pub use reconnect::ReconnectPolicy;
+pub use supervisor::{FeedSupervisor, SupervisorHandle};
 
 // Re-export error types
 pub use error::{FeedError, FeedResult};
@@ -1,9 +1,23 @@
 // OKX WebSocket feed handler
 
 use super::error::{FeedError, FeedResult};
+use super::handler::{FeedHandler, FeedHealth};
+use super::reconnect::{ReconnectPolicy, Reconnector};
 use crate::config::Settings;
-use kairos_domain::MarketTick;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::{SinkExt, StreamExt};
+use kairos_domain::{Exchange, MarketEvent, MarketTick, OrderSide, Quote};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
 use tokio::sync::broadcast;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Public channels subscribed for every instrument
+const CHANNELS: [&str; 2] = ["trades", "tickers"];
 
 /// OKX API credentials
 ///
@@ -68,6 +82,7 @@ impl OkxCredentials {
 #[derive(Debug, Clone)]
 pub struct OkxConfig {
     pub ws_url: String,
+    pub ws_private_url: String,
 }
 
 impl OkxConfig {
@@ -84,16 +99,85 @@ impl OkxConfig {
     pub fn from_settings(settings: &Settings) -> Self {
         Self {
             ws_url: settings.exchange.okx_ws_public_url.clone(),
+            ws_private_url: settings.exchange.okx_ws_private_url.clone(),
         }
     }
 }
 
+/// Channel/instrument pair used in subscribe requests and push envelopes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct OkxChannelArg {
+    pub channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inst_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inst_id: Option<String>,
+}
+
+/// Operation message structure for OKX WebSocket (`subscribe`, `login`, ...)
+#[derive(Debug, Serialize)]
+pub(super) struct OkxOpMessage<T> {
+    pub op: String,
+    pub args: Vec<T>,
+}
+
+/// Incoming OKX WebSocket message
+///
+/// OKX sends either a data push (`{"arg": {...}, "data": [...]}`) or an
+/// event acknowledgement (`{"event": "subscribe", ...}` / `{"event": "error", ...}`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(super) enum OkxMessage {
+    Push {
+        arg: OkxChannelArg,
+        data: Vec<serde_json::Value>,
+    },
+    Event {
+        event: String,
+        #[serde(default)]
+        arg: Option<OkxChannelArg>,
+        #[serde(default)]
+        code: Option<String>,
+        #[serde(default)]
+        msg: Option<String>,
+    },
+}
+
+/// OKX `trades` channel entry
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxTradeMessage {
+    inst_id: String,
+    trade_id: String,
+    px: String,   // Trade price
+    sz: String,   // Trade quantity
+    side: String, // Taker side ("buy" / "sell")
+    ts: String,   // Trade timestamp (ms)
+}
+
+/// OKX `tickers` channel entry
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxTickerMessage {
+    inst_id: String,
+    bid_px: String, // Best bid price
+    bid_sz: String, // Best bid quantity
+    ask_px: String, // Best ask price
+    ask_sz: String, // Best ask quantity
+    ts: String,     // Ticker timestamp (ms)
+}
+
 pub struct OkxFeedHandler {
-    market_data_tx: broadcast::Sender<MarketTick>,
+    market_data_tx: broadcast::Sender<MarketEvent>,
+    instruments: Vec<String>,
     api_key: String,
     api_secret: String,
     api_passphrase: Option<String>,
     ws_url: String,
+    reconnect: ReconnectPolicy,
+    health: Arc<FeedHealth>,
+    shutdown: CancellationToken,
 }
 
 impl OkxFeedHandler {
@@ -103,6 +187,7 @@ impl OkxFeedHandler {
     /// * `credentials` - Validated OKX API credentials
     /// * `config` - OKX configuration (WebSocket URL, etc.)
     /// * `market_data_tx` - Broadcast channel for market data
+    /// * `instruments` - OKX instrument IDs from `[markets]` (e.g., ["BTC-USDT", "ETH-USDT"])
     ///
     /// # Returns
     /// * Successfully created handler with credentials
@@ -112,12 +197,18 @@ impl OkxFeedHandler {
     /// let settings = Settings::new()?;
     /// let credentials = OkxCredentials::from_settings(&settings)?;
     /// let config = OkxConfig::from_settings(&settings);
-    /// let handler = OkxFeedHandler::new(credentials, config, market_data_tx);
+    /// let handler = OkxFeedHandler::new(
+    ///     credentials,
+    ///     config,
+    ///     market_data_tx,
+    ///     settings.markets.exchange_symbols(&Exchange::OKX)?,
+    /// );
     /// ```
     pub fn new(
         credentials: OkxCredentials,
         config: OkxConfig,
-        market_data_tx: broadcast::Sender<MarketTick>,
+        market_data_tx: broadcast::Sender<MarketEvent>,
+        instruments: Vec<String>,
     ) -> Self {
         tracing::info!(
             "✅ OKX Feed Handler initialized with API credentials (key: {}...)",
@@ -126,10 +217,14 @@ impl OkxFeedHandler {
 
         Self {
             market_data_tx,
+            instruments,
             api_key: credentials.api_key,
             api_secret: credentials.api_secret,
             api_passphrase: credentials.api_passphrase,
             ws_url: config.ws_url,
+            reconnect: ReconnectPolicy::default(),
+            health: Arc::new(FeedHealth::new()),
+            shutdown: CancellationToken::new(),
         }
     }
 
@@ -140,40 +235,374 @@ impl OkxFeedHandler {
     /// # Arguments
     /// * `config` - OKX configuration (WebSocket URL, etc.)
     /// * `market_data_tx` - Broadcast channel for market data
+    /// * `instruments` - OKX instrument IDs from `[markets]` (e.g., ["BTC-USDT", "ETH-USDT"])
     ///
     /// # Example
     /// ```rust,ignore
     /// let settings = Settings::new()?;
     /// let config = OkxConfig::from_settings(&settings);
-    /// let handler = OkxFeedHandler::new_public(config, market_data_tx);
+    /// let handler = let instruments = settings.markets.exchange_symbols(&Exchange::OKX)?;
+    /// let handler = OkxFeedHandler::new_public(config, market_data_tx, instruments);
     /// ```
-    pub fn new_public(config: OkxConfig, market_data_tx: broadcast::Sender<MarketTick>) -> Self {
+    pub fn new_public(
+        config: OkxConfig,
+        market_data_tx: broadcast::Sender<MarketEvent>,
+        instruments: Vec<String>,
+    ) -> Self {
         tracing::info!("OKX Feed Handler initialized in PUBLIC mode (no authentication)");
 
         Self {
             market_data_tx,
+            instruments,
             api_key: String::new(),
             api_secret: String::new(),
             api_passphrase: None,
             ws_url: config.ws_url,
+            reconnect: ReconnectPolicy::default(),
+            health: Arc::new(FeedHealth::new()),
+            shutdown: CancellationToken::new(),
         }
     }
 
-    pub async fn start(&self) -> FeedResult<()> {
-        // TODO: Implement WebSocket connection to OKX
-        // 1. Connect to self.ws_url
-        // 2. Authenticate using api_key, api_secret, api_passphrase (if needed)
-        // 3. Subscribe to market data streams
-        // 4. Parse incoming JSON
-        // 5. Convert to MarketTick
-        // 6. Broadcast to channel
+    /// Override the reconnect/keep-alive policy (defaults to [`ReconnectPolicy::default`])
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Internal method to handle connection, subscription and streaming
+    async fn connect_and_stream(&self, reconnector: &mut Reconnector) -> FeedResult<()> {
+        tracing::info!("Connecting to OKX WebSocket: {}", self.ws_url);
+
+        let (ws_stream, _) = connect_async(&self.ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // Subscribe to trades and tickers for every configured instrument
+        let args = self
+            .instruments
+            .iter()
+            .flat_map(|inst_id| {
+                CHANNELS.iter().map(|channel| OkxChannelArg {
+                    channel: channel.to_string(),
+                    inst_id: Some(inst_id.clone()),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        let subscribe = OkxOpMessage {
+            op: "subscribe".to_string(),
+            args,
+        };
+        write
+            .send(Message::Text(serde_json::to_string(&subscribe)?))
+            .await?;
+
+        tracing::info!(
+            "✅ Connected to OKX WebSocket, subscribed to {} instruments: {:?}",
+            self.instruments.len(),
+            self.instruments
+        );
 
+        // OKX drops connections idle for 30s; keep alive with text "ping"
+        let mut ping = reconnector.ping_timer();
+        loop {
+            tokio::select! {
+                message = reconnector.next_message(&mut read) => {
+                    let Some(message) = message? else { break };
+                    match message {
+                        Message::Text(text) => {
+                            if let Err(e) = self.process_message(&text) {
+                                tracing::warn!("Failed to process message: {:?}", e);
+                            }
+                        }
+                        Message::Ping(payload) => {
+                            write.send(Message::Pong(payload)).await?;
+                        }
+                        Message::Close(frame) => {
+                            tracing::info!("WebSocket closed: {:?}", frame);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                _ = ping.tick() => {
+                    write.send(Message::Text("ping".to_string())).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process a single message from OKX WebSocket
+    fn process_message(&self, text: &str) -> FeedResult<()> {
+        for event in self.parse_message(text)? {
+            tracing::debug!("📊 Market event: {:?}", event);
+
+            // Broadcast to all subscribers
+            let _ = self.market_data_tx.send(event);
+        }
+        Ok(())
+    }
+
+    /// Decode an OKX message into market events
+    ///
+    /// A malformed entry is logged and skipped, so one bad item does not
+    /// drop the rest of the batch.
+    fn parse_message(&self, text: &str) -> FeedResult<Vec<MarketEvent>> {
+        let received_at = Utc::now();
+
+        // Keep-alive reply to a text "ping"
+        if text == "pong" {
+            return Ok(Vec::new());
+        }
+
+        match serde_json::from_str::<OkxMessage>(text)? {
+            OkxMessage::Push { arg, data } => {
+                let mut events = Vec::with_capacity(data.len());
+                for entry in data {
+                    let event = match arg.channel.as_str() {
+                        "trades" => match serde_json::from_value(entry) {
+                            Ok(msg) => {
+                                Self::convert_trade(msg, received_at).map(MarketEvent::Trade)
+                            }
+                            Err(e) => Err(e.into()),
+                        },
+                        "tickers" => match serde_json::from_value(entry) {
+                            Ok(msg) => {
+                                Self::convert_ticker(msg, received_at).map(MarketEvent::Quote)
+                            }
+                            Err(e) => Err(e.into()),
+                        },
+                        other => {
+                            tracing::debug!("Ignoring push from unhandled OKX channel: {}", other);
+                            continue;
+                        }
+                    };
+
+                    match event {
+                        Ok(event) => events.push(event),
+                        Err(e) => {
+                            tracing::warn!(
+                                "⚠️ Skipping malformed OKX {} entry: {:?}",
+                                arg.channel,
+                                e
+                            );
+                        }
+                    }
+                }
+                Ok(events)
+            }
+            OkxMessage::Event {
+                event,
+                arg,
+                code,
+                msg,
+            } => match event.as_str() {
+                "subscribe" => {
+                    tracing::debug!("OKX subscription confirmed: {:?}", arg);
+                    Ok(Vec::new())
+                }
+                "error" => Err(FeedError::SubscriptionFailed {
+                    symbol: arg
+                        .and_then(|a| a.inst_id)
+                        .unwrap_or_else(|| self.instruments.join(",")),
+                    reason: format!(
+                        "code {}: {}",
+                        code.unwrap_or_default(),
+                        msg.unwrap_or_default()
+                    ),
+                }),
+                other => {
+                    tracing::debug!("OKX event: {}", other);
+                    Ok(Vec::new())
+                }
+            },
+        }
+    }
+
+    /// Convert an OKX trade to internal MarketTick format
+    fn convert_trade(msg: OkxTradeMessage, received_at: DateTime<Utc>) -> FeedResult<MarketTick> {
+        let side = match msg.side.as_str() {
+            "buy" => Some(OrderSide::Buy),
+            "sell" => Some(OrderSide::Sell),
+            _ => None,
+        };
+
+        Ok(MarketTick {
+            id: Uuid::new_v4(),
+            symbol: msg.inst_id,
+            price: parse_decimal("px", &msg.px)?,
+            volume: parse_decimal("sz", &msg.sz)?,
+            timestamp: parse_timestamp(&msg.ts)?,
+            received_at,
+            exchange: Exchange::OKX,
+            trade_id: Some(msg.trade_id),
+            side,
+        })
+    }
+
+    /// Convert an OKX ticker to a top-of-book Quote
+    fn convert_ticker(msg: OkxTickerMessage, received_at: DateTime<Utc>) -> FeedResult<Quote> {
+        Ok(Quote {
+            exchange: Exchange::OKX,
+            symbol: msg.inst_id,
+            bid_price: parse_decimal("bidPx", &msg.bid_px)?,
+            bid_quantity: parse_decimal("bidSz", &msg.bid_sz)?,
+            ask_price: parse_decimal("askPx", &msg.ask_px)?,
+            ask_quantity: parse_decimal("askSz", &msg.ask_sz)?,
+            timestamp: parse_timestamp(&msg.ts)?,
+            received_at,
+        })
+    }
+}
+
+#[async_trait]
+impl FeedHandler for OkxFeedHandler {
+    fn name(&self) -> &'static str {
+        "okx"
+    }
+
+    /// Start the WebSocket connection and begin streaming market data
+    ///
+    /// Returns `FeedError::ReconnectAttemptsExhausted` once the reconnect policy gives up.
+    async fn start(&self) -> FeedResult<()> {
         tracing::info!("🚀 OKX feed handler started (URL: {})", self.ws_url);
-        tracing::debug!(
-            "Using API key: {}...",
-            &self.api_key[..self.api_key.len().min(8)]
+
+        let mut reconnector = Reconnector::new(
+            "OKX",
+            self.reconnect.clone(),
+            self.health.clone(),
+            self.shutdown.clone(),
         );
+        while !self.shutdown.is_cancelled() {
+            let outcome = tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                outcome = self.connect_and_stream(&mut reconnector) => outcome,
+            };
+            reconnector.backoff(outcome).await?;
+        }
 
+        self.health.set_connected(false);
         Ok(())
     }
+
+    fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    fn health(&self) -> &FeedHealth {
+        &self.health
+    }
+
+    fn subscriptions(&self) -> Vec<String> {
+        self.instruments
+            .iter()
+            .flat_map(|inst_id| {
+                CHANNELS
+                    .iter()
+                    .map(move |channel| format!("{}:{}", channel, inst_id))
+            })
+            .collect()
+    }
+}
+
+/// Parse an OKX decimal string into a fixed-point price or quantity
+pub(super) fn parse_decimal<T: FromStr>(field: &str, value: &str) -> FeedResult<T> {
+    value
+        .parse::<T>()
+        .map_err(|_| FeedError::DecimalParseError {
+            field: field.to_string(),
+            value: value.to_string(),
+        })
+}
+
+/// Parse an OKX millisecond timestamp string
+pub(super) fn parse_timestamp(ts: &str) -> FeedResult<DateTime<Utc>> {
+    ts.parse::<i64>()
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .ok_or_else(|| FeedError::InvalidData(format!("invalid OKX timestamp: {}", ts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handler() -> OkxFeedHandler {
+        let (tx, _rx) = broadcast::channel(16);
+        OkxFeedHandler::new_public(
+            OkxConfig {
+                ws_url: String::new(),
+                ws_private_url: String::new(),
+            },
+            tx,
+            vec!["BTC-USDT".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_parse_trade_and_ticker_push() {
+        let handler = handler();
+
+        let trade = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"130639474","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897","count":"3"}]}"#;
+        match handler.parse_message(trade).unwrap().as_slice() {
+            [MarketEvent::Trade(tick)] => {
+                assert_eq!(tick.symbol, "BTC-USDT");
+                assert_eq!(tick.price, "42219.9".parse().unwrap());
+                assert_eq!(tick.volume, "0.12060306".parse().unwrap());
+                assert_eq!(tick.side, Some(OrderSide::Buy));
+                assert_eq!(tick.trade_id.as_deref(), Some("130639474"));
+                assert_eq!(tick.timestamp.timestamp_millis(), 1630048897897);
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+
+        let ticker = r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[{"instType":"SPOT","instId":"BTC-USDT","last":"9999.99","lastSz":"0.1","askPx":"9999.99","askSz":"11","bidPx":"8888.88","bidSz":"5","open24h":"9000","high24h":"10000","low24h":"8888.88","volCcy24h":"2222","vol24h":"2222","sodUtc0":"2222","sodUtc8":"2222","ts":"1597026383085"}]}"#;
+        match handler.parse_message(ticker).unwrap().as_slice() {
+            [MarketEvent::Quote(quote)] => {
+                assert_eq!(quote.bid_price, "8888.88".parse().unwrap());
+                assert_eq!(quote.bid_quantity, "5".parse().unwrap());
+                assert_eq!(quote.ask_price, "9999.99".parse().unwrap());
+                assert_eq!(quote.ask_quantity, "11".parse().unwrap());
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_entry_does_not_drop_batch() {
+        let handler = handler();
+
+        let batch = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"1","px":"not-a-price","sz":"0.1","side":"buy","ts":"1630048897897"},{"instId":"BTC-USDT","tradeId":"2","sz":"0.1","side":"sell","ts":"1630048897897"},{"instId":"BTC-USDT","tradeId":"3","px":"42220.1","sz":"0.5","side":"sell","ts":"1630048897898"}]}"#;
+        match handler.parse_message(batch).unwrap().as_slice() {
+            [MarketEvent::Trade(tick)] => {
+                assert_eq!(tick.trade_id.as_deref(), Some("3"));
+                assert_eq!(tick.side, Some(OrderSide::Sell));
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pong_subscribe_and_error_events() {
+        let handler = handler();
+
+        assert!(handler.parse_message("pong").unwrap().is_empty());
+        assert!(handler
+            .parse_message(
+                r#"{"event":"subscribe","arg":{"channel":"tickers","instId":"BTC-USDT"},"connId":"accb8e21"}"#
+            )
+            .unwrap()
+            .is_empty());
+        match handler.parse_message(
+            r#"{"event":"error","code":"60012","msg":"Invalid request: {\"op\": \"subscribe\", \"argss\":[{ \"channel\" : \"tickers\", \"instId\" : \"BTC-USDT\"}]}","connId":"a4d3ae55"}"#,
+        ) {
+            Err(FeedError::SubscriptionFailed { symbol, reason }) => {
+                assert_eq!(symbol, "BTC-USDT");
+                assert!(reason.starts_with("code 60012"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 }
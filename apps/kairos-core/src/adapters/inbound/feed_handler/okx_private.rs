@@ -0,0 +1,550 @@
+// OKX private WebSocket feed handler (orders, account, positions)
+
+use super::error::{FeedError, FeedResult};
+use super::handler::{FeedHandler, FeedHealth};
+use super::okx::{
+    parse_decimal, parse_timestamp, OkxChannelArg, OkxConfig, OkxCredentials, OkxMessage,
+    OkxOpMessage,
+};
+use super::reconnect::{ReconnectPolicy, Reconnector};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use hmac::{Hmac, Mac};
+use kairos_domain::{
+    AccountEvent, Amount, BalanceUpdate, Exchange, Fill, Liquidity, OrderSide, OrderStatus,
+    OrderUpdate, PositionUpdate, Price, Quantity,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::time::{timeout, Duration};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
+
+/// Request path signed during the WebSocket login handshake
+const LOGIN_PATH: &str = "/users/self/verify";
+
+/// Private channels subscribed after login
+const CHANNELS: [&str; 3] = ["orders", "account", "positions"];
+
+/// How long to wait for the login acknowledgement
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Login arguments for the OKX private WebSocket
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxLoginArg {
+    api_key: String,
+    passphrase: String,
+    timestamp: String,
+    sign: String,
+}
+
+/// OKX `orders` channel entry
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxOrderMessage {
+    inst_id: String,
+    ord_id: String,
+    #[serde(default)]
+    cl_ord_id: String,
+    side: String,
+    state: String,
+    #[serde(default)]
+    acc_fill_sz: String,
+    #[serde(default)]
+    avg_px: String,
+    #[serde(default)]
+    trade_id: String,
+    #[serde(default)]
+    fill_px: String,
+    #[serde(default)]
+    fill_sz: String,
+    #[serde(default)]
+    fill_fee: String,
+    #[serde(default)]
+    fill_fee_ccy: String,
+    #[serde(default)]
+    fill_time: String,
+    /// "M" maker, "T" taker
+    #[serde(default)]
+    exec_type: String,
+    u_time: String,
+}
+
+/// OKX `account` channel entry
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxAccountMessage {
+    u_time: String,
+    details: Vec<OkxBalanceDetail>,
+}
+
+/// Per-currency balance inside an `account` push
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxBalanceDetail {
+    ccy: String,
+    #[serde(default)]
+    avail_bal: String,
+    #[serde(default)]
+    frozen_bal: String,
+    #[serde(default)]
+    cash_bal: String,
+}
+
+/// OKX `positions` channel entry
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxPositionMessage {
+    inst_id: String,
+    pos: String,
+    #[serde(default)]
+    pos_side: String,
+    #[serde(default)]
+    avg_px: String,
+    #[serde(default)]
+    upl: String,
+    u_time: String,
+}
+
+/// Authenticated OKX feed streaming order, balance and position events
+pub struct OkxPrivateFeedHandler {
+    account_event_tx: broadcast::Sender<AccountEvent>,
+    api_key: String,
+    api_secret: String,
+    api_passphrase: String,
+    ws_url: String,
+    reconnect: ReconnectPolicy,
+    health: Arc<FeedHealth>,
+    shutdown: CancellationToken,
+}
+
+impl OkxPrivateFeedHandler {
+    /// Create a new private OKX feed handler
+    ///
+    /// # Arguments
+    /// * `credentials` - Validated OKX API credentials (passphrase is required)
+    /// * `config` - OKX configuration (private WebSocket URL)
+    /// * `account_event_tx` - Broadcast channel for account events
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let credentials = OkxCredentials::from_settings(&settings)?;
+    /// let config = OkxConfig::from_settings(&settings);
+    /// let handler = OkxPrivateFeedHandler::new(credentials, config, account_event_tx)?;
+    /// ```
+    pub fn new(
+        credentials: OkxCredentials,
+        config: OkxConfig,
+        account_event_tx: broadcast::Sender<AccountEvent>,
+    ) -> FeedResult<Self> {
+        let api_passphrase =
+            credentials
+                .api_passphrase
+                .ok_or_else(|| FeedError::MissingCredentials {
+                    exchange: "OKX (passphrase)".to_string(),
+                })?;
+
+        tracing::info!(
+            "✅ OKX Private Feed Handler initialized (key: {}...)",
+            &credentials.api_key[..credentials.api_key.len().min(8)]
+        );
+
+        Ok(Self {
+            account_event_tx,
+            api_key: credentials.api_key,
+            api_secret: credentials.api_secret,
+            api_passphrase,
+            ws_url: config.ws_private_url,
+            reconnect: ReconnectPolicy::default(),
+            health: Arc::new(FeedHealth::new()),
+            shutdown: CancellationToken::new(),
+        })
+    }
+
+    /// Override the reconnect/keep-alive policy (defaults to [`ReconnectPolicy::default`])
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Internal method to handle login, subscription and streaming
+    async fn connect_and_stream(&self, reconnector: &mut Reconnector) -> FeedResult<()> {
+        tracing::info!("Connecting to OKX private WebSocket: {}", self.ws_url);
+
+        let (ws_stream, _) = connect_async(&self.ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        // 1. Login handshake
+        let timestamp = Utc::now().timestamp().to_string();
+        let login = OkxOpMessage {
+            op: "login".to_string(),
+            args: vec![OkxLoginArg {
+                api_key: self.api_key.clone(),
+                passphrase: self.api_passphrase.clone(),
+                sign: sign(&self.api_secret, &timestamp, "GET", LOGIN_PATH, ""),
+                timestamp,
+            }],
+        };
+        write
+            .send(Message::Text(serde_json::to_string(&login)?))
+            .await?;
+
+        timeout(LOGIN_TIMEOUT, async {
+            while let Some(message) = read.next().await {
+                if let Message::Text(text) = message? {
+                    if let OkxMessage::Event {
+                        event, code, msg, ..
+                    } = serde_json::from_str::<OkxMessage>(&text)?
+                    {
+                        match event.as_str() {
+                            "login" => return Ok(()),
+                            "error" => {
+                                return Err(FeedError::AuthenticationFailed {
+                                    reason: format!(
+                                        "code {}: {}",
+                                        code.unwrap_or_default(),
+                                        msg.unwrap_or_default()
+                                    ),
+                                })
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            Err(FeedError::InvalidData(
+                "connection closed before OKX login completed".to_string(),
+            ))
+        })
+        .await
+        .map_err(|_| {
+            FeedError::InvalidData("timed out waiting for OKX login response".to_string())
+        })??;
+
+        tracing::info!("🔐 Logged in to OKX private WebSocket");
+
+        // 2. Subscribe to private channels
+        let subscribe = OkxOpMessage {
+            op: "subscribe".to_string(),
+            args: vec![
+                OkxChannelArg {
+                    channel: "orders".to_string(),
+                    inst_type: Some("ANY".to_string()),
+                    ..Default::default()
+                },
+                OkxChannelArg {
+                    channel: "account".to_string(),
+                    ..Default::default()
+                },
+                OkxChannelArg {
+                    channel: "positions".to_string(),
+                    inst_type: Some("ANY".to_string()),
+                    ..Default::default()
+                },
+            ],
+        };
+        write
+            .send(Message::Text(serde_json::to_string(&subscribe)?))
+            .await?;
+
+        // 3. Process incoming messages
+        // OKX drops connections idle for 30s; keep alive with text "ping"
+        let mut ping = reconnector.ping_timer();
+        loop {
+            tokio::select! {
+                message = reconnector.next_message(&mut read) => {
+                    let Some(message) = message? else { break };
+                    match message {
+                        Message::Text(text) => {
+                            if let Err(e) = self.process_message(&text) {
+                                tracing::warn!("Failed to process private message: {:?}", e);
+                            }
+                        }
+                        Message::Ping(payload) => {
+                            write.send(Message::Pong(payload)).await?;
+                        }
+                        Message::Close(frame) => {
+                            tracing::info!("Private WebSocket closed: {:?}", frame);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                _ = ping.tick() => {
+                    write.send(Message::Text("ping".to_string())).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process a single message from the OKX private WebSocket
+    fn process_message(&self, text: &str) -> FeedResult<()> {
+        if text == "pong" {
+            return Ok(());
+        }
+
+        match serde_json::from_str::<OkxMessage>(text)? {
+            OkxMessage::Push { arg, data } => {
+                for entry in data {
+                    let events = match arg.channel.as_str() {
+                        "orders" => convert_order(serde_json::from_value(entry)?)?,
+                        "account" => convert_account(serde_json::from_value(entry)?)?,
+                        "positions" => vec![convert_position(serde_json::from_value(entry)?)?],
+                        other => {
+                            tracing::debug!("Ignoring push from unhandled OKX channel: {}", other);
+                            continue;
+                        }
+                    };
+
+                    for event in events {
+                        tracing::debug!("🧾 Account event: {:?}", event);
+                        let _ = self.account_event_tx.send(event);
+                    }
+                }
+            }
+            OkxMessage::Event {
+                event,
+                arg,
+                code,
+                msg,
+            } => match event.as_str() {
+                "subscribe" => {
+                    tracing::debug!("OKX private subscription confirmed: {:?}", arg);
+                }
+                "error" => {
+                    return Err(FeedError::SubscriptionFailed {
+                        symbol: arg.map(|a| a.channel).unwrap_or_default(),
+                        reason: format!(
+                            "code {}: {}",
+                            code.unwrap_or_default(),
+                            msg.unwrap_or_default()
+                        ),
+                    });
+                }
+                other => {
+                    tracing::debug!("OKX private event: {}", other);
+                }
+            },
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FeedHandler for OkxPrivateFeedHandler {
+    fn name(&self) -> &'static str {
+        "okx_private"
+    }
+
+    /// Start the authenticated WebSocket connection and stream account events
+    ///
+    /// Gives up immediately on a rejected login, or with
+    /// `FeedError::ReconnectAttemptsExhausted` once the reconnect policy is exhausted.
+    async fn start(&self) -> FeedResult<()> {
+        tracing::info!("🚀 OKX private feed handler started (URL: {})", self.ws_url);
+
+        let mut reconnector = Reconnector::new(
+            "OKX private",
+            self.reconnect.clone(),
+            self.health.clone(),
+            self.shutdown.clone(),
+        );
+        while !self.shutdown.is_cancelled() {
+            let outcome = tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                outcome = self.connect_and_stream(&mut reconnector) => outcome,
+            };
+            if let Err(FeedError::AuthenticationFailed { reason }) = outcome {
+                // Retrying with the same credentials will not help
+                tracing::error!("❌ OKX login rejected: {}", reason);
+                return Err(FeedError::AuthenticationFailed { reason });
+            }
+            reconnector.backoff(outcome).await?;
+        }
+
+        self.health.set_connected(false);
+        Ok(())
+    }
+
+    fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    fn health(&self) -> &FeedHealth {
+        &self.health
+    }
+
+    fn subscriptions(&self) -> Vec<String> {
+        CHANNELS.iter().map(|channel| channel.to_string()).collect()
+    }
+}
+
+/// Compute the OKX request signature: `base64(HMAC-SHA256(secret, ts + method + path + body))`
+pub(crate) fn sign(secret: &str, timestamp: &str, method: &str, path: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(method.as_bytes());
+    mac.update(path.as_bytes());
+    mac.update(body.as_bytes());
+    BASE64.encode(mac.finalize().into_bytes())
+}
+
+/// Parse an optional OKX decimal field (OKX sends `""` when not applicable)
+fn parse_optional<T: FromStr>(field: &str, value: &str) -> FeedResult<Option<T>> {
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        parse_decimal(field, value).map(Some)
+    }
+}
+
+fn parse_side(side: &str) -> FeedResult<OrderSide> {
+    match side {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        other => Err(FeedError::InvalidData(format!(
+            "unknown OKX side: {}",
+            other
+        ))),
+    }
+}
+
+fn parse_state(state: &str) -> FeedResult<OrderStatus> {
+    match state {
+        "live" | "partially_filled" => Ok(OrderStatus::Approved),
+        "filled" => Ok(OrderStatus::Executed),
+        "canceled" | "mmp_canceled" => Ok(OrderStatus::Cancelled),
+        other => Err(FeedError::InvalidData(format!(
+            "unknown OKX order state: {}",
+            other
+        ))),
+    }
+}
+
+/// Convert an `orders` push into an order update plus a fill when one occurred
+fn convert_order(msg: OkxOrderMessage) -> FeedResult<Vec<AccountEvent>> {
+    let side = parse_side(&msg.side)?;
+    let client_order_id = (!msg.cl_ord_id.is_empty()).then(|| msg.cl_ord_id.clone());
+    let mut events = Vec::with_capacity(2);
+
+    if !msg.trade_id.is_empty() {
+        events.push(AccountEvent::Fill(Fill {
+            exchange: Exchange::OKX,
+            symbol: msg.inst_id.clone(),
+            exchange_order_id: msg.ord_id.clone(),
+            client_order_id: client_order_id.clone(),
+            trade_id: msg.trade_id,
+            side: side.clone(),
+            price: parse_decimal("fillPx", &msg.fill_px)?,
+            quantity: parse_decimal("fillSz", &msg.fill_sz)?,
+            // OKX reports charged fees as negative numbers
+            fee: -parse_optional::<Amount>("fillFee", &msg.fill_fee)?.unwrap_or_default(),
+            fee_currency: msg.fill_fee_ccy,
+            liquidity: match msg.exec_type.as_str() {
+                "M" => Some(Liquidity::Maker),
+                "T" => Some(Liquidity::Taker),
+                _ => None,
+            },
+            timestamp: parse_timestamp(&msg.fill_time)?,
+        }));
+    }
+
+    events.push(AccountEvent::OrderUpdate(OrderUpdate {
+        exchange: Exchange::OKX,
+        symbol: msg.inst_id,
+        exchange_order_id: msg.ord_id,
+        client_order_id,
+        side,
+        status: parse_state(&msg.state)?,
+        filled_quantity: parse_optional("accFillSz", &msg.acc_fill_sz)?.unwrap_or_default(),
+        average_price: parse_optional("avgPx", &msg.avg_px)?.filter(Price::is_positive),
+        timestamp: parse_timestamp(&msg.u_time)?,
+    }));
+
+    Ok(events)
+}
+
+/// Convert an `account` push into one balance update per currency
+fn convert_account(msg: OkxAccountMessage) -> FeedResult<Vec<AccountEvent>> {
+    let timestamp = parse_timestamp(&msg.u_time)?;
+
+    msg.details
+        .into_iter()
+        .map(|detail| {
+            Ok(AccountEvent::Balance(BalanceUpdate {
+                exchange: Exchange::OKX,
+                available: parse_optional("availBal", &detail.avail_bal)?.unwrap_or_default(),
+                locked: parse_optional("frozenBal", &detail.frozen_bal)?.unwrap_or_default(),
+                total: parse_optional("cashBal", &detail.cash_bal)?.unwrap_or_default(),
+                currency: detail.ccy,
+                timestamp,
+            }))
+        })
+        .collect()
+}
+
+/// Convert a `positions` push into a signed position update
+fn convert_position(msg: OkxPositionMessage) -> FeedResult<AccountEvent> {
+    let pos: Quantity = parse_optional("pos", &msg.pos)?.unwrap_or_default();
+    // In long/short mode `pos` is always positive and `posSide` carries the direction
+    let quantity = if msg.pos_side == "short" {
+        -pos.abs()
+    } else {
+        pos
+    };
+
+    Ok(AccountEvent::Position(PositionUpdate {
+        exchange: Exchange::OKX,
+        symbol: msg.inst_id,
+        quantity,
+        average_price: parse_optional("avgPx", &msg.avg_px)?,
+        unrealized_pnl: parse_optional("upl", &msg.upl)?.unwrap_or_default(),
+        timestamp: parse_timestamp(&msg.u_time)?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_signature() {
+        let signature = sign("secret", "1538054050", "GET", LOGIN_PATH, "");
+        assert_eq!(signature, "Gj2hQIVKFcXbiwCak8SmVOu5mxPCizWDdmUAhbx8Z+s=");
+    }
+
+    #[test]
+    fn test_order_push_with_fill() {
+        let msg: OkxOrderMessage = serde_json::from_str(
+            r#"{"instId":"BTC-USDT","ordId":"312269865356374016","clOrdId":"k1","side":"buy",
+                "state":"partially_filled","accFillSz":"0.01","avgPx":"30000","tradeId":"242589207",
+                "fillPx":"30000","fillSz":"0.01","fillFee":"-0.00001","fillFeeCcy":"BTC",
+                "fillTime":"1597026383085","execType":"T","uTime":"1597026383085"}"#,
+        )
+        .unwrap();
+
+        let events = convert_order(msg).unwrap();
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            AccountEvent::Fill(fill) => {
+                assert_eq!(fill.trade_id, "242589207");
+                assert_eq!(fill.quantity, "0.01".parse().unwrap());
+                assert_eq!(fill.fee, "0.00001".parse().unwrap());
+                assert_eq!(fill.client_order_id.as_deref(), Some("k1"));
+                assert_eq!(fill.liquidity, Some(Liquidity::Taker));
+            }
+            other => panic!("expected fill, got {:?}", other),
+        }
+        assert!(matches!(events[1], AccountEvent::OrderUpdate(_)));
+    }
+}
@@ -0,0 +1,250 @@
+// Shared WebSocket reconnect policy (backoff, attempt cap, stale watchdog, keep-alive)
+
+use super::error::{FeedError, FeedResult};
+use super::handler::FeedHealth;
+use crate::config::Settings;
+use futures::{Stream, StreamExt};
+use rand::Rng;
+use std::sync::Arc;
+use tokio::time::{interval_at, sleep, timeout, Duration, Instant, Interval};
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_util::sync::CancellationToken;
+
+/// How a feed handler reconnects and detects dead connections
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Delay before the first retry, doubled on each consecutive failure
+    pub initial_delay: Duration,
+    /// Upper bound for the backoff delay
+    pub max_delay: Duration,
+    /// Consecutive failed attempts before giving up (0 = retry forever)
+    pub max_attempts: u32,
+    /// How often the client sends its own keep-alive ping
+    pub ping_interval: Duration,
+    /// Reconnect when no frame arrives within this window
+    pub stale_timeout: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(5000),
+            max_delay: Duration::from_millis(60_000),
+            max_attempts: 10,
+            ping_interval: Duration::from_secs(20),
+            stale_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Build the policy from `[exchange]` `ws_*` settings
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let handler = OkxFeedHandler::new_public(config, market_data_tx, instruments)
+    ///     .with_reconnect_policy(ReconnectPolicy::from_settings(&settings));
+    /// ```
+    pub fn from_settings(settings: &Settings) -> Self {
+        let exchange = &settings.exchange;
+        Self {
+            initial_delay: Duration::from_millis(exchange.ws_reconnect_delay_ms),
+            max_delay: Duration::from_millis(exchange.ws_max_reconnect_delay_ms),
+            max_attempts: exchange.ws_max_reconnect_attempts,
+            ping_interval: Duration::from_secs(exchange.ws_ping_interval_sec),
+            stale_timeout: Duration::from_secs(exchange.ws_stale_timeout_sec),
+        }
+    }
+
+    /// Un-jittered exponential delay for a 1-based attempt number
+    fn base_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+
+    /// Backoff delay with jitter in `[base / 2, base]` so handlers don't reconnect in lockstep
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let base = self.base_delay(attempt);
+        base.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+
+    /// Keep-alive timer whose first tick fires one interval from now
+    pub fn ping_timer(&self) -> Interval {
+        interval_at(Instant::now() + self.ping_interval, self.ping_interval)
+    }
+}
+
+/// Per-handler reconnect state driven by a [`ReconnectPolicy`]
+///
+/// Also keeps the handler's [`FeedHealth`] up to date and cuts backoff
+/// short when the handler is stopped.
+pub struct Reconnector {
+    exchange: &'static str,
+    policy: ReconnectPolicy,
+    health: Arc<FeedHealth>,
+    shutdown: CancellationToken,
+    attempts: u32,
+}
+
+impl Reconnector {
+    pub fn new(
+        exchange: &'static str,
+        policy: ReconnectPolicy,
+        health: Arc<FeedHealth>,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            exchange,
+            policy,
+            health,
+            shutdown,
+            attempts: 0,
+        }
+    }
+
+    /// Read the next frame, enforcing the stale-connection watchdog
+    ///
+    /// Any frame proves the connection is healthy and resets the backoff.
+    pub async fn next_message<S>(&mut self, read: &mut S) -> FeedResult<Option<Message>>
+    where
+        S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin,
+    {
+        match timeout(self.policy.stale_timeout, read.next()).await {
+            Ok(Some(message)) => {
+                let message = message?;
+                self.attempts = 0;
+                self.health.set_connected(true);
+                self.health.record_message();
+                Ok(Some(message))
+            }
+            Ok(None) => Ok(None),
+            Err(_) => Err(FeedError::StaleConnection {
+                exchange: self.exchange.to_string(),
+                seconds: self.policy.stale_timeout.as_secs(),
+            }),
+        }
+    }
+
+    /// Keep-alive timer for the current connection
+    pub fn ping_timer(&self) -> Interval {
+        self.policy.ping_timer()
+    }
+
+    /// Handle the end of a connection: wait out the backoff, or give up
+    /// with [`FeedError::ReconnectAttemptsExhausted`] once the cap is hit
+    pub async fn backoff(&mut self, outcome: FeedResult<()>) -> FeedResult<()> {
+        self.health.set_connected(false);
+        if self.shutdown.is_cancelled() {
+            return Ok(());
+        }
+        self.attempts += 1;
+
+        let reason = match outcome {
+            Ok(()) => "connection closed".to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        if self.policy.max_attempts > 0 && self.attempts > self.policy.max_attempts {
+            tracing::error!(
+                "❌ {} WebSocket gave up after {} attempts: {}",
+                self.exchange,
+                self.policy.max_attempts,
+                reason
+            );
+            return Err(FeedError::ReconnectAttemptsExhausted {
+                exchange: self.exchange.to_string(),
+                attempts: self.policy.max_attempts,
+                last_error: reason,
+            });
+        }
+
+        let delay = self.policy.backoff_delay(self.attempts);
+        tracing::warn!(
+            "{} WebSocket {}, reconnecting in {:?} (attempt {})",
+            self.exchange,
+            reason,
+            delay,
+            self.attempts
+        );
+        self.health.record_reconnect();
+        tokio::select! {
+            _ = sleep(delay) => {}
+            _ = self.shutdown.cancelled() => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_attempts: u32) -> ReconnectPolicy {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = policy(0);
+        assert_eq!(policy.base_delay(1), Duration::from_millis(100));
+        assert_eq!(policy.base_delay(3), Duration::from_millis(400));
+        assert_eq!(policy.base_delay(10), Duration::from_millis(1000));
+        assert_eq!(policy.base_delay(u32::MAX), Duration::from_millis(1000));
+
+        for attempt in 1..20 {
+            let delay = policy.backoff_delay(attempt);
+            let base = policy.base_delay(attempt);
+            assert!(delay >= base / 2 && delay <= base);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let health = Arc::new(FeedHealth::new());
+        let mut reconnector = Reconnector::new(
+            "Test",
+            ReconnectPolicy {
+                initial_delay: Duration::from_millis(1),
+                ..policy(2)
+            },
+            health.clone(),
+            CancellationToken::new(),
+        );
+
+        assert!(reconnector.backoff(Ok(())).await.is_ok());
+        assert!(reconnector
+            .backoff(Err(FeedError::InvalidData("boom".to_string())))
+            .await
+            .is_ok());
+        assert!(matches!(
+            reconnector.backoff(Ok(())).await,
+            Err(FeedError::ReconnectAttemptsExhausted { attempts: 2, .. })
+        ));
+        assert_eq!(health.status("test").reconnects, 2);
+    }
+
+    #[tokio::test]
+    async fn test_stop_cuts_backoff_short() {
+        let shutdown = CancellationToken::new();
+        let mut reconnector = Reconnector::new(
+            "Test",
+            ReconnectPolicy {
+                initial_delay: Duration::from_secs(3600),
+                ..policy(0)
+            },
+            Arc::new(FeedHealth::new()),
+            shutdown.clone(),
+        );
+
+        let stop = tokio::spawn(async move { shutdown.cancel() });
+        assert!(reconnector.backoff(Ok(())).await.is_ok());
+        stop.await.unwrap();
+    }
+}
@@ -0,0 +1,221 @@
+// Feed supervisor - keeps feed handlers running and reports their health
+
+use super::error::FeedError;
+use super::handler::{FeedHandler, FeedStatus};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
+
+/// Runs a set of [`FeedHandler`]s, restarting any that crash
+///
+/// A feed is restarted after `restart_delay` when it panics or exits with an
+/// error. Feeds that stop cleanly (via [`FeedHandler::stop`]), fail
+/// authentication or use up their reconnect attempts are not restarted: the
+/// feed's [`ReconnectPolicy`](super::reconnect::ReconnectPolicy) owns the
+/// retry limit, so `ws_max_reconnect_attempts = 0` is how to retry forever.
+///
+/// # Example
+/// ```rust,ignore
+/// let supervisor = FeedSupervisor::new(Duration::from_secs(5))
+///     .with_feed(Arc::new(binance_handler))
+///     .with_feed(Arc::new(okx_handler))
+///     .spawn();
+///
+/// for status in supervisor.statuses() {
+///     tracing::info!("{}: connected={}", status.name, status.connected);
+/// }
+/// supervisor.shutdown().await;
+/// ```
+pub struct FeedSupervisor {
+    feeds: Vec<Arc<dyn FeedHandler>>,
+    restart_delay: Duration,
+}
+
+impl FeedSupervisor {
+    pub fn new(restart_delay: Duration) -> Self {
+        Self {
+            feeds: Vec::new(),
+            restart_delay,
+        }
+    }
+
+    /// Add a feed to be supervised
+    pub fn with_feed(mut self, feed: Arc<dyn FeedHandler>) -> Self {
+        self.feeds.push(feed);
+        self
+    }
+
+    /// Start every feed on its own task
+    pub fn spawn(self) -> SupervisorHandle {
+        let tasks = self
+            .feeds
+            .iter()
+            .map(|feed| tokio::spawn(supervise(feed.clone(), self.restart_delay)))
+            .collect();
+
+        SupervisorHandle {
+            feeds: self.feeds,
+            tasks,
+        }
+    }
+}
+
+/// Handle to the running feeds returned by [`FeedSupervisor::spawn`]
+pub struct SupervisorHandle {
+    feeds: Vec<Arc<dyn FeedHandler>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl SupervisorHandle {
+    /// Health of every supervised feed
+    pub fn statuses(&self) -> Vec<FeedStatus> {
+        self.feeds.iter().map(|feed| feed.status()).collect()
+    }
+
+    /// Ask every feed to stop; supervision ends once they return
+    pub fn stop_all(&self) {
+        for feed in &self.feeds {
+            feed.stop();
+        }
+    }
+
+    /// Stop every feed and wait for the supervision tasks to finish
+    pub async fn shutdown(self) {
+        self.stop_all();
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+/// Run a feed until it stops cleanly or fails permanently
+async fn supervise(feed: Arc<dyn FeedHandler>, restart_delay: Duration) {
+    loop {
+        let run = tokio::spawn({
+            let feed = feed.clone();
+            async move { feed.start().await }
+        });
+
+        match run.await {
+            Ok(Ok(())) => {
+                tracing::info!("🛑 {} feed stopped", feed.name());
+                return;
+            }
+            Ok(Err(
+                e @ (FeedError::AuthenticationFailed { .. }
+                | FeedError::MissingCredentials { .. }
+                | FeedError::ReconnectAttemptsExhausted { .. }),
+            )) => {
+                tracing::error!("❌ {} feed failed permanently: {}", feed.name(), e);
+                return;
+            }
+            Ok(Err(e)) => tracing::error!("❌ {} feed exited: {}", feed.name(), e),
+            Err(e) => tracing::error!("💥 {} feed task panicked: {}", feed.name(), e),
+        }
+
+        feed.health().record_restart();
+        tracing::warn!("🔁 Restarting {} feed in {:?}", feed.name(), restart_delay);
+        sleep(restart_delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::inbound::feed_handler::{FeedHealth, FeedResult};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Panics on the first run, fails on the second, then stops cleanly
+    #[derive(Default)]
+    struct FlakyFeed {
+        runs: AtomicU32,
+        health: FeedHealth,
+    }
+
+    #[async_trait]
+    impl FeedHandler for FlakyFeed {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn start(&self) -> FeedResult<()> {
+            match self.runs.fetch_add(1, Ordering::SeqCst) {
+                0 => panic!("boom"),
+                1 => Err(FeedError::InvalidData("bad frame".to_string())),
+                _ => Ok(()),
+            }
+        }
+
+        fn stop(&self) {}
+
+        fn health(&self) -> &FeedHealth {
+            &self.health
+        }
+
+        fn subscriptions(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restarts_crashed_feed_until_clean_stop() {
+        let feed = Arc::new(FlakyFeed::default());
+        let supervisor = FeedSupervisor::new(Duration::from_millis(1))
+            .with_feed(feed.clone())
+            .spawn();
+
+        for task in supervisor.tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(feed.runs.load(Ordering::SeqCst), 3);
+        assert_eq!(feed.status().restarts, 2);
+    }
+
+    /// Gives up reconnecting on every run
+    #[derive(Default)]
+    struct ExhaustedFeed {
+        runs: AtomicU32,
+        health: FeedHealth,
+    }
+
+    #[async_trait]
+    impl FeedHandler for ExhaustedFeed {
+        fn name(&self) -> &'static str {
+            "exhausted"
+        }
+
+        async fn start(&self) -> FeedResult<()> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Err(FeedError::ReconnectAttemptsExhausted {
+                exchange: "Test".to_string(),
+                attempts: 3,
+                last_error: "connection refused".to_string(),
+            })
+        }
+
+        fn stop(&self) {}
+
+        fn health(&self) -> &FeedHealth {
+            &self.health
+        }
+
+        fn subscriptions(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_reconnects_not_restarted() {
+        let feed = Arc::new(ExhaustedFeed::default());
+        let supervisor = FeedSupervisor::new(Duration::from_millis(1))
+            .with_feed(feed.clone())
+            .spawn();
+
+        for task in supervisor.tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(feed.runs.load(Ordering::SeqCst), 1);
+        assert_eq!(feed.status().restarts, 0);
+    }
+}
@@ -1,10 +1,74 @@
 // gRPC Server - receives orders from satellites
 
-use kairos_proto::trading_engine_server::{TradingEngine as TradingEngineService, TradingEngineServer};
-use kairos_proto::{OrderRequest, OrderResponse, CancelOrderRequest, BalanceRequest, BalanceResponse, OrderStatusRequest, OrderStatusResponse};
+use crate::adapters::inbound::feed_handler::binance::BinanceSubscriptionHandle;
+use crate::application::engine::StrategyOrder;
+use crate::domain::risk::BalanceLedger;
+use kairos_domain::{DomainError, DomainResult, Exchange, InternalOrder, Price, Quantity, Symbol};
+use kairos_proto::trading_engine_server::{
+    TradingEngine as TradingEngineService, TradingEngineServer,
+};
+use kairos_proto::{
+    BalanceRequest, BalanceResponse, CancelOrderRequest, MarketSubscriptionRequest,
+    MarketSubscriptionResponse, OrderRequest, OrderResponse, OrderStatusRequest,
+    OrderStatusResponse,
+};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tonic::{transport::Server, Request, Response, Status};
 
-pub struct GrpcServer;
+/// Strategy name recorded on orders placed over gRPC
+const GRPC_STRATEGY: &str = "grpc";
+
+pub struct GrpcServer {
+    ledger: Arc<BalanceLedger>,
+    order_tx: mpsc::Sender<StrategyOrder>,
+    market_subscriptions: Option<BinanceSubscriptionHandle>,
+}
+
+impl GrpcServer {
+    pub fn new(ledger: Arc<BalanceLedger>, order_tx: mpsc::Sender<StrategyOrder>) -> Self {
+        Self {
+            ledger,
+            order_tx,
+            market_subscriptions: None,
+        }
+    }
+
+    /// Serve `SubscribeMarket`/`UnsubscribeMarket` through the Binance feed
+    pub fn with_market_subscriptions(mut self, handle: Option<BinanceSubscriptionHandle>) -> Self {
+        self.market_subscriptions = handle;
+        self
+    }
+
+    /// Resolve a subscription request to the feed handle and native symbol
+    fn subscription_target(
+        &self,
+        req: &MarketSubscriptionRequest,
+    ) -> Result<(&BinanceSubscriptionHandle, String), Status> {
+        let exchange = req
+            .exchange
+            .parse::<Exchange>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let symbol = req
+            .symbol
+            .trim()
+            .parse::<Symbol>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        if exchange != Exchange::Binance {
+            return Err(Status::unimplemented(format!(
+                "runtime subscriptions are not supported on {:?}",
+                exchange
+            )));
+        }
+
+        let handle = self
+            .market_subscriptions
+            .as_ref()
+            .ok_or_else(|| Status::unavailable("Binance market data feed is not running"))?;
+        Ok((handle, symbol.to_exchange(&exchange)))
+    }
+}
 
 #[tonic::async_trait]
 impl TradingEngineService for GrpcServer {
@@ -15,13 +79,25 @@ impl TradingEngineService for GrpcServer {
         let req = request.into_inner();
         tracing::info!("Received order via gRPC: {:?}", req);
 
-        // TODO: Convert to InternalOrder and send to MPSC channel
-        
+        let (exchange, order) = req
+            .exchange
+            .parse::<Exchange>()
+            .and_then(|exchange| Ok((exchange, to_internal_order(&req)?)))
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        // Risk checks and execution happen asynchronously in the engine
+        let intent = StrategyOrder::new(GRPC_STRATEGY, exchange.clone(), order);
+        let order_id = intent.client_order_id.clone();
+        self.order_tx
+            .send(intent)
+            .await
+            .map_err(|_| Status::unavailable("trading engine is not running"))?;
+
         let response = OrderResponse {
             success: true,
-            order_id: uuid::Uuid::new_v4().to_string(),
-            message: "Order received".to_string(),
-            status: 0, // Pending
+            order_id,
+            message: format!("Order queued for {:?}", exchange),
+            status: kairos_proto::OrderStatus::Pending as i32,
         };
 
         Ok(Response::new(response))
@@ -37,13 +113,19 @@ impl TradingEngineService for GrpcServer {
 
     async fn get_balance(
         &self,
-        _request: Request<BalanceRequest>,
+        request: Request<BalanceRequest>,
     ) -> Result<Response<BalanceResponse>, Status> {
-        // TODO: Return actual balance from RiskEngine
+        let req = request.into_inner();
+        if req.currency.trim().is_empty() {
+            return Err(Status::invalid_argument("currency is required"));
+        }
+
+        // Summed across venues; unknown currencies report zero
+        let balance = self.ledger.total(req.currency.trim());
         let response = BalanceResponse {
-            available: 10000.0,
-            locked: 0.0,
-            total: 10000.0,
+            available: balance.free.to_f64(),
+            locked: balance.locked.to_f64(),
+            total: balance.total().to_f64(),
         };
         Ok(Response::new(response))
     }
@@ -55,18 +137,197 @@ impl TradingEngineService for GrpcServer {
         // TODO: Implement order status lookup
         Err(Status::unimplemented("Not implemented yet"))
     }
+
+    async fn subscribe_market(
+        &self,
+        request: Request<MarketSubscriptionRequest>,
+    ) -> Result<Response<MarketSubscriptionResponse>, Status> {
+        let req = request.into_inner();
+        let (handle, native) = self.subscription_target(&req)?;
+        handle
+            .add_symbol(&native)
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+        tracing::info!("➕ Subscribed {} on {} via gRPC", req.symbol, req.exchange);
+
+        Ok(Response::new(MarketSubscriptionResponse {
+            success: true,
+            message: format!("Subscribed to {}", req.symbol),
+            streams: handle.streams_for(&native),
+        }))
+    }
+
+    async fn unsubscribe_market(
+        &self,
+        request: Request<MarketSubscriptionRequest>,
+    ) -> Result<Response<MarketSubscriptionResponse>, Status> {
+        let req = request.into_inner();
+        let (handle, native) = self.subscription_target(&req)?;
+        handle
+            .remove_symbol(&native)
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+        tracing::info!(
+            "➖ Unsubscribed {} on {} via gRPC",
+            req.symbol,
+            req.exchange
+        );
+
+        Ok(Response::new(MarketSubscriptionResponse {
+            success: true,
+            message: format!("Unsubscribed from {}", req.symbol),
+            streams: handle.streams_for(&native),
+        }))
+    }
+}
+
+/// Convert a proto order into the domain representation
+///
+/// Doubles go through their shortest round-trip form, so a client sending
+/// 0.1 gets exactly 0.1 rather than the nearest binary fraction.
+fn to_internal_order(req: &OrderRequest) -> DomainResult<InternalOrder> {
+    let quantity = Quantity::try_from(req.quantity)
+        .ok()
+        .filter(Quantity::is_positive)
+        .ok_or_else(|| {
+            DomainError::InvalidQuantity(format!("invalid quantity {}", req.quantity))
+        })?;
+
+    let price = match req.order_type() {
+        kairos_proto::OrderType::Market => None,
+        kairos_proto::OrderType::Limit => {
+            let price = req
+                .price
+                .and_then(|price| Price::try_from(price).ok())
+                .filter(Price::is_positive)
+                .ok_or_else(|| {
+                    DomainError::InvalidPrice("limit order needs a positive price".to_string())
+                })?;
+            Some(price)
+        }
+    };
+
+    Ok(InternalOrder {
+        symbol: req.symbol.clone(),
+        side: match req.side() {
+            kairos_proto::OrderSide::Buy => kairos_domain::OrderSide::Buy,
+            kairos_proto::OrderSide::Sell => kairos_domain::OrderSide::Sell,
+        },
+        quantity,
+        price,
+        risk_score: 0.0,
+    })
 }
 
-pub async fn start_grpc_server(addr: String) -> anyhow::Result<()> {
-    let service = GrpcServer;
+/// Serve until `shutdown` is cancelled, letting in-flight requests finish
+pub async fn start_grpc_server(
+    addr: String,
+    ledger: Arc<BalanceLedger>,
+    order_tx: mpsc::Sender<StrategyOrder>,
+    market_subscriptions: Option<BinanceSubscriptionHandle>,
+    shutdown: CancellationToken,
+) -> anyhow::Result<()> {
+    let service = GrpcServer::new(ledger, order_tx).with_market_subscriptions(market_subscriptions);
     let addr = addr.parse()?;
 
     tracing::info!("🌐 Starting gRPC server on {}", addr);
 
     Server::builder()
         .add_service(TradingEngineServer::new(service))
-        .serve(addr)
+        .serve_with_shutdown(addr, shutdown.cancelled_owned())
         .await?;
 
+    tracing::info!("🛑 gRPC server stopped");
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        order_type: kairos_proto::OrderType,
+        quantity: f64,
+        price: Option<f64>,
+    ) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTC/USDT".to_string(),
+            side: kairos_proto::OrderSide::Sell as i32,
+            order_type: order_type as i32,
+            quantity,
+            price,
+            exchange: "binance".to_string(),
+        }
+    }
+
+    fn subscription(symbol: &str, exchange: &str) -> Request<MarketSubscriptionRequest> {
+        Request::new(MarketSubscriptionRequest {
+            symbol: symbol.to_string(),
+            exchange: exchange.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_market_subscriptions() {
+        use crate::adapters::inbound::feed_handler::binance::{BinanceConfig, BinanceFeedHandler};
+
+        let (order_tx, _order_rx) = mpsc::channel(1);
+        let server = GrpcServer::new(Arc::new(BalanceLedger::new()), order_tx);
+        let status = server
+            .subscribe_market(subscription("SOL/USDT", "binance"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let (market_tx, _market_rx) = tokio::sync::broadcast::channel(1);
+        let feed = BinanceFeedHandler::new_public(
+            BinanceConfig {
+                ws_url: String::new(),
+                rest_url: String::new(),
+            },
+            market_tx,
+            Vec::new(),
+        );
+        let server = server.with_market_subscriptions(Some(feed.subscription_handle()));
+
+        let response = server
+            .subscribe_market(subscription("SOL/USDT", "binance"))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(response.success);
+        assert!(response
+            .streams
+            .iter()
+            .all(|stream| stream.starts_with("solusdt@")));
+        assert!(!response.streams.is_empty());
+
+        let status = server
+            .unsubscribe_market(subscription("SOL-USDT", "binance"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        let status = server
+            .subscribe_market(subscription("SOL/USDT", "okx"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unimplemented);
+    }
+
+    #[test]
+    fn test_to_internal_order() {
+        let order = to_internal_order(&request(kairos_proto::OrderType::Limit, 0.1, Some(67432.1)))
+            .unwrap();
+        assert_eq!(order.quantity, "0.1".parse().unwrap());
+        assert_eq!(order.price, Some("67432.1".parse().unwrap()));
+        assert_eq!(order.side, kairos_domain::OrderSide::Sell);
+
+        let market =
+            to_internal_order(&request(kairos_proto::OrderType::Market, 1.0, Some(5.0))).unwrap();
+        assert_eq!(market.price, None);
+
+        assert!(to_internal_order(&request(kairos_proto::OrderType::Limit, 1.0, None)).is_err());
+        assert!(
+            to_internal_order(&request(kairos_proto::OrderType::Market, f64::NAN, None)).is_err()
+        );
+    }
+}
@@ -2,3 +2,4 @@
 
 pub mod feed_handler;
 pub mod grpc_server;
+pub mod reference_data;
@@ -0,0 +1,151 @@
+// Binance instrument metadata (`GET /api/v3/exchangeInfo`)
+
+use super::error::{ReferenceDataError, ReferenceDataResult};
+use super::parse_step;
+use kairos_domain::{Exchange, Instrument, Notional, Symbol};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct ExchangeInfo {
+    symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SymbolInfo {
+    symbol: String,
+    status: String,
+    base_asset: String,
+    quote_asset: String,
+    filters: Vec<SymbolFilter>,
+}
+
+/// Subset of symbol filters that map onto [`Instrument`] fields
+#[derive(Debug, Deserialize)]
+#[serde(tag = "filterType", rename_all = "SCREAMING_SNAKE_CASE")]
+enum SymbolFilter {
+    #[serde(rename_all = "camelCase")]
+    PriceFilter { tick_size: String },
+    #[serde(rename_all = "camelCase")]
+    LotSize { step_size: String, min_qty: String },
+    #[serde(rename_all = "camelCase")]
+    MinNotional { min_notional: String },
+    #[serde(rename_all = "camelCase")]
+    Notional { min_notional: String },
+    #[serde(other)]
+    Other,
+}
+
+/// Fetch trading rules for the given symbols
+pub async fn fetch_instruments(
+    http: &reqwest::Client,
+    rest_url: &str,
+    symbols: &[Symbol],
+) -> ReferenceDataResult<Vec<Instrument>> {
+    let venue_symbols: Vec<String> = symbols
+        .iter()
+        .map(|symbol| symbol.to_exchange(&Exchange::Binance))
+        .collect();
+
+    let body = http
+        .get(format!("{}/api/v3/exchangeInfo", rest_url))
+        .query(&[("symbols", serde_json::to_string(&venue_symbols)?)])
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    parse_exchange_info(&body)
+}
+
+/// Convert an `exchangeInfo` response into instruments
+fn parse_exchange_info(body: &str) -> ReferenceDataResult<Vec<Instrument>> {
+    let info: ExchangeInfo = serde_json::from_str(body)?;
+
+    info.symbols
+        .into_iter()
+        .filter(|info| info.status == "TRADING")
+        .map(|info| {
+            let mut tick_size = None;
+            let mut lot = None;
+            let mut min_notional = Notional::ZERO;
+
+            for filter in &info.filters {
+                match filter {
+                    SymbolFilter::PriceFilter { tick_size: tick } => {
+                        tick_size = Some(parse_step("tickSize", tick)?)
+                    }
+                    SymbolFilter::LotSize { step_size, min_qty } => {
+                        lot = Some((
+                            parse_step("stepSize", step_size)?,
+                            parse_step("minQty", min_qty)?,
+                        ))
+                    }
+                    SymbolFilter::MinNotional {
+                        min_notional: value,
+                    }
+                    | SymbolFilter::Notional {
+                        min_notional: value,
+                    } => min_notional = parse_step("minNotional", value)?,
+                    SymbolFilter::Other => {}
+                }
+            }
+
+            let (Some(tick_size), Some((lot_size, min_quantity))) = (tick_size, lot) else {
+                return Err(ReferenceDataError::InvalidData(format!(
+                    "{} is missing PRICE_FILTER or LOT_SIZE",
+                    info.symbol
+                )));
+            };
+
+            Ok(Instrument::new(
+                Exchange::Binance,
+                Symbol::new(info.base_asset, info.quote_asset),
+                tick_size,
+                lot_size,
+                min_quantity,
+                min_notional,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exchange_info() {
+        let body = r#"{
+            "timezone": "UTC",
+            "symbols": [{
+                "symbol": "BTCUSDT",
+                "status": "TRADING",
+                "baseAsset": "BTC",
+                "quoteAsset": "USDT",
+                "filters": [
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01000000", "maxPrice": "1000000.00000000", "tickSize": "0.01000000"},
+                    {"filterType": "LOT_SIZE", "minQty": "0.00001000", "maxQty": "9000.00000000", "stepSize": "0.00001000"},
+                    {"filterType": "ICEBERG_PARTS", "limit": 10},
+                    {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true}
+                ]
+            }]
+        }"#;
+
+        let instruments = parse_exchange_info(body).unwrap();
+        assert_eq!(
+            instruments,
+            vec![Instrument::new(
+                Exchange::Binance,
+                Symbol::new("BTC", "USDT"),
+                "0.01".parse().unwrap(),
+                "0.00001".parse().unwrap(),
+                "0.00001".parse().unwrap(),
+                "5".parse().unwrap()
+            )]
+        );
+        assert_eq!(instruments[0].price_precision, 2);
+        assert_eq!(instruments[0].quantity_precision, 5);
+    }
+}
@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+/// Instrument reference data errors
+#[derive(Error, Debug)]
+pub enum ReferenceDataError {
+    #[error("REST request failed")]
+    HttpError(#[from] reqwest::Error),
+
+    #[error("JSON error")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Instrument cache I/O failed")]
+    IoError(#[from] std::io::Error),
+
+    #[error("{exchange} API error {code}: {message}")]
+    ApiError {
+        exchange: String,
+        code: String,
+        message: String,
+    },
+
+    #[error("Instrument {symbol} not listed on {exchange}")]
+    MissingInstrument { exchange: String, symbol: String },
+
+    #[error("Invalid instrument data: {0}")]
+    InvalidData(String),
+
+    #[error("Invalid markets configuration")]
+    ConfigError(#[from] crate::config::ConfigError),
+}
+
+pub type ReferenceDataResult<T> = Result<T, ReferenceDataError>;
@@ -0,0 +1,153 @@
+// Kraken instrument metadata (`GET /0/public/AssetPairs`)
+
+use super::error::{ReferenceDataError, ReferenceDataResult};
+use super::parse_step;
+use kairos_domain::{Exchange, Instrument, Notional, Price, Quantity, Symbol};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+struct KrakenResponse {
+    error: Vec<String>,
+    #[serde(default)]
+    result: HashMap<String, KrakenAssetPair>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KrakenAssetPair {
+    /// "XBT/USDT" style name; dark pools (".d") have none
+    wsname: Option<String>,
+    pair_decimals: u32,
+    lot_decimals: u32,
+    ordermin: Option<String>,
+    costmin: Option<String>,
+    tick_size: Option<String>,
+    #[serde(default = "online")]
+    status: String,
+}
+
+fn online() -> String {
+    "online".to_string()
+}
+
+/// Fetch spot trading rules and keep the configured instruments
+pub async fn fetch_instruments(
+    http: &reqwest::Client,
+    rest_url: &str,
+    symbols: &[Symbol],
+) -> ReferenceDataResult<Vec<Instrument>> {
+    let body = http
+        .get(format!("{}/0/public/AssetPairs", rest_url))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    Ok(parse_instruments(&body)?
+        .into_iter()
+        .filter(|instrument| symbols.contains(&instrument.symbol))
+        .collect())
+}
+
+/// Convert an `AssetPairs` response into instruments
+fn parse_instruments(body: &str) -> ReferenceDataResult<Vec<Instrument>> {
+    let response: KrakenResponse = serde_json::from_str(body)?;
+    if let Some(error) = response.error.first() {
+        let (code, message) = error.split_once(':').unwrap_or((error, ""));
+        return Err(ReferenceDataError::ApiError {
+            exchange: "Kraken".to_string(),
+            code: code.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    response
+        .result
+        .into_values()
+        .filter(|pair| pair.status == "online")
+        .filter_map(|pair| Some((canonical_symbol(pair.wsname.as_deref()?)?, pair)))
+        .map(|(symbol, pair)| {
+            let tick_size = match &pair.tick_size {
+                Some(tick_size) => parse_step("tick_size", tick_size)?,
+                None => Price::new(Decimal::new(1, pair.pair_decimals)),
+            };
+            let lot_size = Quantity::new(Decimal::new(1, pair.lot_decimals));
+            let min_quantity = match &pair.ordermin {
+                Some(ordermin) => parse_step("ordermin", ordermin)?,
+                None => lot_size,
+            };
+            let min_notional = match &pair.costmin {
+                Some(costmin) => parse_step("costmin", costmin)?,
+                None => Notional::ZERO,
+            };
+            // Venue symbol is the WebSocket v2 form ("BTC/USDT"), as used by
+            // the feed and the executor
+            Ok(Instrument::new(
+                Exchange::Kraken,
+                symbol,
+                tick_size,
+                lot_size,
+                min_quantity,
+                min_notional,
+            ))
+        })
+        .collect()
+}
+
+/// Canonical symbol for a `wsname`, using the v2 asset codes ("XBT" -> "BTC")
+fn canonical_symbol(wsname: &str) -> Option<Symbol> {
+    let (base, quote) = wsname.split_once('/')?;
+    let asset = |code: &str| match code {
+        "XBT" => "BTC".to_string(),
+        "XDG" => "DOGE".to_string(),
+        other => other.to_string(),
+    };
+    Some(Symbol::new(asset(base), asset(quote)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instruments() {
+        let body = r#"{
+            "error": [],
+            "result": {
+                "XBTUSDT": {"altname": "XBTUSDT", "wsname": "XBT/USDT", "base": "XXBT", "quote": "USDT",
+                            "pair_decimals": 1, "lot_decimals": 8, "ordermin": "0.0001",
+                            "costmin": "0.5", "tick_size": "0.1", "status": "online"},
+                "ETHXBT": {"altname": "ETHXBT", "wsname": "ETH/XBT", "base": "XETH", "quote": "XXBT",
+                           "pair_decimals": 5, "lot_decimals": 8, "ordermin": "0.002",
+                           "status": "online"},
+                "OLDUSD": {"altname": "OLDUSD", "wsname": "OLD/USD", "base": "OLD", "quote": "ZUSD",
+                           "pair_decimals": 4, "lot_decimals": 2, "status": "delisted"}
+            }
+        }"#;
+
+        let mut instruments = parse_instruments(body).unwrap();
+        instruments.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        assert_eq!(instruments.len(), 2);
+
+        let btc = &instruments[0];
+        assert_eq!(btc.symbol, Symbol::new("BTC", "USDT"));
+        assert_eq!(btc.venue_symbol, "BTC/USDT");
+        assert_eq!(btc.tick_size, "0.1".parse().unwrap());
+        assert_eq!(btc.quantity_precision, 8);
+        assert_eq!(btc.min_notional, "0.5".parse().unwrap());
+
+        // Without `tick_size` the tick comes from `pair_decimals`
+        let eth = &instruments[1];
+        assert_eq!(eth.symbol, Symbol::new("ETH", "BTC"));
+        assert_eq!(eth.tick_size, "0.00001".parse().unwrap());
+        assert_eq!(eth.min_quantity, "0.002".parse().unwrap());
+
+        let error = parse_instruments(r#"{"error": ["EGeneral:Too many requests"]}"#);
+        assert!(matches!(
+            error,
+            Err(ReferenceDataError::ApiError { code, .. }) if code == "EGeneral"
+        ));
+    }
+}
@@ -0,0 +1,140 @@
+// Reference data - instrument metadata from exchange REST APIs
+
+pub mod binance;
+pub mod error;
+pub mod kraken;
+pub mod okx;
+
+pub use error::{ReferenceDataError, ReferenceDataResult};
+
+use crate::config::{MarketsSettings, Settings};
+use kairos_domain::{Exchange, InstrumentRegistry};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Loads the [`InstrumentRegistry`] for the configured `[markets]`
+///
+/// Fetches live metadata from each exchange and refreshes the JSON cache;
+/// when an exchange is unreachable the last cached registry is used instead.
+pub struct InstrumentLoader {
+    http: reqwest::Client,
+    binance_rest_url: String,
+    okx_rest_url: String,
+    kraken_rest_url: String,
+    cache_path: PathBuf,
+}
+
+impl InstrumentLoader {
+    /// Build a loader from exchange REST URLs and `markets.instrument_cache`
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let loader = InstrumentLoader::from_settings(&settings);
+    /// let instruments = loader.load(&settings.markets).await?;
+    /// ```
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            binance_rest_url: settings.exchange.binance_rest_url.clone(),
+            okx_rest_url: settings.exchange.okx_rest_url.clone(),
+            kraken_rest_url: settings.exchange.kraken_rest_url.clone(),
+            cache_path: PathBuf::from(&settings.markets.instrument_cache),
+        }
+    }
+
+    /// Fetch instruments from the exchanges, falling back to the cache file
+    pub async fn load(&self, markets: &MarketsSettings) -> ReferenceDataResult<InstrumentRegistry> {
+        match self.fetch(markets).await {
+            Ok(registry) => {
+                if let Err(e) = self.write_cache(&registry) {
+                    tracing::warn!(
+                        "Failed to write instrument cache {}: {}",
+                        self.cache_path.display(),
+                        e
+                    );
+                }
+                tracing::info!("📐 Loaded {} instruments from exchanges", registry.len());
+                Ok(registry)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Instrument fetch failed ({}), using cache {}",
+                    e,
+                    self.cache_path.display()
+                );
+                let registry = self.read_cache()?;
+                tracing::info!("📐 Loaded {} instruments from cache", registry.len());
+                Ok(registry)
+            }
+        }
+    }
+
+    /// Fetch every configured instrument, failing if any is not listed
+    async fn fetch(&self, markets: &MarketsSettings) -> ReferenceDataResult<InstrumentRegistry> {
+        let mut registry = InstrumentRegistry::new();
+
+        let binance = markets.symbols(&Exchange::Binance)?;
+        if !binance.is_empty() {
+            for instrument in
+                binance::fetch_instruments(&self.http, &self.binance_rest_url, &binance).await?
+            {
+                registry.insert(instrument);
+            }
+        }
+
+        let okx = markets.symbols(&Exchange::OKX)?;
+        if !okx.is_empty() {
+            for instrument in okx::fetch_instruments(&self.http, &self.okx_rest_url, &okx).await? {
+                registry.insert(instrument);
+            }
+        }
+
+        let kraken = markets.symbols(&Exchange::Kraken)?;
+        if !kraken.is_empty() {
+            for instrument in
+                kraken::fetch_instruments(&self.http, &self.kraken_rest_url, &kraken).await?
+            {
+                registry.insert(instrument);
+            }
+        }
+
+        for (exchange, symbols) in [
+            (Exchange::Binance, binance),
+            (Exchange::OKX, okx),
+            (Exchange::Kraken, kraken),
+        ] {
+            if let Some(missing) = symbols
+                .iter()
+                .find(|s| registry.get(&exchange, s).is_none())
+            {
+                return Err(ReferenceDataError::MissingInstrument {
+                    exchange: format!("{:?}", exchange),
+                    symbol: missing.to_string(),
+                });
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Read the registry saved by the last successful fetch
+    pub fn read_cache(&self) -> ReferenceDataResult<InstrumentRegistry> {
+        let json = std::fs::read_to_string(&self.cache_path)?;
+        Ok(InstrumentRegistry::from_json(&json)?)
+    }
+
+    fn write_cache(&self, registry: &InstrumentRegistry) -> ReferenceDataResult<()> {
+        if let Some(parent) = self.cache_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.cache_path, registry.to_json()?)?;
+        Ok(())
+    }
+}
+
+/// Parse a decimal string step/minimum field into a fixed-point value
+fn parse_step<T: FromStr>(field: &str, value: &str) -> ReferenceDataResult<T> {
+    value
+        .parse::<T>()
+        .map_err(|_| ReferenceDataError::InvalidData(format!("{} = '{}'", field, value)))
+}
@@ -0,0 +1,107 @@
+// OKX instrument metadata (`GET /api/v5/public/instruments`)
+
+use super::error::{ReferenceDataError, ReferenceDataResult};
+use super::parse_step;
+use kairos_domain::{Exchange, Instrument, Notional, Symbol};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct OkxResponse {
+    code: String,
+    msg: String,
+    data: Vec<OkxInstrument>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxInstrument {
+    inst_id: String,
+    base_ccy: String,
+    quote_ccy: String,
+    tick_sz: String,
+    lot_sz: String,
+    min_sz: String,
+    state: String,
+}
+
+/// Fetch spot trading rules and keep the configured instruments
+pub async fn fetch_instruments(
+    http: &reqwest::Client,
+    rest_url: &str,
+    symbols: &[Symbol],
+) -> ReferenceDataResult<Vec<Instrument>> {
+    let body = http
+        .get(format!("{}/api/v5/public/instruments", rest_url))
+        .query(&[("instType", "SPOT")])
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    Ok(parse_instruments(&body)?
+        .into_iter()
+        .filter(|instrument| symbols.contains(&instrument.symbol))
+        .collect())
+}
+
+/// Convert an `instruments` response into instruments
+fn parse_instruments(body: &str) -> ReferenceDataResult<Vec<Instrument>> {
+    let response: OkxResponse = serde_json::from_str(body)?;
+    if response.code != "0" {
+        return Err(ReferenceDataError::ApiError {
+            exchange: "OKX".to_string(),
+            code: response.code,
+            message: response.msg,
+        });
+    }
+
+    response
+        .data
+        .into_iter()
+        .filter(|instrument| instrument.state == "live")
+        .map(|instrument| {
+            let min_quantity = parse_step("minSz", &instrument.min_sz)?;
+            let mut parsed = Instrument::new(
+                Exchange::OKX,
+                Symbol::new(instrument.base_ccy, instrument.quote_ccy),
+                parse_step("tickSz", &instrument.tick_sz)?,
+                parse_step("lotSz", &instrument.lot_sz)?,
+                min_quantity,
+                // OKX spot has no minimum notional, only a minimum size
+                Notional::ZERO,
+            );
+            parsed.venue_symbol = instrument.inst_id;
+            Ok(parsed)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instruments() {
+        let body = r#"{
+            "code": "0",
+            "msg": "",
+            "data": [
+                {"instType": "SPOT", "instId": "BTC-USDT", "baseCcy": "BTC", "quoteCcy": "USDT",
+                 "tickSz": "0.1", "lotSz": "0.00000001", "minSz": "0.00001", "state": "live"},
+                {"instType": "SPOT", "instId": "OLD-USDT", "baseCcy": "OLD", "quoteCcy": "USDT",
+                 "tickSz": "0.0001", "lotSz": "1", "minSz": "1", "state": "suspend"}
+            ]
+        }"#;
+
+        let instruments = parse_instruments(body).unwrap();
+        assert_eq!(instruments.len(), 1);
+        assert_eq!(instruments[0].venue_symbol, "BTC-USDT");
+        assert_eq!(instruments[0].price_precision, 1);
+        assert_eq!(instruments[0].quantity_precision, 8);
+        assert_eq!(instruments[0].min_quantity, "0.00001".parse().unwrap());
+
+        let error = parse_instruments(r#"{"code": "50011", "msg": "Rate limit", "data": []}"#);
+        assert!(matches!(error, Err(ReferenceDataError::ApiError { .. })));
+    }
+}
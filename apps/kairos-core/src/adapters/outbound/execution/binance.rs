@@ -1,32 +1,550 @@
 // Binance execution client
 
-use super::error::ExecutionResult;
+use super::error::{ExecutionError, ExecutionResult};
+use super::executor::{OrderAck, OrderExecutor, OrderRequest};
+use super::resolve_symbol;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use kairos_domain::{
+    CheckedDiv, Exchange, Notional, OrderSide, OrderStatus, OrderType, Quantity, Symbol,
+    TimeInForce,
+};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+const DEFAULT_REST_URL: &str = "https://api.binance.com";
+const DEFAULT_RECV_WINDOW_MS: u64 = 5000;
+const ORDER_PATH: &str = "/api/v3/order";
+const OPEN_ORDERS_PATH: &str = "/api/v3/openOrders";
+const TIME_PATH: &str = "/api/v3/time";
+
+/// Binance error code for a request timestamp outside `recvWindow`
+const TIMESTAMP_OUTSIDE_RECV_WINDOW: i64 = -1021;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceOrderResponse {
+    symbol: String,
+    order_id: u64,
+    client_order_id: String,
+    /// Set on cancel responses, where `clientOrderId` identifies the cancel itself
+    #[serde(default)]
+    orig_client_order_id: Option<String>,
+    executed_qty: String,
+    cummulative_quote_qty: String,
+    status: String,
+    #[serde(default)]
+    transact_time: Option<i64>,
+    #[serde(default)]
+    update_time: Option<i64>,
+}
+
+/// Error body of a rejected request (`{"code": -2010, "msg": "..."}`)
+#[derive(Debug, Deserialize)]
+struct BinanceApiError {
+    code: i64,
+    msg: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BinanceServerTime {
+    server_time: i64,
+}
 
 pub struct BinanceExecutor {
     api_key: String,
     api_secret: String,
+    markets: Vec<Symbol>,
+    rest_url: String,
+    recv_window_ms: u64,
+    http: reqwest::Client,
+    /// Binance server time minus local time, refreshed by [`BinanceExecutor::sync_time`]
+    time_offset_ms: AtomicI64,
 }
 
 impl BinanceExecutor {
-    pub fn new(api_key: String, api_secret: String) -> Self {
+    pub fn new(api_key: String, api_secret: String, markets: Vec<Symbol>) -> Self {
         Self {
             api_key,
             api_secret,
+            markets,
+            rest_url: DEFAULT_REST_URL.to_string(),
+            recv_window_ms: DEFAULT_RECV_WINDOW_MS,
+            http: reqwest::Client::new(),
+            time_offset_ms: AtomicI64::new(0),
+        }
+    }
+
+    /// Override the REST base URL (e.g. testnet or a local mock)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let executor = BinanceExecutor::new(api_key, api_secret, markets)
+    ///     .with_rest_url(&settings.exchange.binance_rest_url)
+    ///     .with_recv_window(settings.exchange.binance_recv_window_ms);
+    /// executor.sync_time().await?;
+    /// ```
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Override how long (ms) a signed request stays valid on the server
+    pub fn with_recv_window(mut self, recv_window_ms: u64) -> Self {
+        self.recv_window_ms = recv_window_ms;
+        self
+    }
+
+    /// List open orders for one symbol, or for the whole account when `None`
+    pub async fn open_orders(&self, symbol: Option<&str>) -> ExecutionResult<Vec<OrderAck>> {
+        let params = match symbol {
+            Some(symbol) => vec![(
+                "symbol",
+                resolve_symbol(&self.markets, &Exchange::Binance, symbol)?,
+            )],
+            None => Vec::new(),
+        };
+
+        let response: Vec<BinanceOrderResponse> = self
+            .signed_request(Method::GET, OPEN_ORDERS_PATH, &params)
+            .await?;
+        response.into_iter().map(into_ack).collect()
+    }
+
+    /// Measure the offset between local and Binance server time
+    ///
+    /// Called automatically when Binance rejects a request timestamp (-1021).
+    pub async fn sync_time(&self) -> ExecutionResult<()> {
+        let response = self
+            .http
+            .get(format!("{}{}", self.rest_url, TIME_PATH))
+            .send()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+        let time: BinanceServerTime = parse_response(status, &body)?;
+
+        let offset = time.server_time - Utc::now().timestamp_millis();
+        self.time_offset_ms.store(offset, Ordering::Relaxed);
+        tracing::debug!("Binance server time offset: {}ms", offset);
+        Ok(())
+    }
+
+    /// Build the `/api/v3/order` parameters for an order
+    fn order_params(&self, request: &OrderRequest) -> ExecutionResult<Vec<(&'static str, String)>> {
+        request.validate()?;
+        if request.reduce_only {
+            return Err(ExecutionError::InvalidOrder(
+                "reduce-only orders are not supported on Binance spot".to_string(),
+            ));
+        }
+
+        let side = match request.side {
+            OrderSide::Buy => "BUY",
+            OrderSide::Sell => "SELL",
+        };
+        let order_type = match (&request.order_type, request.post_only) {
+            (OrderType::Market, _) => "MARKET",
+            (OrderType::Limit, false) => "LIMIT",
+            (OrderType::Limit, true) => "LIMIT_MAKER",
+        };
+
+        let mut params = vec![
+            (
+                "symbol",
+                resolve_symbol(&self.markets, &Exchange::Binance, &request.symbol)?,
+            ),
+            ("side", side.to_string()),
+            ("type", order_type.to_string()),
+            ("quantity", request.quantity.to_string()),
+        ];
+
+        if let Some(price) = request.price {
+            // LIMIT_MAKER is implicitly GTC and rejects an explicit timeInForce
+            if order_type == "LIMIT" {
+                let time_in_force = match request.time_in_force {
+                    TimeInForce::Gtc => "GTC",
+                    TimeInForce::Ioc => "IOC",
+                    TimeInForce::Fok => "FOK",
+                };
+                params.push(("timeInForce", time_in_force.to_string()));
+            }
+            params.push(("price", price.to_string()));
+        }
+
+        params.push(("newClientOrderId", request.client_order_id.clone()));
+        params.push(("newOrderRespType", "RESULT".to_string()));
+
+        Ok(params)
+    }
+
+    /// Send a signed request, resyncing server time once if the timestamp is rejected
+    async fn signed_request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> ExecutionResult<T> {
+        let (status, body) = self.send_signed(method.clone(), path, params).await?;
+
+        let timestamp_rejected = !status.is_success()
+            && serde_json::from_str::<BinanceApiError>(&body)
+                .is_ok_and(|e| e.code == TIMESTAMP_OUTSIDE_RECV_WINDOW);
+        if timestamp_rejected {
+            tracing::warn!("Binance rejected request timestamp, resyncing server time");
+            self.sync_time().await?;
+            let (status, body) = self.send_signed(method, path, params).await?;
+            return parse_response(status, &body);
         }
+
+        parse_response(status, &body)
+    }
+
+    /// Append `timestamp`/`recvWindow`, sign the query string and send it
+    async fn send_signed(
+        &self,
+        method: Method,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> ExecutionResult<(StatusCode, String)> {
+        let timestamp = Utc::now().timestamp_millis() + self.time_offset_ms.load(Ordering::Relaxed);
+
+        let mut query = serde_urlencoded::to_string(params)
+            .map_err(|e| ExecutionError::InvalidOrder(e.to_string()))?;
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.push_str(&format!(
+            "recvWindow={}&timestamp={}",
+            self.recv_window_ms, timestamp
+        ));
+        let signature = sign(&self.api_secret, &query);
+
+        let response = self
+            .http
+            .request(
+                method,
+                format!(
+                    "{}{}?{}&signature={}",
+                    self.rest_url, path, query, signature
+                ),
+            )
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+        Ok((status, body))
+    }
+}
+
+#[async_trait]
+impl OrderExecutor for BinanceExecutor {
+    fn exchange(&self) -> Exchange {
+        Exchange::Binance
+    }
+
+    /// Submit a market, limit or maker-only order (`POST /api/v3/order`)
+    async fn submit_order(&self, request: &OrderRequest) -> ExecutionResult<OrderAck> {
+        let params = self.order_params(request)?;
+        tracing::info!(
+            "Placing order on Binance: {:?} {:?} {} {} @ {:?}",
+            request.order_type,
+            request.side,
+            request.quantity,
+            request.symbol,
+            request.price
+        );
+
+        let response: BinanceOrderResponse = self
+            .signed_request(Method::POST, ORDER_PATH, &params)
+            .await?;
+        into_ack(response)
     }
 
-    pub async fn place_order(
+    /// Cancel an open order (`DELETE /api/v3/order`)
+    async fn cancel_order(
         &self,
-        _symbol: &str,
-        _side: &str,
-        _quantity: f64,
-    ) -> ExecutionResult<String> {
-        // TODO: Implement HTTP REST API call to Binance
-        // 1. Sign request with HMAC SHA256
-        // 2. Send POST to /api/v3/order
-        // 3. Return order ID
-
-        tracing::info!("Placing order on Binance");
-        Ok("ORDER_ID_123".to_string())
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let params = [
+            (
+                "symbol",
+                resolve_symbol(&self.markets, &Exchange::Binance, symbol)?,
+            ),
+            ("orderId", exchange_order_id.to_string()),
+        ];
+
+        let response: BinanceOrderResponse = self
+            .signed_request(Method::DELETE, ORDER_PATH, &params)
+            .await
+            .map_err(|e| match e {
+                ExecutionError::OrderFailed { reason, .. } => ExecutionError::CancelFailed {
+                    order_id: exchange_order_id.to_string(),
+                    reason,
+                },
+                other => other,
+            })?;
+        into_ack(response)
+    }
+
+    /// Fetch the current state of an order (`GET /api/v3/order`)
+    async fn query_order(
+        &self,
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let params = [
+            (
+                "symbol",
+                resolve_symbol(&self.markets, &Exchange::Binance, symbol)?,
+            ),
+            ("orderId", exchange_order_id.to_string()),
+        ];
+
+        let response: BinanceOrderResponse = self
+            .signed_request(Method::GET, ORDER_PATH, &params)
+            .await?;
+        into_ack(response)
+    }
+}
+
+/// Convert an order response into a venue-independent ack
+fn into_ack(response: BinanceOrderResponse) -> ExecutionResult<OrderAck> {
+    let filled_quantity: Quantity = parse_number("executedQty", &response.executed_qty)?;
+    let quote_quantity: Notional =
+        parse_number("cummulativeQuoteQty", &response.cummulative_quote_qty)?;
+    let timestamp = response
+        .transact_time
+        .or(response.update_time)
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+
+    Ok(OrderAck {
+        exchange: Exchange::Binance,
+        symbol: Symbol::from_exchange(&Exchange::Binance, &response.symbol)
+            .map(|symbol| symbol.to_string())
+            .unwrap_or(response.symbol),
+        exchange_order_id: response.order_id.to_string(),
+        client_order_id: Some(
+            response
+                .orig_client_order_id
+                .unwrap_or(response.client_order_id),
+        ),
+        status: parse_status(&response.status)?,
+        filled_quantity,
+        average_price: quote_quantity.checked_div(filled_quantity),
+        timestamp,
+    })
+}
+
+/// Compute the Binance request signature: `hex(HMAC-SHA256(secret, query))`
+fn sign(secret: &str, query: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(query.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Decode a successful response body, or map the Binance error it carries
+fn parse_response<T: DeserializeOwned>(status: StatusCode, body: &str) -> ExecutionResult<T> {
+    if status.is_success() {
+        return serde_json::from_str(body)
+            .map_err(|e| ExecutionError::HttpError(format!("invalid Binance response: {}", e)));
+    }
+    Err(map_error(
+        status,
+        serde_json::from_str::<BinanceApiError>(body).ok(),
+        body,
+    ))
+}
+
+/// Map an HTTP status and Binance error code to an ExecutionError
+fn map_error(status: StatusCode, error: Option<BinanceApiError>, body: &str) -> ExecutionError {
+    let exchange = "Binance".to_string();
+
+    // 429 = request weight exceeded, 418 = IP banned after repeated 429s
+    if status == StatusCode::TOO_MANY_REQUESTS || status.as_u16() == 418 {
+        return ExecutionError::RateLimitExceeded { exchange };
+    }
+
+    let Some(BinanceApiError { code, msg }) = error else {
+        return ExecutionError::HttpError(format!("HTTP {}: {}", status, body));
+    };
+
+    match code {
+        // Too many requests / too many new orders
+        -1003 | -1015 => ExecutionError::RateLimitExceeded { exchange },
+        // Unauthorized, invalid signature, invalid API key/IP/permissions
+        -1002 | -1022 | -2014 | -2015 => ExecutionError::AuthenticationFailed { exchange },
+        // Filter failures and malformed request parameters
+        -1013 | -1199..=-1100 => ExecutionError::InvalidOrder(format!("{} ({})", msg, code)),
+        _ => ExecutionError::OrderFailed {
+            exchange,
+            reason: format!("code {}: {}", code, msg),
+        },
+    }
+}
+
+/// Map a Binance order status to the internal lifecycle
+fn parse_status(status: &str) -> ExecutionResult<OrderStatus> {
+    match status {
+        "NEW" | "PENDING_NEW" | "PARTIALLY_FILLED" => Ok(OrderStatus::Approved),
+        "FILLED" => Ok(OrderStatus::Executed),
+        "CANCELED" | "PENDING_CANCEL" => Ok(OrderStatus::Cancelled),
+        "EXPIRED" | "EXPIRED_IN_MATCH" => Ok(OrderStatus::Expired),
+        "REJECTED" => Ok(OrderStatus::Rejected),
+        other => Err(ExecutionError::HttpError(format!(
+            "unknown Binance order status: {}",
+            other
+        ))),
+    }
+}
+
+/// Parse a Binance decimal string field into a fixed-point amount
+fn parse_number<T: FromStr>(field: &str, value: &str) -> ExecutionResult<T> {
+    value
+        .parse::<T>()
+        .map_err(|_| ExecutionError::HttpError(format!("invalid Binance {}: '{}'", field, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec<T: FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    fn executor() -> BinanceExecutor {
+        BinanceExecutor::new(
+            String::new(),
+            String::new(),
+            vec![Symbol::new("BTC", "USDT")],
+        )
+    }
+
+    #[test]
+    fn test_sign_matches_binance_reference() {
+        // Example from Binance's SIGNED endpoint documentation
+        let secret = "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j";
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+        assert_eq!(
+            sign(secret, query),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    #[test]
+    fn test_order_params_per_order_type() {
+        let executor = executor();
+
+        let limit = OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("0.5"), dec("100.1"))
+            .with_time_in_force(TimeInForce::Ioc)
+            .with_client_order_id("abc");
+        let params = executor.order_params(&limit).unwrap();
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            "symbol=BTCUSDT&side=BUY&type=LIMIT&quantity=0.5&timeInForce=IOC&price=100.1&newClientOrderId=abc&newOrderRespType=RESULT"
+        );
+
+        let maker =
+            OrderRequest::limit("BTC/USDT", OrderSide::Sell, dec("1"), dec("200")).post_only();
+        let params = executor.order_params(&maker).unwrap();
+        assert!(params.contains(&("type", "LIMIT_MAKER".to_string())));
+        assert!(!params.iter().any(|(key, _)| *key == "timeInForce"));
+
+        // Amounts are sent as exact decimals, never as binary float noise
+        let summed = Quantity::try_from(0.1).unwrap() + Quantity::try_from(0.2).unwrap();
+        let params = executor
+            .order_params(&OrderRequest::market("BTC/USDT", OrderSide::Buy, summed))
+            .unwrap();
+        assert!(params.contains(&("quantity", "0.3".to_string())));
+
+        assert!(matches!(
+            executor.order_params(&maker.clone().reduce_only()),
+            Err(ExecutionError::InvalidOrder(_))
+        ));
+        // ETH/USDT is not in the configured markets
+        assert!(matches!(
+            executor.order_params(&OrderRequest::market("ETH/USDT", OrderSide::Buy, dec("1"))),
+            Err(ExecutionError::InvalidOrder(_))
+        ));
+    }
+
+    #[test]
+    fn test_order_response_to_ack() {
+        let response: BinanceOrderResponse = serde_json::from_str(
+            r#"{"symbol":"BTCUSDT","orderId":28,"clientOrderId":"cancel-1","origClientOrderId":"abc",
+                "transactTime":1507725176595,"price":"0.0","origQty":"10.0","executedQty":"4.0",
+                "cummulativeQuoteQty":"400.0","status":"CANCELED","side":"SELL","type":"LIMIT"}"#,
+        )
+        .unwrap();
+
+        let ack = into_ack(response).unwrap();
+        assert_eq!(ack.symbol, "BTC/USDT");
+        assert_eq!(ack.exchange_order_id, "28");
+        assert_eq!(ack.client_order_id.as_deref(), Some("abc"));
+        assert_eq!(ack.status, OrderStatus::Cancelled);
+        assert_eq!(ack.filled_quantity, dec("4"));
+        assert_eq!(ack.average_price, Some(dec("100")));
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        let api_error = |code: i64| {
+            Some(BinanceApiError {
+                code,
+                msg: "error".to_string(),
+            })
+        };
+
+        assert!(matches!(
+            map_error(StatusCode::TOO_MANY_REQUESTS, api_error(-1003), ""),
+            ExecutionError::RateLimitExceeded { .. }
+        ));
+        assert!(matches!(
+            map_error(StatusCode::UNAUTHORIZED, api_error(-2015), ""),
+            ExecutionError::AuthenticationFailed { .. }
+        ));
+        assert!(matches!(
+            map_error(StatusCode::BAD_REQUEST, api_error(-1013), ""),
+            ExecutionError::InvalidOrder(_)
+        ));
+        assert!(matches!(
+            map_error(StatusCode::BAD_REQUEST, api_error(-1102), ""),
+            ExecutionError::InvalidOrder(_)
+        ));
+        assert!(matches!(
+            map_error(StatusCode::BAD_REQUEST, api_error(-2010), ""),
+            ExecutionError::OrderFailed { .. }
+        ));
+        assert!(matches!(
+            map_error(StatusCode::BAD_GATEWAY, None, "<html>"),
+            ExecutionError::HttpError(_)
+        ));
     }
 }
@@ -0,0 +1,209 @@
+// Exchange-agnostic order execution interface
+
+use super::error::{ExecutionError, ExecutionResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use kairos_domain::{Exchange, OrderSide, OrderStatus, OrderType, Price, Quantity, TimeInForce};
+use uuid::Uuid;
+
+/// Sends orders to a single venue (live exchange or paper simulation)
+///
+/// # Example
+/// ```rust,ignore
+/// let executor: Arc<dyn OrderExecutor> = Arc::new(binance_executor);
+/// let order = OrderRequest::limit("BTC/USDT", OrderSide::Buy, "0.01".parse()?, "65000".parse()?);
+/// let ack = executor.submit_order(&order.post_only()).await?;
+/// executor.cancel_order("BTC/USDT", &ack.exchange_order_id).await?;
+/// ```
+#[async_trait]
+pub trait OrderExecutor: Send + Sync {
+    /// Venue the orders are routed to
+    fn exchange(&self) -> Exchange;
+
+    /// Place an order
+    async fn submit_order(&self, request: &OrderRequest) -> ExecutionResult<OrderAck>;
+
+    /// Cancel an open order by exchange order ID
+    async fn cancel_order(
+        &self,
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck>;
+
+    /// Fetch the current state of an order by exchange order ID
+    async fn query_order(&self, symbol: &str, exchange_order_id: &str)
+        -> ExecutionResult<OrderAck>;
+}
+
+/// Venue-independent order
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    /// Canonical symbol (e.g. "BTC/USDT")
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    /// Only used for limit orders
+    pub time_in_force: TimeInForce,
+    /// Base currency quantity
+    pub quantity: Quantity,
+    /// Required for limit orders, must be `None` for market orders
+    pub price: Option<Price>,
+    /// Our ID for the order, echoed back in acks and account updates
+    pub client_order_id: String,
+    /// Only reduce an existing position (derivatives / margin venues)
+    pub reduce_only: bool,
+    /// Reject the order instead of taking liquidity (limit orders only)
+    pub post_only: bool,
+}
+
+impl OrderRequest {
+    /// Market order with a generated client order ID
+    pub fn market(symbol: impl Into<String>, side: OrderSide, quantity: Quantity) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            order_type: OrderType::Market,
+            time_in_force: TimeInForce::default(),
+            quantity,
+            price: None,
+            client_order_id: Uuid::new_v4().simple().to_string(),
+            reduce_only: false,
+            post_only: false,
+        }
+    }
+
+    /// Good-till-cancelled limit order with a generated client order ID
+    pub fn limit(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        quantity: Quantity,
+        price: Price,
+    ) -> Self {
+        Self {
+            order_type: OrderType::Limit,
+            price: Some(price),
+            ..Self::market(symbol, side, quantity)
+        }
+    }
+
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+
+    pub fn with_client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = client_order_id.into();
+        self
+    }
+
+    pub fn post_only(mut self) -> Self {
+        self.post_only = true;
+        self
+    }
+
+    pub fn reduce_only(mut self) -> Self {
+        self.reduce_only = true;
+        self
+    }
+
+    /// Check the request is internally consistent before it is sent
+    pub fn validate(&self) -> ExecutionResult<()> {
+        if !self.quantity.is_positive() {
+            return Err(ExecutionError::InvalidOrder(format!(
+                "quantity must be positive, got {}",
+                self.quantity
+            )));
+        }
+
+        match (&self.order_type, self.price) {
+            (OrderType::Market, Some(_)) => Err(ExecutionError::InvalidOrder(
+                "market orders do not take a price".to_string(),
+            )),
+            (OrderType::Market, None) if self.post_only => Err(ExecutionError::InvalidOrder(
+                "market orders cannot be post-only".to_string(),
+            )),
+            (OrderType::Limit, None) => Err(ExecutionError::InvalidOrder(
+                "limit orders require a price".to_string(),
+            )),
+            (OrderType::Limit, Some(price)) if !price.is_positive() => Err(
+                ExecutionError::InvalidOrder(format!("price must be positive, got {}", price)),
+            ),
+            (OrderType::Limit, Some(_))
+                if self.post_only && self.time_in_force != TimeInForce::Gtc =>
+            {
+                Err(ExecutionError::InvalidOrder(
+                    "post-only orders must be good-till-cancelled".to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Venue response to a submit, cancel or query
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderAck {
+    pub exchange: Exchange,
+    /// Canonical symbol (e.g. "BTC/USDT")
+    pub symbol: String,
+    pub exchange_order_id: String,
+    pub client_order_id: Option<String>,
+    pub status: OrderStatus,
+    pub filled_quantity: Quantity,
+    pub average_price: Option<Price>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec<T: std::str::FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_validate_order_request() {
+        assert!(OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("1"))
+            .validate()
+            .is_ok());
+        assert!(
+            OrderRequest::limit("BTC/USDT", OrderSide::Sell, dec("1"), dec("100"))
+                .post_only()
+                .validate()
+                .is_ok()
+        );
+
+        let mut priced_market = OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("1"));
+        priced_market.price = Some(dec("100"));
+        assert!(priced_market.validate().is_err());
+
+        assert!(OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("1"))
+            .post_only()
+            .validate()
+            .is_err());
+        assert!(
+            OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("0"), dec("100"))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("1"), dec("100"))
+                .with_time_in_force(TimeInForce::Ioc)
+                .post_only()
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generated_client_order_ids_are_unique() {
+        let a = OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("1"));
+        let b = OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("1"));
+        assert_ne!(a.client_order_id, b.client_order_id);
+        assert_eq!(a.client_order_id.len(), 32);
+    }
+}
@@ -0,0 +1,472 @@
+// Kraken execution client
+
+use super::error::{ExecutionError, ExecutionResult};
+use super::executor::{OrderAck, OrderExecutor, OrderRequest};
+use super::resolve_symbol;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use kairos_domain::{
+    Exchange, OrderSide, OrderStatus, OrderType, Price, Quantity, Symbol, TimeInForce,
+};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEFAULT_REST_URL: &str = "https://api.kraken.com";
+const ADD_ORDER_PATH: &str = "/0/private/AddOrder";
+const CANCEL_ORDER_PATH: &str = "/0/private/CancelOrder";
+const QUERY_ORDERS_PATH: &str = "/0/private/QueryOrders";
+
+/// Envelope of every Kraken REST response
+#[derive(Debug, Deserialize)]
+struct KrakenResponse<T> {
+    #[serde(default)]
+    error: Vec<String>,
+    result: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddOrderResult {
+    txid: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CancelOrderResult {
+    count: u32,
+}
+
+/// Entry of the `QueryOrders` result, keyed by transaction ID
+#[derive(Debug, Deserialize)]
+struct KrakenOrderInfo {
+    status: String,
+    #[serde(default)]
+    cl_ord_id: Option<String>,
+    vol_exec: String,
+    /// Average fill price
+    price: String,
+    #[serde(default)]
+    opentm: f64,
+    #[serde(default)]
+    closetm: Option<f64>,
+}
+
+pub struct KrakenExecutor {
+    api_key: String,
+    api_secret: String,
+    markets: Vec<Symbol>,
+    rest_url: String,
+    http: reqwest::Client,
+    /// Last nonce sent; Kraken rejects nonces that do not increase
+    last_nonce: AtomicU64,
+}
+
+impl KrakenExecutor {
+    pub fn new(api_key: String, api_secret: String, markets: Vec<Symbol>) -> Self {
+        Self {
+            api_key,
+            api_secret,
+            markets,
+            rest_url: DEFAULT_REST_URL.to_string(),
+            http: reqwest::Client::new(),
+            last_nonce: AtomicU64::new(0),
+        }
+    }
+
+    /// Override the REST base URL (e.g. `settings.exchange.kraken_rest_url`)
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Build the `AddOrder` form parameters for an order
+    fn order_params(&self, request: &OrderRequest) -> ExecutionResult<Vec<(&'static str, String)>> {
+        request.validate()?;
+        let symbol = resolve_symbol(&self.markets, &Exchange::Kraken, &request.symbol)?;
+
+        let mut params = vec![
+            (
+                "ordertype",
+                match request.order_type {
+                    OrderType::Market => "market",
+                    OrderType::Limit => "limit",
+                }
+                .to_string(),
+            ),
+            (
+                "type",
+                match request.side {
+                    OrderSide::Buy => "buy",
+                    OrderSide::Sell => "sell",
+                }
+                .to_string(),
+            ),
+            ("volume", request.quantity.to_string()),
+            ("pair", rest_pair(&symbol)),
+            ("cl_ord_id", request.client_order_id.clone()),
+        ];
+
+        if let Some(price) = request.price {
+            params.push(("price", price.to_string()));
+            match request.time_in_force {
+                TimeInForce::Gtc => {}
+                TimeInForce::Ioc => params.push(("timeinforce", "IOC".to_string())),
+                TimeInForce::Fok => {
+                    return Err(ExecutionError::InvalidOrder(
+                        "Kraken does not support fill-or-kill orders".to_string(),
+                    ))
+                }
+            }
+        }
+        if request.post_only {
+            params.push(("oflags", "post".to_string()));
+        }
+        if request.reduce_only {
+            params.push(("reduce_only", "true".to_string()));
+        }
+
+        Ok(params)
+    }
+
+    /// Send a signed POST to a private endpoint and unwrap the `result`
+    async fn private_request<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> ExecutionResult<T> {
+        let nonce = self.next_nonce();
+        let mut form = vec![("nonce", nonce.to_string())];
+        form.extend(params.iter().cloned());
+        let body = serde_urlencoded::to_string(&form)
+            .map_err(|e| ExecutionError::InvalidOrder(e.to_string()))?;
+
+        let response: KrakenResponse<T> = self
+            .http
+            .post(format!("{}{}", self.rest_url, path))
+            .header("API-Key", &self.api_key)
+            .header("API-Sign", sign(&self.api_secret, path, nonce, &body)?)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+
+        if !response.error.is_empty() {
+            return Err(map_errors(response.error));
+        }
+        response
+            .result
+            .ok_or_else(|| ExecutionError::HttpError("Kraken response has no result".to_string()))
+    }
+
+    /// Millisecond timestamp, bumped so concurrent requests never reuse a nonce
+    fn next_nonce(&self) -> u64 {
+        let now = Utc::now().timestamp_millis() as u64;
+        let mut last = self.last_nonce.load(Ordering::Relaxed);
+        loop {
+            let next = now.max(last + 1);
+            match self.last_nonce.compare_exchange_weak(
+                last,
+                next,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return next,
+                Err(current) => last = current,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OrderExecutor for KrakenExecutor {
+    fn exchange(&self) -> Exchange {
+        Exchange::Kraken
+    }
+
+    async fn submit_order(&self, request: &OrderRequest) -> ExecutionResult<OrderAck> {
+        let params = self.order_params(request)?;
+        tracing::info!(
+            "Placing order on Kraken: {:?} {:?} {} {} @ {:?}",
+            request.order_type,
+            request.side,
+            request.quantity,
+            request.symbol,
+            request.price
+        );
+
+        let result: AddOrderResult = self.private_request(ADD_ORDER_PATH, &params).await?;
+        let txid = result
+            .txid
+            .into_iter()
+            .next()
+            .ok_or_else(|| ExecutionError::OrderFailed {
+                exchange: "Kraken".to_string(),
+                reason: "response contained no txid".to_string(),
+            })?;
+
+        Ok(OrderAck {
+            exchange: Exchange::Kraken,
+            symbol: request.symbol.clone(),
+            exchange_order_id: txid,
+            client_order_id: Some(request.client_order_id.clone()),
+            status: OrderStatus::Approved,
+            filled_quantity: Quantity::ZERO,
+            average_price: None,
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Cancel an open order by transaction ID
+    async fn cancel_order(
+        &self,
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let result: CancelOrderResult = self
+            .private_request(
+                CANCEL_ORDER_PATH,
+                &[("txid", exchange_order_id.to_string())],
+            )
+            .await
+            .map_err(|e| match e {
+                ExecutionError::OrderFailed { reason, .. } => ExecutionError::CancelFailed {
+                    order_id: exchange_order_id.to_string(),
+                    reason,
+                },
+                other => other,
+            })?;
+
+        if result.count == 0 {
+            return Err(ExecutionError::CancelFailed {
+                order_id: exchange_order_id.to_string(),
+                reason: "no order cancelled".to_string(),
+            });
+        }
+
+        Ok(OrderAck {
+            exchange: Exchange::Kraken,
+            symbol: symbol.to_string(),
+            exchange_order_id: exchange_order_id.to_string(),
+            client_order_id: None,
+            status: OrderStatus::Cancelled,
+            filled_quantity: Quantity::ZERO,
+            average_price: None,
+            timestamp: Utc::now(),
+        })
+    }
+
+    async fn query_order(
+        &self,
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let mut result: HashMap<String, KrakenOrderInfo> = self
+            .private_request(
+                QUERY_ORDERS_PATH,
+                &[("txid", exchange_order_id.to_string())],
+            )
+            .await?;
+
+        let info = result.remove(exchange_order_id).ok_or_else(|| {
+            ExecutionError::HttpError(format!("Kraken order {} not found", exchange_order_id))
+        })?;
+        into_ack(symbol, exchange_order_id, info)
+    }
+}
+
+/// Ack describing a queried order
+fn into_ack(symbol: &str, txid: &str, info: KrakenOrderInfo) -> ExecutionResult<OrderAck> {
+    let status = match info.status.as_str() {
+        "pending" | "open" => OrderStatus::Approved,
+        "closed" => OrderStatus::Executed,
+        "canceled" => OrderStatus::Cancelled,
+        "expired" => OrderStatus::Expired,
+        other => {
+            return Err(ExecutionError::HttpError(format!(
+                "unknown Kraken order status: {}",
+                other
+            )))
+        }
+    };
+    let filled_quantity: Quantity = parse_number("vol_exec", &info.vol_exec)?;
+    let average_price: Price = parse_number("price", &info.price)?;
+    let seconds = info.closetm.unwrap_or(info.opentm);
+
+    Ok(OrderAck {
+        exchange: Exchange::Kraken,
+        symbol: symbol.to_string(),
+        exchange_order_id: txid.to_string(),
+        client_order_id: info.cl_ord_id,
+        status,
+        filled_quantity,
+        average_price: filled_quantity.is_positive().then_some(average_price),
+        timestamp: DateTime::from_timestamp_millis((seconds * 1000.0) as i64)
+            .unwrap_or_else(Utc::now),
+    })
+}
+
+/// Parse a Kraken decimal string field into a fixed-point amount
+fn parse_number<T: FromStr>(field: &str, value: &str) -> ExecutionResult<T> {
+    value
+        .parse::<T>()
+        .map_err(|_| ExecutionError::HttpError(format!("invalid Kraken {}: '{}'", field, value)))
+}
+
+/// Compute the Kraken `API-Sign` header:
+/// `base64(HMAC-SHA512(base64_decode(secret), path + SHA256(nonce + body)))`
+fn sign(secret: &str, path: &str, nonce: u64, body: &str) -> ExecutionResult<String> {
+    let key = BASE64
+        .decode(secret)
+        .map_err(|_| ExecutionError::AuthenticationFailed {
+            exchange: "Kraken".to_string(),
+        })?;
+
+    let digest = Sha256::new()
+        .chain_update(nonce.to_string())
+        .chain_update(body)
+        .finalize();
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(&key).expect("HMAC accepts keys of any length");
+    mac.update(path.as_bytes());
+    mac.update(&digest);
+    Ok(BASE64.encode(mac.finalize().into_bytes()))
+}
+
+/// REST pair name for a WebSocket v2 symbol ("BTC/USDT" -> "XBTUSDT")
+fn rest_pair(symbol: &str) -> String {
+    symbol
+        .split('/')
+        .map(|asset| if asset == "BTC" { "XBT" } else { asset })
+        .collect()
+}
+
+/// Map Kraken `error` entries (e.g. "EAPI:Invalid key") to an ExecutionError
+fn map_errors(errors: Vec<String>) -> ExecutionError {
+    let exchange = "Kraken".to_string();
+    if errors
+        .iter()
+        .any(|e| e.contains("Rate limit exceeded") || e.contains("Throttled"))
+    {
+        return ExecutionError::RateLimitExceeded { exchange };
+    }
+    if errors.iter().any(|e| {
+        matches!(
+            e.as_str(),
+            "EAPI:Invalid key" | "EAPI:Invalid signature" | "EGeneral:Permission denied"
+        )
+    }) {
+        return ExecutionError::AuthenticationFailed { exchange };
+    }
+    ExecutionError::OrderFailed {
+        exchange,
+        reason: errors.join(", "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec<T: FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_sign_matches_kraken_reference() {
+        // Example from Kraken's REST authentication documentation
+        let secret = "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==";
+        let body =
+            "nonce=1616492376594&ordertype=limit&pair=XBTUSD&price=37500&type=buy&volume=1.25";
+        assert_eq!(
+            sign(secret, ADD_ORDER_PATH, 1616492376594, body).unwrap(),
+            "4/dpxb3iT4tp/ZCVEwSnEsLxx0bqyhLpdfOpc6fn7OR8+UClSV5n9E6aSS8MPtnRfp32bAb0nmbRn6H8ndwLUQ=="
+        );
+    }
+
+    #[test]
+    fn test_nonce_strictly_increases() {
+        let executor = KrakenExecutor::new(String::new(), String::new(), Vec::new());
+        let first = executor.next_nonce();
+        let second = executor.next_nonce();
+        assert!(second > first);
+    }
+
+    #[test]
+    fn test_order_params() {
+        let executor = KrakenExecutor::new(
+            String::new(),
+            String::new(),
+            vec![Symbol::new("BTC", "USDT")],
+        );
+
+        let params = executor
+            .order_params(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("1.25"), dec("37500"))
+                    .with_time_in_force(TimeInForce::Ioc)
+                    .with_client_order_id("abc"),
+            )
+            .unwrap();
+        assert_eq!(
+            serde_urlencoded::to_string(&params).unwrap(),
+            "ordertype=limit&type=buy&volume=1.25&pair=XBTUSDT&cl_ord_id=abc&price=37500&timeinforce=IOC"
+        );
+
+        let params = executor
+            .order_params(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Sell, dec("1"), dec("100")).post_only(),
+            )
+            .unwrap();
+        assert!(params.contains(&("oflags", "post".to_string())));
+
+        assert!(executor
+            .order_params(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("1"), dec("100"))
+                    .with_time_in_force(TimeInForce::Fok)
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_query_result_to_ack() {
+        let info: KrakenOrderInfo = serde_json::from_str(
+            r#"{"status":"closed","cl_ord_id":"abc","vol":"1.0","vol_exec":"1.0","price":"37500.5","opentm":1616492376.5,"closetm":1616492377.25}"#,
+        )
+        .unwrap();
+        let ack = into_ack("BTC/USDT", "OQCLML-BW3P3-BUCMWZ", info).unwrap();
+        assert_eq!(ack.status, OrderStatus::Executed);
+        assert_eq!(ack.client_order_id.as_deref(), Some("abc"));
+        assert_eq!(ack.filled_quantity, dec("1"));
+        assert_eq!(ack.average_price, Some(dec("37500.5")));
+        assert_eq!(ack.timestamp.timestamp_millis(), 1616492377250);
+    }
+
+    #[test]
+    fn test_rest_pair_and_error_mapping() {
+        assert_eq!(rest_pair("BTC/USDT"), "XBTUSDT");
+        assert_eq!(rest_pair("ETH/BTC"), "ETHXBT");
+
+        assert!(matches!(
+            map_errors(vec!["EAPI:Invalid key".to_string()]),
+            ExecutionError::AuthenticationFailed { .. }
+        ));
+        assert!(matches!(
+            map_errors(vec!["EAPI:Rate limit exceeded".to_string()]),
+            ExecutionError::RateLimitExceeded { .. }
+        ));
+        assert!(matches!(
+            map_errors(vec!["EOrder:Insufficient funds".to_string()]),
+            ExecutionError::OrderFailed { .. }
+        ));
+    }
+}
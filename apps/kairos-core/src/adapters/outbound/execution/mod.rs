@@ -2,7 +2,35 @@
 
 pub mod binance;
 pub mod error;
+pub mod executor;
+pub mod kraken;
 pub mod okx;
+pub mod paper;
 
 // Re-export error types
 pub use error::{ExecutionError, ExecutionResult};
+pub use executor::{OrderAck, OrderExecutor, OrderRequest};
+pub use paper::{PaperConfig, PaperExecutor};
+
+use kairos_domain::{Exchange, Symbol};
+
+/// Map a canonical symbol (e.g. "BTC/USDT") to the exchange-native name,
+/// rejecting instruments outside the configured `[markets]` universe
+pub(crate) fn resolve_symbol(
+    markets: &[Symbol],
+    exchange: &Exchange,
+    symbol: &str,
+) -> ExecutionResult<String> {
+    let symbol: Symbol = symbol
+        .parse()
+        .map_err(|_| ExecutionError::InvalidOrder(format!("invalid symbol '{}'", symbol)))?;
+
+    if !markets.contains(&symbol) {
+        return Err(ExecutionError::InvalidOrder(format!(
+            "{} is not configured for {:?}",
+            symbol, exchange
+        )));
+    }
+
+    Ok(symbol.to_exchange(exchange))
+}
@@ -1,34 +1,638 @@
 // OKX execution client
 
-use super::error::ExecutionResult;
+use super::error::{ExecutionError, ExecutionResult};
+use super::executor::{OrderAck, OrderExecutor, OrderRequest};
+use super::resolve_symbol;
+use crate::adapters::inbound::feed_handler::okx_private::sign;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use kairos_domain::{
+    Exchange, OrderSide, OrderStatus, OrderType, Price, Quantity, Symbol, TimeInForce,
+};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const DEFAULT_REST_URL: &str = "https://www.okx.com";
+const ORDER_PATH: &str = "/api/v5/trade/order";
+const BATCH_ORDERS_PATH: &str = "/api/v5/trade/batch-orders";
+const CANCEL_ORDER_PATH: &str = "/api/v5/trade/cancel-order";
+const AMEND_ORDER_PATH: &str = "/api/v5/trade/amend-order";
+
+/// Maximum orders accepted by `batch-orders`
+const MAX_BATCH_ORDERS: usize = 20;
+
+/// Per-order result of the place, cancel and amend endpoints
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxAckData {
+    ord_id: String,
+    #[serde(default)]
+    cl_ord_id: String,
+    #[serde(default)]
+    ts: String,
+}
+
+/// `trade/order` request body
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct OkxOrderBody {
+    inst_id: String,
+    td_mode: &'static str,
+    side: &'static str,
+    ord_type: &'static str,
+    sz: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    px: Option<String>,
+    /// Market order size unit; `base_ccy` so `sz` is always a base quantity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tgt_ccy: Option<&'static str>,
+    cl_ord_id: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    reduce_only: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxCancelBody {
+    inst_id: String,
+    ord_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxAmendBody {
+    inst_id: String,
+    ord_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_sz: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_px: Option<String>,
+}
+
+/// Envelope of every OKX REST response
+///
+/// `code` is "0" on success, "1" when the operation failed and "2" when a
+/// batch partially succeeded; per-order results carry `sCode`/`sMsg`.
+#[derive(Debug, Deserialize)]
+struct OkxResponse {
+    code: String,
+    #[serde(default)]
+    msg: String,
+    #[serde(default)]
+    data: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OkxOrderDetails {
+    ord_id: String,
+    #[serde(default)]
+    cl_ord_id: String,
+    state: String,
+    #[serde(default)]
+    acc_fill_sz: String,
+    #[serde(default)]
+    avg_px: String,
+    #[serde(default)]
+    u_time: String,
+}
 
 pub struct OkxExecutor {
     api_key: String,
     api_secret: String,
     passphrase: String,
+    markets: Vec<Symbol>,
+    rest_url: String,
+    simulated_trading: bool,
+    http: reqwest::Client,
 }
 
 impl OkxExecutor {
-    pub fn new(api_key: String, api_secret: String, passphrase: String) -> Self {
+    pub fn new(
+        api_key: String,
+        api_secret: String,
+        passphrase: String,
+        markets: Vec<Symbol>,
+    ) -> Self {
         Self {
             api_key,
             api_secret,
             passphrase,
+            markets,
+            rest_url: DEFAULT_REST_URL.to_string(),
+            simulated_trading: false,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Override the REST base URL (e.g. `settings.exchange.okx_rest_url` or a local mock)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let executor = OkxExecutor::new(api_key, api_secret, passphrase, markets)
+    ///     .with_rest_url(&settings.exchange.okx_rest_url)
+    ///     .with_simulated_trading(settings.exchange.okx_simulated_trading);
+    /// ```
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self
+    }
+
+    /// Route orders to OKX demo trading (`x-simulated-trading: 1`)
+    pub fn with_simulated_trading(mut self, simulated_trading: bool) -> Self {
+        self.simulated_trading = simulated_trading;
+        self
+    }
+
+    /// Submit up to 20 orders in one request (`POST /api/v5/trade/batch-orders`)
+    ///
+    /// Returns one result per order, in request order; a partially failed
+    /// batch yields errors only for the rejected orders.
+    pub async fn submit_batch(
+        &self,
+        requests: &[OrderRequest],
+    ) -> ExecutionResult<Vec<ExecutionResult<OrderAck>>> {
+        if requests.is_empty() || requests.len() > MAX_BATCH_ORDERS {
+            return Err(ExecutionError::InvalidOrder(format!(
+                "batch must contain 1 to {} orders, got {}",
+                MAX_BATCH_ORDERS,
+                requests.len()
+            )));
+        }
+
+        let bodies = requests
+            .iter()
+            .map(|request| self.order_body(request))
+            .collect::<ExecutionResult<Vec<_>>>()?;
+        tracing::info!("Placing batch of {} orders on OKX", bodies.len());
+
+        let response = self
+            .request(
+                Method::POST,
+                BATCH_ORDERS_PATH,
+                serde_json::to_string(&bodies)?,
+            )
+            .await?;
+        Ok(response
+            .data
+            .into_iter()
+            .zip(requests)
+            .map(|(item, request)| {
+                parse_item(item).map(|data| into_ack(&request.symbol, data, OrderStatus::Approved))
+            })
+            .collect())
+    }
+
+    /// Change the size and/or price of an open order (`POST /api/v5/trade/amend-order`)
+    pub async fn amend_order(
+        &self,
+        symbol: &str,
+        exchange_order_id: &str,
+        new_quantity: Option<Quantity>,
+        new_price: Option<Price>,
+    ) -> ExecutionResult<OrderAck> {
+        if new_quantity.is_none() && new_price.is_none() {
+            return Err(ExecutionError::InvalidOrder(
+                "amend requires a new quantity or price".to_string(),
+            ));
+        }
+
+        let body = OkxAmendBody {
+            inst_id: resolve_symbol(&self.markets, &Exchange::OKX, symbol)?,
+            ord_id: exchange_order_id.to_string(),
+            new_sz: new_quantity.map(|sz| sz.to_string()),
+            new_px: new_price.map(|px| px.to_string()),
+        };
+
+        let response = self
+            .request(
+                Method::POST,
+                AMEND_ORDER_PATH,
+                serde_json::to_string(&body)?,
+            )
+            .await?;
+        Ok(into_ack(
+            symbol,
+            first_item(response)?,
+            OrderStatus::Approved,
+        ))
+    }
+
+    /// Build the `trade/order` body for an order
+    fn order_body(&self, request: &OrderRequest) -> ExecutionResult<OkxOrderBody> {
+        request.validate()?;
+
+        let ord_type = match (
+            &request.order_type,
+            request.post_only,
+            request.time_in_force,
+        ) {
+            (OrderType::Market, _, _) => "market",
+            (OrderType::Limit, true, _) => "post_only",
+            (OrderType::Limit, false, TimeInForce::Gtc) => "limit",
+            (OrderType::Limit, false, TimeInForce::Ioc) => "ioc",
+            (OrderType::Limit, false, TimeInForce::Fok) => "fok",
+        };
+
+        Ok(OkxOrderBody {
+            inst_id: resolve_symbol(&self.markets, &Exchange::OKX, &request.symbol)?,
+            td_mode: "cash",
+            side: match request.side {
+                OrderSide::Buy => "buy",
+                OrderSide::Sell => "sell",
+            },
+            ord_type,
+            sz: request.quantity.to_string(),
+            px: request.price.map(|price| price.to_string()),
+            tgt_ccy: (request.order_type == OrderType::Market).then_some("base_ccy"),
+            cl_ord_id: request.client_order_id.clone(),
+            reduce_only: request.reduce_only,
+        })
+    }
+
+    /// Authentication headers for a request (`path` includes any query string)
+    fn headers(
+        &self,
+        timestamp: &str,
+        method: &Method,
+        path: &str,
+        body: &str,
+    ) -> Vec<(&'static str, String)> {
+        let mut headers = vec![
+            ("OK-ACCESS-KEY", self.api_key.clone()),
+            (
+                "OK-ACCESS-SIGN",
+                sign(&self.api_secret, timestamp, method.as_str(), path, body),
+            ),
+            ("OK-ACCESS-TIMESTAMP", timestamp.to_string()),
+            ("OK-ACCESS-PASSPHRASE", self.passphrase.clone()),
+        ];
+        if self.simulated_trading {
+            headers.push(("x-simulated-trading", "1".to_string()));
+        }
+        headers
+    }
+
+    /// Send a signed request and check the response envelope
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        body: String,
+    ) -> ExecutionResult<OkxResponse> {
+        let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+
+        let mut request = self
+            .http
+            .request(method.clone(), format!("{}{}", self.rest_url, path))
+            .header("Content-Type", "application/json");
+        for (name, value) in self.headers(&timestamp, &method, path, &body) {
+            request = request.header(name, value);
         }
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| ExecutionError::HttpError(e.to_string()))?;
+
+        parse_response(status, &text)
+    }
+}
+
+#[async_trait]
+impl OrderExecutor for OkxExecutor {
+    fn exchange(&self) -> Exchange {
+        Exchange::OKX
+    }
+
+    /// Submit a single order (`POST /api/v5/trade/order`)
+    async fn submit_order(&self, request: &OrderRequest) -> ExecutionResult<OrderAck> {
+        let body = self.order_body(request)?;
+        tracing::info!(
+            "Placing order on OKX: {} {} {} {} @ {:?}",
+            body.ord_type,
+            body.side,
+            body.sz,
+            body.inst_id,
+            body.px
+        );
+
+        let response = self
+            .request(Method::POST, ORDER_PATH, serde_json::to_string(&body)?)
+            .await?;
+        Ok(into_ack(
+            &request.symbol,
+            first_item(response)?,
+            OrderStatus::Approved,
+        ))
     }
 
-    pub async fn place_order(
+    /// Cancel an open order (`POST /api/v5/trade/cancel-order`)
+    async fn cancel_order(
         &self,
-        _symbol: &str,
-        _side: &str,
-        _quantity: f64,
-    ) -> ExecutionResult<String> {
-        // TODO: Implement HTTP REST API call to OKX
-        // 1. Sign request with HMAC SHA256
-        // 2. Send POST to /api/v5/trade/order
-        // 3. Return order ID
-
-        tracing::info!("Placing order on OKX");
-        Ok("ORDER_ID_456".to_string())
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let body = OkxCancelBody {
+            inst_id: resolve_symbol(&self.markets, &Exchange::OKX, symbol)?,
+            ord_id: exchange_order_id.to_string(),
+        };
+
+        self.request(
+            Method::POST,
+            CANCEL_ORDER_PATH,
+            serde_json::to_string(&body)?,
+        )
+        .await
+        .and_then(first_item)
+        .map(|data| into_ack(symbol, data, OrderStatus::Cancelled))
+        .map_err(|e| match e {
+            ExecutionError::OrderFailed { reason, .. } => ExecutionError::CancelFailed {
+                order_id: exchange_order_id.to_string(),
+                reason,
+            },
+            other => other,
+        })
+    }
+
+    /// Fetch the current state of an order (`GET /api/v5/trade/order`)
+    async fn query_order(
+        &self,
+        symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let query = serde_urlencoded::to_string([
+            (
+                "instId",
+                resolve_symbol(&self.markets, &Exchange::OKX, symbol)?,
+            ),
+            ("ordId", exchange_order_id.to_string()),
+        ])
+        .map_err(|e| ExecutionError::InvalidOrder(e.to_string()))?;
+
+        let response = self
+            .request(
+                Method::GET,
+                &format!("{}?{}", ORDER_PATH, query),
+                String::new(),
+            )
+            .await?;
+        details_into_ack(symbol, first_item(response)?)
+    }
+}
+
+/// Ack for an accepted place/cancel/amend request
+fn into_ack(symbol: &str, data: OkxAckData, status: OrderStatus) -> OrderAck {
+    OrderAck {
+        exchange: Exchange::OKX,
+        symbol: symbol.to_string(),
+        exchange_order_id: data.ord_id,
+        client_order_id: Some(data.cl_ord_id).filter(|id| !id.is_empty()),
+        status,
+        filled_quantity: Quantity::ZERO,
+        average_price: None,
+        timestamp: parse_millis(&data.ts),
+    }
+}
+
+/// Ack describing a queried order
+fn details_into_ack(symbol: &str, details: OkxOrderDetails) -> ExecutionResult<OrderAck> {
+    let status = match details.state.as_str() {
+        "live" | "partially_filled" => OrderStatus::Approved,
+        "filled" => OrderStatus::Executed,
+        "canceled" | "mmp_canceled" => OrderStatus::Cancelled,
+        other => {
+            return Err(ExecutionError::HttpError(format!(
+                "unknown OKX order state: {}",
+                other
+            )))
+        }
+    };
+
+    Ok(OrderAck {
+        exchange: Exchange::OKX,
+        symbol: symbol.to_string(),
+        exchange_order_id: details.ord_id,
+        client_order_id: Some(details.cl_ord_id).filter(|id| !id.is_empty()),
+        status,
+        filled_quantity: parse_optional("accFillSz", &details.acc_fill_sz)?.unwrap_or_default(),
+        average_price: parse_optional("avgPx", &details.avg_px)?,
+        timestamp: parse_millis(&details.u_time),
+    })
+}
+
+/// Parse an OKX millisecond timestamp, falling back to now when absent
+fn parse_millis(ts: &str) -> DateTime<Utc> {
+    ts.parse::<i64>()
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now)
+}
+
+/// Decode the response envelope, mapping HTTP and top-level OKX errors
+///
+/// Codes "1" and "2" are passed through so per-order `sCode`s can be reported.
+fn parse_response(status: StatusCode, text: &str) -> ExecutionResult<OkxResponse> {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(ExecutionError::RateLimitExceeded {
+            exchange: "OKX".to_string(),
+        });
+    }
+
+    let response: OkxResponse = serde_json::from_str(text)
+        .map_err(|_| ExecutionError::HttpError(format!("HTTP {}: {}", status, text)))?;
+    match response.code.as_str() {
+        "0" | "1" | "2" => Ok(response),
+        code => Err(map_error(code, &response.msg)),
+    }
+}
+
+/// Result of a single-order request
+fn first_item<T: DeserializeOwned>(response: OkxResponse) -> ExecutionResult<T> {
+    match response.data.into_iter().next() {
+        Some(item) => parse_item(item),
+        None => Err(map_error(&response.code, &response.msg)),
+    }
+}
+
+/// Check an item's `sCode` and decode it
+fn parse_item<T: DeserializeOwned>(item: serde_json::Value) -> ExecutionResult<T> {
+    let s_code = item
+        .get("sCode")
+        .and_then(|code| code.as_str())
+        .unwrap_or("0");
+    if s_code != "0" {
+        let s_msg = item
+            .get("sMsg")
+            .and_then(|msg| msg.as_str())
+            .unwrap_or_default();
+        return Err(map_error(s_code, s_msg));
+    }
+    serde_json::from_value(item)
+        .map_err(|e| ExecutionError::HttpError(format!("invalid OKX response: {}", e)))
+}
+
+/// Map an OKX error code (`code` or per-order `sCode`) to an ExecutionError
+fn map_error(code: &str, msg: &str) -> ExecutionError {
+    let exchange = "OKX".to_string();
+    match code.parse::<u32>().unwrap_or_default() {
+        // Requests too frequent / order rate limit
+        50011 | 50061 => ExecutionError::RateLimitExceeded { exchange },
+        // API key, passphrase, signature, timestamp and permission errors
+        50100..=50119 => ExecutionError::AuthenticationFailed { exchange },
+        // Parameter error, instrument not found, price/size limits
+        51000 | 51001 | 51006 | 51020 | 51121 => {
+            ExecutionError::InvalidOrder(format!("{} ({})", msg, code))
+        }
+        _ => ExecutionError::OrderFailed {
+            exchange,
+            reason: format!("code {}: {}", code, msg),
+        },
+    }
+}
+
+/// Parse an optional OKX decimal field (OKX sends `""` when not applicable)
+fn parse_optional<T: FromStr>(field: &str, value: &str) -> ExecutionResult<Option<T>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<T>()
+        .map(Some)
+        .map_err(|_| ExecutionError::HttpError(format!("invalid OKX {}: '{}'", field, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec<T: FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    fn executor() -> OkxExecutor {
+        OkxExecutor::new(
+            "key".to_string(),
+            "secret".to_string(),
+            "passphrase".to_string(),
+            vec![Symbol::new("BTC", "USDT")],
+        )
+    }
+
+    #[test]
+    fn test_order_body_per_order_type() {
+        let executor = executor();
+
+        let market = executor
+            .order_body(
+                &OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("0.01"))
+                    .with_client_order_id("m1"),
+            )
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(&market).unwrap(),
+            r#"{"instId":"BTC-USDT","tdMode":"cash","side":"buy","ordType":"market","sz":"0.01","tgtCcy":"base_ccy","clOrdId":"m1"}"#
+        );
+
+        let ioc = executor
+            .order_body(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Sell, dec("1"), dec("100.5"))
+                    .with_time_in_force(TimeInForce::Ioc)
+                    .reduce_only(),
+            )
+            .unwrap();
+        assert_eq!(ioc.ord_type, "ioc");
+        assert_eq!(ioc.px.as_deref(), Some("100.5"));
+        assert_eq!(ioc.tgt_ccy, None);
+        assert!(ioc.reduce_only);
+
+        let maker = executor
+            .order_body(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("1"), dec("100")).post_only(),
+            )
+            .unwrap();
+        assert_eq!(maker.ord_type, "post_only");
+    }
+
+    #[test]
+    fn test_order_details_to_ack() {
+        let details: OkxOrderDetails = serde_json::from_str(
+            r#"{"instId":"BTC-USDT","ordId":"312269865356374016","clOrdId":"b1","px":"2.15","sz":"2","accFillSz":"0.00192834","avgPx":"51858","state":"partially_filled","uTime":"1597026383085"}"#,
+        )
+        .unwrap();
+        let ack = details_into_ack("BTC/USDT", details).unwrap();
+        assert_eq!(ack.status, OrderStatus::Approved);
+        assert_eq!(ack.filled_quantity, dec("0.00192834"));
+        assert_eq!(ack.average_price, Some(dec("51858")));
+
+        let details: OkxOrderDetails = serde_json::from_str(
+            r#"{"ordId":"1","state":"live","accFillSz":"0","avgPx":"","uTime":"1597026383085"}"#,
+        )
+        .unwrap();
+        let ack = details_into_ack("BTC/USDT", details).unwrap();
+        assert!(ack.filled_quantity.is_zero());
+        assert_eq!(ack.average_price, None);
+    }
+
+    #[test]
+    fn test_simulated_trading_header() {
+        let live = executor();
+        let headers = live.headers("2020-12-08T09:08:57.715Z", &Method::GET, ORDER_PATH, "");
+        assert!(!headers
+            .iter()
+            .any(|(name, _)| *name == "x-simulated-trading"));
+        assert!(headers.contains(&("OK-ACCESS-PASSPHRASE", "passphrase".to_string())));
+
+        let demo = executor().with_simulated_trading(true);
+        let headers = demo.headers("2020-12-08T09:08:57.715Z", &Method::GET, ORDER_PATH, "");
+        assert!(headers.contains(&("x-simulated-trading", "1".to_string())));
+    }
+
+    #[test]
+    fn test_batch_partial_failure_and_error_mapping() {
+        let response = parse_response(
+            StatusCode::OK,
+            r#"{"code":"2","msg":"","data":[
+                {"ordId":"1","clOrdId":"a","sCode":"0","sMsg":""},
+                {"ordId":"","clOrdId":"b","sCode":"51008","sMsg":"Insufficient balance"}
+            ]}"#,
+        )
+        .unwrap();
+        let results: Vec<ExecutionResult<OkxAckData>> =
+            response.data.into_iter().map(parse_item).collect();
+        assert_eq!(results[0].as_ref().unwrap().ord_id, "1");
+        assert!(matches!(
+            results[1],
+            Err(ExecutionError::OrderFailed { .. })
+        ));
+
+        assert!(matches!(
+            parse_response(
+                StatusCode::UNAUTHORIZED,
+                r#"{"code":"50113","msg":"Invalid Sign"}"#
+            ),
+            Err(ExecutionError::AuthenticationFailed { .. })
+        ));
+        assert!(matches!(
+            parse_response(StatusCode::TOO_MANY_REQUESTS, ""),
+            Err(ExecutionError::RateLimitExceeded { .. })
+        ));
+        assert!(matches!(
+            map_error("51000", "Parameter sz error"),
+            ExecutionError::InvalidOrder(_)
+        ));
     }
 }
@@ -0,0 +1,730 @@
+// Paper execution client - simulated matching against live market data
+
+use super::error::{ExecutionError, ExecutionResult};
+use super::executor::{OrderAck, OrderExecutor, OrderRequest};
+use super::resolve_symbol;
+use crate::config::Settings;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use kairos_domain::{
+    AccountEvent, Amount, BalanceUpdate, Exchange, Fill, Liquidity, MarketEvent, OrderSide,
+    OrderStatus, OrderType, OrderUpdate, Price, Quantity, Quote, Symbol, TimeInForce,
+};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+/// Fill simulation parameters
+#[derive(Debug, Clone)]
+pub struct PaperConfig {
+    /// Price penalty applied to market orders, in basis points of the BBO
+    pub slippage_bps: f64,
+    pub maker_fee_bps: f64,
+    pub taker_fee_bps: f64,
+    /// Starting balance per currency
+    pub initial_balances: HashMap<String, Amount>,
+}
+
+impl PaperConfig {
+    /// Extract the `[paper]` section from Settings
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let settings = Settings::new()?;
+    /// let config = PaperConfig::from_settings(&settings);
+    /// ```
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            slippage_bps: settings.paper.slippage_bps,
+            maker_fee_bps: settings.paper.maker_fee_bps,
+            taker_fee_bps: settings.paper.taker_fee_bps,
+            // Config keys are case-insensitive, currencies are not
+            initial_balances: settings
+                .paper
+                .initial_balances
+                .iter()
+                .filter_map(|(currency, amount)| match Amount::try_from(*amount) {
+                    Ok(amount) => Some((currency.to_uppercase(), amount)),
+                    Err(e) => {
+                        tracing::warn!("⚠️  Ignoring paper balance for {}: {}", currency, e);
+                        None
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Order held by the simulator
+struct PaperOrder {
+    request: OrderRequest,
+    symbol: Symbol,
+    /// Exchange-native symbol, as carried by market events
+    native_symbol: String,
+    ack: OrderAck,
+    /// Funds locked while the order rests (quote for buys, base for sells)
+    reserved: Amount,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PaperBalance {
+    available: Amount,
+    locked: Amount,
+}
+
+/// Order executor that simulates a venue locally
+///
+/// Market orders fill immediately at the latest BBO plus slippage. Limit
+/// orders fill immediately when marketable, otherwise rest until a trade
+/// prints through their price. Balances, fees and the resulting account
+/// events (order updates, fills, balances) mirror a real venue's private
+/// feed. Market data must be fed in with [`PaperExecutor::run`].
+///
+/// # Example
+/// ```rust,ignore
+/// let paper = Arc::new(PaperExecutor::new(
+///     Exchange::Binance,
+///     settings.markets.symbols(&Exchange::Binance)?,
+///     PaperConfig::from_settings(&settings),
+///     account_event_tx.clone(),
+/// ));
+/// tokio::spawn({
+///     let paper = paper.clone();
+///     let rx = market_data_tx.subscribe();
+///     async move { paper.run(rx, shutdown).await }
+/// });
+/// ```
+pub struct PaperExecutor {
+    exchange: Exchange,
+    markets: Vec<Symbol>,
+    config: PaperConfig,
+    account_event_tx: broadcast::Sender<AccountEvent>,
+    /// Latest BBO per exchange-native symbol
+    quotes: DashMap<String, Quote>,
+    orders: DashMap<String, PaperOrder>,
+    balances: Mutex<HashMap<String, PaperBalance>>,
+    next_order_id: AtomicU64,
+    next_trade_id: AtomicU64,
+}
+
+impl PaperExecutor {
+    /// Create a paper executor impersonating `exchange`
+    ///
+    /// # Arguments
+    /// * `exchange` - Venue whose market data drives the simulation
+    /// * `markets` - Symbols accepted for trading
+    /// * `config` - Slippage, fees and starting balances
+    /// * `account_event_tx` - Channel receiving the simulated account events
+    pub fn new(
+        exchange: Exchange,
+        markets: Vec<Symbol>,
+        config: PaperConfig,
+        account_event_tx: broadcast::Sender<AccountEvent>,
+    ) -> Self {
+        let balances = config
+            .initial_balances
+            .iter()
+            .map(|(currency, amount)| {
+                (
+                    currency.clone(),
+                    PaperBalance {
+                        available: *amount,
+                        locked: Amount::ZERO,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            exchange,
+            markets,
+            config,
+            account_event_tx,
+            quotes: DashMap::new(),
+            orders: DashMap::new(),
+            balances: Mutex::new(balances),
+            next_order_id: AtomicU64::new(1),
+            next_trade_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Consume market data until the bus closes or `shutdown` is cancelled
+    pub async fn run(
+        &self,
+        mut market_rx: broadcast::Receiver<MarketEvent>,
+        shutdown: CancellationToken,
+    ) {
+        tracing::info!("📝 Paper trading active for {:?}", self.exchange);
+        loop {
+            let event = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                event = market_rx.recv() => event,
+            };
+            match event {
+                Ok(event) => self.on_market_event(&event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "⚠️  Paper executor for {:?} lagged, skipped {} market events",
+                        self.exchange,
+                        skipped
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        tracing::info!("🛑 Paper trading stopped for {:?}", self.exchange);
+    }
+
+    /// Update the BBO on quotes and fill resting limit orders crossed by trades
+    pub fn on_market_event(&self, event: &MarketEvent) {
+        if *event.exchange() != self.exchange {
+            return;
+        }
+
+        match event {
+            MarketEvent::Quote(quote) => {
+                self.quotes.insert(quote.symbol.clone(), quote.clone());
+            }
+            MarketEvent::Trade(tick) => {
+                for mut entry in self.orders.iter_mut() {
+                    let order = entry.value_mut();
+                    if order.ack.status != OrderStatus::Approved
+                        || order.native_symbol != tick.symbol
+                    {
+                        continue;
+                    }
+
+                    // Trades at exactly our price may not reach us in the queue
+                    let limit = order.request.price.unwrap_or_default();
+                    let crossed = match order.request.side {
+                        OrderSide::Buy => tick.price < limit,
+                        OrderSide::Sell => tick.price > limit,
+                    };
+                    if crossed {
+                        self.fill(order, limit, Liquidity::Maker, tick.timestamp);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Current simulated balances, one entry per currency
+    pub fn balances(&self) -> Vec<BalanceUpdate> {
+        let balances = self.balances.lock().expect("paper balances poisoned");
+        let timestamp = Utc::now();
+        balances
+            .iter()
+            .map(|(currency, balance)| self.balance_update(currency, balance, timestamp))
+            .collect()
+    }
+
+    /// Price a new order would fill at immediately, if it is marketable
+    fn taker_price(&self, request: &OrderRequest, native_symbol: &str) -> Option<Price> {
+        let quote = self.quotes.get(native_symbol)?;
+        let (bid, ask) = (quote.bid_price, quote.ask_price);
+        let slippage = bps(self.config.slippage_bps);
+
+        match (&request.order_type, &request.side) {
+            (OrderType::Market, OrderSide::Buy) => {
+                Some(Price::new(ask.value() * (Decimal::ONE + slippage)))
+            }
+            (OrderType::Market, OrderSide::Sell) => {
+                Some(Price::new(bid.value() * (Decimal::ONE - slippage)))
+            }
+            (OrderType::Limit, OrderSide::Buy) => {
+                request.price.filter(|limit| *limit >= ask).map(|_| ask)
+            }
+            (OrderType::Limit, OrderSide::Sell) => {
+                request.price.filter(|limit| *limit <= bid).map(|_| bid)
+            }
+        }
+    }
+
+    /// Lock the funds an order needs, failing when they are not available
+    fn reserve(&self, order: &mut PaperOrder, price: Price) -> ExecutionResult<()> {
+        let (currency, amount) = match order.request.side {
+            // Taker fee is the most a buy can cost on top of its notional
+            OrderSide::Buy => (
+                &order.symbol.quote,
+                Amount::new(
+                    (order.request.quantity * price).value()
+                        * (Decimal::ONE + bps(self.config.taker_fee_bps)),
+                ),
+            ),
+            OrderSide::Sell => (&order.symbol.base, Amount::from(order.request.quantity)),
+        };
+
+        let mut balances = self.balances.lock().expect("paper balances poisoned");
+        let balance = balances.entry(currency.clone()).or_default();
+        if balance.available < amount {
+            return Err(ExecutionError::OrderFailed {
+                exchange: format!("{:?} (paper)", self.exchange),
+                reason: format!(
+                    "insufficient {}: {} available, {} required",
+                    currency, balance.available, amount
+                ),
+            });
+        }
+
+        balance.available -= amount;
+        balance.locked += amount;
+        order.reserved = amount;
+        self.publish(AccountEvent::Balance(self.balance_update(
+            currency,
+            balance,
+            Utc::now(),
+        )));
+        Ok(())
+    }
+
+    /// Return an order's locked funds to the available balance
+    fn release(&self, order: &mut PaperOrder) {
+        let currency = match order.request.side {
+            OrderSide::Buy => &order.symbol.quote,
+            OrderSide::Sell => &order.symbol.base,
+        };
+
+        let mut balances = self.balances.lock().expect("paper balances poisoned");
+        let balance = balances.entry(currency.clone()).or_default();
+        balance.locked -= order.reserved;
+        balance.available += order.reserved;
+        order.reserved = Amount::ZERO;
+        self.publish(AccountEvent::Balance(self.balance_update(
+            currency,
+            balance,
+            Utc::now(),
+        )));
+    }
+
+    /// Fill an order in full, settle balances and publish the account events
+    fn fill(
+        &self,
+        order: &mut PaperOrder,
+        price: Price,
+        liquidity: Liquidity,
+        timestamp: DateTime<Utc>,
+    ) {
+        let quantity = order.request.quantity;
+        let notional = Amount::from(quantity * price);
+        let fee_bps = match liquidity {
+            Liquidity::Maker => self.config.maker_fee_bps,
+            Liquidity::Taker => self.config.taker_fee_bps,
+        };
+        let fee = Amount::new(notional.value() * bps(fee_bps));
+
+        {
+            let mut balances = self.balances.lock().expect("paper balances poisoned");
+            let (spent, spent_amount, received, received_amount) = match order.request.side {
+                OrderSide::Buy => (
+                    &order.symbol.quote,
+                    notional + fee,
+                    &order.symbol.base,
+                    Amount::from(quantity),
+                ),
+                OrderSide::Sell => (
+                    &order.symbol.base,
+                    Amount::from(quantity),
+                    &order.symbol.quote,
+                    notional - fee,
+                ),
+            };
+
+            let balance = balances.entry(spent.clone()).or_default();
+            balance.locked -= order.reserved;
+            balance.available += order.reserved - spent_amount;
+            let spent_update = self.balance_update(spent, balance, timestamp);
+
+            let balance = balances.entry(received.clone()).or_default();
+            balance.available += received_amount;
+            let received_update = self.balance_update(received, balance, timestamp);
+
+            order.reserved = Amount::ZERO;
+            self.publish(AccountEvent::Fill(Fill {
+                exchange: self.exchange.clone(),
+                symbol: order.native_symbol.clone(),
+                exchange_order_id: order.ack.exchange_order_id.clone(),
+                client_order_id: order.ack.client_order_id.clone(),
+                trade_id: format!(
+                    "paper-t{}",
+                    self.next_trade_id.fetch_add(1, Ordering::Relaxed)
+                ),
+                side: order.request.side.clone(),
+                price,
+                quantity,
+                fee,
+                fee_currency: order.symbol.quote.clone(),
+                liquidity: Some(liquidity),
+                timestamp,
+            }));
+
+            order.ack.status = OrderStatus::Executed;
+            order.ack.filled_quantity = quantity;
+            order.ack.average_price = Some(price);
+            order.ack.timestamp = timestamp;
+            self.publish_order_update(order);
+            self.publish(AccountEvent::Balance(spent_update));
+            self.publish(AccountEvent::Balance(received_update));
+        }
+
+        tracing::info!(
+            "📝 Paper fill {} on {:?}: {:?} {} {} @ {} ({:?}, fee {} {})",
+            order.ack.exchange_order_id,
+            self.exchange,
+            order.request.side,
+            quantity,
+            order.ack.symbol,
+            price,
+            liquidity,
+            fee,
+            order.symbol.quote
+        );
+    }
+
+    fn balance_update(
+        &self,
+        currency: &str,
+        balance: &PaperBalance,
+        timestamp: DateTime<Utc>,
+    ) -> BalanceUpdate {
+        BalanceUpdate {
+            exchange: self.exchange.clone(),
+            currency: currency.to_string(),
+            available: balance.available,
+            locked: balance.locked,
+            total: balance.available + balance.locked,
+            timestamp,
+        }
+    }
+
+    fn publish_order_update(&self, order: &PaperOrder) {
+        self.publish(AccountEvent::OrderUpdate(OrderUpdate {
+            exchange: self.exchange.clone(),
+            symbol: order.native_symbol.clone(),
+            exchange_order_id: order.ack.exchange_order_id.clone(),
+            client_order_id: order.ack.client_order_id.clone(),
+            side: order.request.side.clone(),
+            status: order.ack.status.clone(),
+            filled_quantity: order.ack.filled_quantity,
+            average_price: order.ack.average_price,
+            timestamp: order.ack.timestamp,
+        }));
+    }
+
+    fn publish(&self, event: AccountEvent) {
+        // No subscribers is not an error: the simulation keeps running
+        let _ = self.account_event_tx.send(event);
+    }
+}
+
+/// Basis points as a decimal fraction (10 bps -> 0.001)
+fn bps(value: f64) -> Decimal {
+    Decimal::try_from(value).unwrap_or_default() / Decimal::from(10_000)
+}
+
+#[async_trait]
+impl OrderExecutor for PaperExecutor {
+    fn exchange(&self) -> Exchange {
+        self.exchange.clone()
+    }
+
+    async fn submit_order(&self, request: &OrderRequest) -> ExecutionResult<OrderAck> {
+        request.validate()?;
+        let native_symbol = resolve_symbol(&self.markets, &self.exchange, &request.symbol)?;
+        let symbol: Symbol = request.symbol.parse().map_err(|_| {
+            ExecutionError::InvalidOrder(format!("invalid symbol '{}'", request.symbol))
+        })?;
+        let taker_price = self.taker_price(request, &native_symbol);
+
+        if request.order_type == OrderType::Market && taker_price.is_none() {
+            return Err(ExecutionError::OrderFailed {
+                exchange: format!("{:?} (paper)", self.exchange),
+                reason: format!("no quote for {} yet", request.symbol),
+            });
+        }
+        if request.post_only && taker_price.is_some() {
+            return Err(ExecutionError::OrderFailed {
+                exchange: format!("{:?} (paper)", self.exchange),
+                reason: "post-only order would take liquidity".to_string(),
+            });
+        }
+
+        let exchange_order_id = format!(
+            "paper-{}",
+            self.next_order_id.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut order = PaperOrder {
+            symbol,
+            native_symbol,
+            ack: OrderAck {
+                exchange: self.exchange.clone(),
+                symbol: request.symbol.clone(),
+                exchange_order_id: exchange_order_id.clone(),
+                client_order_id: Some(request.client_order_id.clone()),
+                status: OrderStatus::Approved,
+                filled_quantity: Quantity::ZERO,
+                average_price: None,
+                timestamp: Utc::now(),
+            },
+            request: request.clone(),
+            reserved: Amount::ZERO,
+        };
+
+        tracing::info!(
+            "📝 Paper order {} on {:?}: {:?} {:?} {} {} @ {:?}",
+            exchange_order_id,
+            self.exchange,
+            request.order_type,
+            request.side,
+            request.quantity,
+            request.symbol,
+            request.price
+        );
+
+        match taker_price {
+            Some(price) => {
+                self.reserve(&mut order, price)?;
+                self.fill(&mut order, price, Liquidity::Taker, Utc::now());
+            }
+            // Immediate-or-cancel and fill-or-kill never rest on the book
+            None if request.time_in_force != TimeInForce::Gtc => {
+                order.ack.status = OrderStatus::Cancelled;
+                self.publish_order_update(&order);
+            }
+            None => {
+                self.reserve(&mut order, request.price.unwrap_or_default())?;
+                self.publish_order_update(&order);
+            }
+        }
+
+        let ack = order.ack.clone();
+        self.orders.insert(exchange_order_id, order);
+        Ok(ack)
+    }
+
+    async fn cancel_order(
+        &self,
+        _symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        let mut order =
+            self.orders
+                .get_mut(exchange_order_id)
+                .ok_or_else(|| ExecutionError::CancelFailed {
+                    order_id: exchange_order_id.to_string(),
+                    reason: "unknown order".to_string(),
+                })?;
+
+        if order.ack.status != OrderStatus::Approved {
+            return Err(ExecutionError::CancelFailed {
+                order_id: exchange_order_id.to_string(),
+                reason: format!("order is {:?}", order.ack.status),
+            });
+        }
+
+        self.release(&mut order);
+        order.ack.status = OrderStatus::Cancelled;
+        order.ack.timestamp = Utc::now();
+        self.publish_order_update(&order);
+        Ok(order.ack.clone())
+    }
+
+    async fn query_order(
+        &self,
+        _symbol: &str,
+        exchange_order_id: &str,
+    ) -> ExecutionResult<OrderAck> {
+        self.orders
+            .get(exchange_order_id)
+            .map(|order| order.ack.clone())
+            .ok_or_else(|| {
+                ExecutionError::InvalidOrder(format!("unknown paper order {}", exchange_order_id))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kairos_domain::MarketTick;
+    use std::str::FromStr;
+
+    fn dec<T: FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+    use uuid::Uuid;
+
+    fn executor() -> (PaperExecutor, broadcast::Receiver<AccountEvent>) {
+        let (tx, rx) = broadcast::channel(64);
+        let config = PaperConfig {
+            slippage_bps: 10.0,
+            maker_fee_bps: 10.0,
+            taker_fee_bps: 20.0,
+            initial_balances: HashMap::from([("USDT".to_string(), dec("10000"))]),
+        };
+        let executor = PaperExecutor::new(
+            Exchange::Binance,
+            vec![Symbol::new("BTC", "USDT")],
+            config,
+            tx,
+        );
+        executor.on_market_event(&MarketEvent::Quote(Quote {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            bid_price: dec("99"),
+            bid_quantity: dec("1"),
+            ask_price: dec("100"),
+            ask_quantity: dec("1"),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        }));
+        (executor, rx)
+    }
+
+    fn trade(price: &str) -> MarketEvent {
+        MarketEvent::Trade(MarketTick {
+            id: Uuid::new_v4(),
+            symbol: "BTCUSDT".to_string(),
+            price: dec(price),
+            volume: "1".parse().unwrap(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            exchange: Exchange::Binance,
+            trade_id: None,
+            side: None,
+        })
+    }
+
+    fn balance(executor: &PaperExecutor, currency: &str) -> (Amount, Amount) {
+        executor
+            .balances()
+            .into_iter()
+            .find(|b| b.currency == currency)
+            .map(|b| (b.available, b.locked))
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_market_order_fills_at_bbo_with_slippage_and_fee() {
+        let (executor, mut rx) = executor();
+        let ack = executor
+            .submit_order(&OrderRequest::market("BTC/USDT", OrderSide::Buy, dec("2")))
+            .await
+            .unwrap();
+
+        assert_eq!(ack.status, OrderStatus::Executed);
+        assert_eq!(ack.average_price, Some(dec("100.1")));
+
+        // 200.2 notional plus the 20 bps taker fee, to the last digit
+        assert_eq!(balance(&executor, "USDT"), (dec("9799.3996"), Amount::ZERO));
+        assert_eq!(balance(&executor, "BTC"), (dec("2"), Amount::ZERO));
+
+        let events: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        let fill = events
+            .iter()
+            .find_map(|event| match event {
+                AccountEvent::Fill(fill) => Some(fill),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(fill.symbol, "BTCUSDT");
+        assert_eq!(fill.fee, dec("0.4004"));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            AccountEvent::OrderUpdate(update) if update.status == OrderStatus::Executed
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_rests_until_trade_crosses() {
+        let (executor, _rx) = executor();
+        let ack = executor
+            .submit_order(&OrderRequest::limit(
+                "BTC/USDT",
+                OrderSide::Buy,
+                dec("1"),
+                dec("95"),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(ack.status, OrderStatus::Approved);
+        assert_eq!(balance(&executor, "USDT").1, dec("95.19"));
+
+        executor.on_market_event(&trade("95"));
+        let resting = executor
+            .query_order("BTC/USDT", &ack.exchange_order_id)
+            .await
+            .unwrap();
+        assert_eq!(resting.status, OrderStatus::Approved);
+
+        executor.on_market_event(&trade("94.5"));
+        let filled = executor
+            .query_order("BTC/USDT", &ack.exchange_order_id)
+            .await
+            .unwrap();
+        assert_eq!(filled.status, OrderStatus::Executed);
+        assert_eq!(filled.average_price, Some(dec("95")));
+
+        // Maker fee, lock fully released
+        assert_eq!(balance(&executor, "USDT"), (dec("9904.905"), Amount::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_rejections_and_cancel() {
+        let (executor, _rx) = executor();
+
+        // Marketable post-only
+        assert!(executor
+            .submit_order(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("1"), dec("100")).post_only()
+            )
+            .await
+            .is_err());
+        // Insufficient funds
+        assert!(executor
+            .submit_order(&OrderRequest::limit(
+                "BTC/USDT",
+                OrderSide::Sell,
+                dec("1"),
+                dec("200")
+            ))
+            .await
+            .is_err());
+        // Non-marketable IOC is cancelled without resting
+        let ioc = executor
+            .submit_order(
+                &OrderRequest::limit("BTC/USDT", OrderSide::Buy, dec("1"), dec("90"))
+                    .with_time_in_force(TimeInForce::Ioc),
+            )
+            .await
+            .unwrap();
+        assert_eq!(ioc.status, OrderStatus::Cancelled);
+
+        let ack = executor
+            .submit_order(&OrderRequest::limit(
+                "BTC/USDT",
+                OrderSide::Buy,
+                dec("1"),
+                dec("90"),
+            ))
+            .await
+            .unwrap();
+        let cancelled = executor
+            .cancel_order("BTC/USDT", &ack.exchange_order_id)
+            .await
+            .unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert_eq!(balance(&executor, "USDT"), (dec("10000"), Amount::ZERO));
+        assert!(executor
+            .cancel_order("BTC/USDT", &ack.exchange_order_id)
+            .await
+            .is_err());
+    }
+}
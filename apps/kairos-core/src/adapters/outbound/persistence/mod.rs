@@ -3,6 +3,8 @@
 pub mod dragonfly;
 pub mod error;
 pub mod timescale;
+pub mod writer;
 
 // Re-export error types
 pub use error::{PersistenceError, PersistenceResult};
+pub use writer::{PersistenceWriter, WriterConfig};
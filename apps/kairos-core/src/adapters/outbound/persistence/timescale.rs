@@ -1,7 +1,10 @@
 // TimescaleDB client for historical data
 
 use super::error::{PersistenceError, PersistenceResult};
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use crate::application::oms::ManagedOrder;
+use kairos_domain::{MarketTick, OrderSide, OrderState};
+use sqlx::{postgres::PgPoolOptions, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
 
 pub struct TimescaleClient {
     pool: PgPool,
@@ -18,14 +21,97 @@ impl TimescaleClient {
         Ok(Self { pool })
     }
 
-    pub async fn save_market_tick(
-        &self,
-        _symbol: &str,
-        _price: f64,
-        _volume: f64,
-    ) -> PersistenceResult<()> {
-        // TODO: Implement batch insert for market ticks
-        // Use hypertables for time-series optimization
+    pub async fn save_market_tick(&self, tick: &MarketTick) -> PersistenceResult<()> {
+        self.save_market_ticks(std::slice::from_ref(tick)).await
+    }
+
+    /// Insert a batch of ticks in one statement (duplicates are skipped)
+    pub async fn save_market_ticks(&self, ticks: &[MarketTick]) -> PersistenceResult<()> {
+        if ticks.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = QueryBuilder::<Postgres>::new(
+            "INSERT INTO market_ticks \
+             (time, received_at, symbol, exchange, price, volume, trade_id, side) ",
+        );
+        query.push_values(ticks, |mut row, tick| {
+            row.push_bind(tick.timestamp)
+                .push_bind(tick.received_at)
+                .push_bind(&tick.symbol)
+                .push_bind(format!("{:?}", tick.exchange))
+                .push_bind(tick.price.to_f64())
+                .push_bind(tick.volume.to_f64())
+                .push_bind(tick.trade_id.as_deref().unwrap_or_default())
+                .push_bind(tick.side.as_ref().map(side_str));
+        });
+        query.push(" ON CONFLICT DO NOTHING");
+
+        query
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PersistenceError::SaveFailed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Upsert the latest state of a batch of managed orders
+    ///
+    /// Orders are keyed by their client order ID (a UUID) and creation time,
+    /// so later snapshots of the same order update its status in place.
+    pub async fn save_orders(&self, orders: &[ManagedOrder]) -> PersistenceResult<()> {
+        let rows: Vec<(Uuid, &ManagedOrder)> = orders
+            .iter()
+            .filter_map(|order| match Uuid::parse_str(&order.client_order_id) {
+                Ok(id) => Some((id, order)),
+                Err(_) => {
+                    tracing::warn!(
+                        "⚠️  Order {} has no UUID client ID, not persisted",
+                        order.client_order_id
+                    );
+                    None
+                }
+            })
+            .collect();
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = QueryBuilder::<Postgres>::new(
+            "INSERT INTO orders \
+             (id, created_at, symbol, side, order_type, quantity, price, status, executed_at, exchange) ",
+        );
+        query.push_values(rows, |mut row, (id, order)| {
+            row.push_bind(id)
+                .push_bind(order.created_at)
+                .push_bind(&order.symbol)
+                .push_bind(side_str(&order.side))
+                .push_bind(format!("{:?}", order.order_type))
+                .push_bind(order.quantity.to_f64())
+                .push_bind(order.price.map(|price| price.to_f64()))
+                .push_bind(format!("{:?}", order.state))
+                .push_bind((order.state == OrderState::Filled).then_some(order.updated_at))
+                .push_bind(format!("{:?}", order.exchange));
+        });
+        query.push(
+            " ON CONFLICT (created_at, id) DO UPDATE \
+             SET status = EXCLUDED.status, executed_at = EXCLUDED.executed_at",
+        );
+
+        query
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PersistenceError::SaveFailed(e.to_string()))?;
+
         Ok(())
     }
 }
+
+fn side_str(side: &OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "buy",
+        OrderSide::Sell => "sell",
+    }
+}
@@ -0,0 +1,295 @@
+// Write-behind persistence - batches ticks and order states into TimescaleDB
+
+use super::timescale::TimescaleClient;
+use crate::application::oms::OrderManager;
+use crate::config::Settings;
+use kairos_domain::{AccountEvent, MarketEvent, MarketTick};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Failed batches kept for retry, in multiples of the batch size
+const MAX_BUFFERED_BATCHES: usize = 10;
+
+/// Batching policy for [`PersistenceWriter`]
+#[derive(Debug, Clone)]
+pub struct WriterConfig {
+    /// Ticks buffered before a batch insert is triggered
+    pub batch_size: usize,
+    /// Partial batches are written at least this often
+    pub flush_interval: Duration,
+}
+
+impl WriterConfig {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            batch_size: settings.database.write_batch_size.max(1),
+            flush_interval: Duration::from_millis(settings.database.write_flush_interval_ms),
+        }
+    }
+}
+
+/// Rows waiting to be written
+///
+/// Trades are kept in arrival order. Orders are tracked by client order ID
+/// only: their latest state is read from the OMS when the batch is written,
+/// so a burst of updates to one order becomes a single upsert.
+#[derive(Debug, Default)]
+pub struct WriteBuffer {
+    ticks: Vec<MarketTick>,
+    orders: HashSet<String>,
+}
+
+impl WriteBuffer {
+    /// Buffer trades; other market events are not persisted
+    pub fn push_market_event(&mut self, event: &MarketEvent) {
+        if let MarketEvent::Trade(tick) = event {
+            self.ticks.push(tick.clone());
+        }
+    }
+
+    /// Mark the order behind an update or fill as changed
+    pub fn push_account_event(&mut self, event: &AccountEvent) {
+        let client_order_id = match event {
+            AccountEvent::OrderUpdate(update) => update.client_order_id.as_ref(),
+            AccountEvent::Fill(fill) => fill.client_order_id.as_ref(),
+            AccountEvent::Balance(_) | AccountEvent::Position(_) => None,
+        };
+        if let Some(id) = client_order_id {
+            self.orders.insert(id.clone());
+        }
+    }
+
+    pub fn tick_count(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn order_count(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty() && self.orders.is_empty()
+    }
+
+    pub fn take_ticks(&mut self) -> Vec<MarketTick> {
+        std::mem::take(&mut self.ticks)
+    }
+
+    pub fn take_orders(&mut self) -> Vec<String> {
+        self.orders.drain().collect()
+    }
+
+    /// Put back ticks whose write failed, dropping the oldest beyond `capacity`
+    ///
+    /// Returns how many ticks were dropped.
+    pub fn restore_ticks(&mut self, mut failed: Vec<MarketTick>, capacity: usize) -> usize {
+        failed.append(&mut self.ticks);
+        let dropped = failed.len().saturating_sub(capacity);
+        failed.drain(..dropped);
+        self.ticks = failed;
+        dropped
+    }
+
+    pub fn restore_orders(&mut self, failed: Vec<String>) {
+        self.orders.extend(failed);
+    }
+}
+
+/// Streams trades and order states to TimescaleDB in batches
+///
+/// Runs until `shutdown` is cancelled, then writes whatever is still
+/// queued on its channels and buffered in memory before returning, and logs
+/// anything it could not persist.
+///
+/// # Example
+/// ```rust,ignore
+/// let writer = PersistenceWriter::new(client, state.orders.clone(), WriterConfig::from_settings(&settings));
+/// tokio::spawn(writer.run(engine.subscribe_market_data(), engine.subscribe_account_events(), token));
+/// ```
+pub struct PersistenceWriter {
+    client: TimescaleClient,
+    orders: Arc<OrderManager>,
+    config: WriterConfig,
+    buffer: WriteBuffer,
+}
+
+impl PersistenceWriter {
+    pub fn new(client: TimescaleClient, orders: Arc<OrderManager>, config: WriterConfig) -> Self {
+        Self {
+            client,
+            orders,
+            config,
+            buffer: WriteBuffer::default(),
+        }
+    }
+
+    pub async fn run(
+        mut self,
+        mut market_rx: broadcast::Receiver<MarketEvent>,
+        mut account_rx: broadcast::Receiver<AccountEvent>,
+        shutdown: CancellationToken,
+    ) {
+        tracing::info!(
+            "💾 Persistence writer running (batch {}, every {:?})",
+            self.config.batch_size,
+            self.config.flush_interval
+        );
+        let mut ticker = tokio::time::interval(self.config.flush_interval);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = ticker.tick() => self.flush().await,
+                event = market_rx.recv() => match event {
+                    Ok(event) => {
+                        self.buffer.push_market_event(&event);
+                        if self.buffer.tick_count() >= self.config.batch_size {
+                            self.flush().await;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("⚠️  Persistence lagged, {} market events not stored", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                event = account_rx.recv() => match event {
+                    Ok(event) => self.buffer.push_account_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("⚠️  Persistence lagged, {} account events not stored", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            }
+        }
+
+        // Events published before shutdown are still written
+        while let Some(event) = next_queued(&mut market_rx) {
+            self.buffer.push_market_event(&event);
+        }
+        while let Some(event) = next_queued(&mut account_rx) {
+            self.buffer.push_account_event(&event);
+        }
+        self.flush().await;
+
+        if self.buffer.is_empty() {
+            tracing::info!("💾 Persistence writer drained");
+        } else {
+            tracing::warn!(
+                "⚠️  Persistence writer stopped with {} ticks and {} orders not written",
+                self.buffer.tick_count(),
+                self.buffer.order_count()
+            );
+        }
+    }
+
+    /// Write every buffered tick and order, keeping failed batches for retry
+    async fn flush(&mut self) {
+        let ticks = self.buffer.take_ticks();
+        if let Err(e) = self.client.save_market_ticks(&ticks).await {
+            tracing::error!("❌ Failed to write {} ticks: {}", ticks.len(), e);
+            let capacity = self.config.batch_size * MAX_BUFFERED_BATCHES;
+            let dropped = self.buffer.restore_ticks(ticks, capacity);
+            if dropped > 0 {
+                tracing::warn!("⚠️  Dropped {} buffered ticks", dropped);
+            }
+        }
+
+        let ids = self.buffer.take_orders();
+        let orders: Vec<_> = ids.iter().filter_map(|id| self.orders.get(id)).collect();
+        if let Err(e) = self.client.save_orders(&orders).await {
+            tracing::error!("❌ Failed to write {} orders: {}", orders.len(), e);
+            self.buffer.restore_orders(ids);
+        }
+    }
+}
+
+/// Next event already in the channel, without waiting
+fn next_queued<T: Clone>(rx: &mut broadcast::Receiver<T>) -> Option<T> {
+    loop {
+        match rx.try_recv() {
+            Ok(event) => return Some(event),
+            Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+            Err(_) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use kairos_domain::{Exchange, OrderSide, OrderStatus, OrderUpdate};
+    use uuid::Uuid;
+
+    fn trade(price: &str) -> MarketEvent {
+        MarketEvent::Trade(MarketTick {
+            id: Uuid::new_v4(),
+            symbol: "BTCUSDT".to_string(),
+            price: price.parse().unwrap(),
+            volume: "1".parse().unwrap(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            exchange: Exchange::Binance,
+            trade_id: None,
+            side: None,
+        })
+    }
+
+    fn update(client_order_id: &str) -> AccountEvent {
+        AccountEvent::OrderUpdate(OrderUpdate {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: Some(client_order_id.to_string()),
+            side: OrderSide::Buy,
+            status: OrderStatus::Approved,
+            filled_quantity: "0.5".parse().unwrap(),
+            average_price: Some("100".parse().unwrap()),
+            timestamp: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_buffer_batches_trades_and_dedupes_orders() {
+        let mut buffer = WriteBuffer::default();
+        buffer.push_market_event(&trade("100"));
+        buffer.push_market_event(&trade("101"));
+        buffer.push_account_event(&update("a"));
+        buffer.push_account_event(&update("a"));
+        buffer.push_account_event(&update("b"));
+
+        assert_eq!(buffer.tick_count(), 2);
+        assert_eq!(buffer.order_count(), 2);
+
+        let mut ids = buffer.take_orders();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(buffer.take_ticks().len(), 2);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_restore_keeps_newest_ticks_within_capacity() {
+        let mut buffer = WriteBuffer::default();
+        buffer.push_market_event(&trade("103"));
+
+        let failed = ["100", "101", "102"]
+            .iter()
+            .map(|price| match trade(price) {
+                MarketEvent::Trade(tick) => tick,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(buffer.restore_ticks(failed, 3), 1);
+
+        let prices: Vec<String> = buffer
+            .take_ticks()
+            .iter()
+            .map(|tick| tick.price.to_string())
+            .collect();
+        assert_eq!(prices, vec!["101", "102", "103"]);
+    }
+}
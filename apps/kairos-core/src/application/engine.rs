@@ -1,56 +1,883 @@
 // Engine orchestrator - coordinates all the "organs"
 
+use super::oms::new_client_order_id;
+use super::state::AppState;
+use crate::adapters::inbound::feed_handler::binance::BinanceSubscriptionHandle;
+use crate::adapters::outbound::execution::{
+    ExecutionResult, OrderAck, OrderExecutor, OrderRequest,
+};
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use kairos_domain::{
+    AccountEvent, DomainError, Exchange, InternalOrder, MarketEvent, MarketEventFilter, OrderState,
+    OrderUpdate, Symbol,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{broadcast, mpsc};
-use kairos_domain::{MarketTick, InternalOrder};
+use tokio_util::sync::CancellationToken;
 
+/// How often finished orders are dropped from the OMS
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long shutdown waits for venues to answer submissions already sent
+const ACK_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Order intent from a strategy (or external client) for a specific venue
+#[derive(Debug, Clone)]
+pub struct StrategyOrder {
+    /// Name of the producer, recorded on the managed order
+    pub strategy: String,
+    /// ID the order is tracked under, known to the producer before routing
+    pub client_order_id: String,
+    pub exchange: Exchange,
+    pub order: InternalOrder,
+}
+
+impl StrategyOrder {
+    /// Intent with a freshly generated client order ID
+    pub fn new(strategy: impl Into<String>, exchange: Exchange, order: InternalOrder) -> Self {
+        Self {
+            strategy: strategy.into(),
+            client_order_id: new_client_order_id(),
+            exchange,
+            order,
+        }
+    }
+}
+
+/// Request to cancel one of a producer's own orders
+#[derive(Debug, Clone)]
+pub struct CancelRequest {
+    pub strategy: String,
+    pub client_order_id: String,
+}
+
+/// Venue request made on an order's behalf
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestKind {
+    Submit,
+    Cancel,
+}
+
+/// Outcome of a request handed to an executor
+struct ExecutionReport {
+    client_order_id: String,
+    kind: RequestKind,
+    result: ExecutionResult<OrderAck>,
+}
+
+/// Routes strategy orders through risk to the venue executors
+///
+/// The engine owns every channel of the pipeline:
+///
+/// ```text
+/// feeds -> market data (broadcast) -> strategies -> orders (mpsc) -> risk -> executor
+///                                                                              |
+/// strategies <- account events (broadcast) <- venues / paper <-----------------+
+/// ```
+///
+/// Acks are published as [`AccountEvent::OrderUpdate`], so fills, venue
+/// updates and acks reach the OMS, the ledger and any subscriber the same way.
+///
+/// The daily risk budget is reset at every UTC midnight while it runs, and
+/// finished orders are pruned from the OMS once older than the retention.
+///
+/// When the shutdown token is cancelled the engine stops taking orders,
+/// waits (up to a bounded timeout) for submissions already sent to a venue,
+/// optionally cancels every open order, then returns.
+///
+/// # Example
+/// ```rust,ignore
+/// let engine = TradingEngine::new(state)
+///     .with_executor(binance_executor)
+///     .with_cancel_on_shutdown(true);
+/// let orders = engine.get_order_sender();
+/// tokio::spawn(engine.run(shutdown.clone()));
+/// orders.send(StrategyOrder::new("arb", exchange, order)).await?;
+/// ```
 pub struct TradingEngine {
+    state: Arc<AppState>,
+    executors: HashMap<Exchange, Arc<dyn OrderExecutor>>,
+    cancel_on_shutdown: bool,
+    terminal_order_retention: Duration,
+
+    // Runtime control of the Binance market data streams
+    market_subscriptions: Option<BinanceSubscriptionHandle>,
+
     // Broadcast channel for market data (The Feed Handler -> Everyone)
-    market_data_tx: broadcast::Sender<MarketTick>,
-    
+    market_data_tx: broadcast::Sender<MarketEvent>,
+
+    // Broadcast channel for private account events (Venues / Paper -> Everyone)
+    account_event_tx: broadcast::Sender<AccountEvent>,
+
     // MPSC channel for orders (Strategies -> Risk Engine)
-    order_tx: mpsc::Sender<InternalOrder>,
-    order_rx: mpsc::Receiver<InternalOrder>,
+    order_tx: mpsc::Sender<StrategyOrder>,
+    order_rx: mpsc::Receiver<StrategyOrder>,
+
+    // MPSC channel for cancels (Strategies -> Executors)
+    cancel_tx: mpsc::Sender<CancelRequest>,
+    cancel_rx: mpsc::Receiver<CancelRequest>,
+
+    // MPSC channel for executor results (Execution tasks -> Engine)
+    report_tx: mpsc::Sender<ExecutionReport>,
+    report_rx: mpsc::Receiver<ExecutionReport>,
 }
 
 impl TradingEngine {
-    pub fn new() -> Self {
+    pub fn new(state: Arc<AppState>) -> Self {
         let (market_data_tx, _) = broadcast::channel(1000);
+        let (account_event_tx, _) = broadcast::channel(1000);
         let (order_tx, order_rx) = mpsc::channel(100);
+        let (cancel_tx, cancel_rx) = mpsc::channel(100);
+        let (report_tx, report_rx) = mpsc::channel(100);
 
         Self {
+            state,
+            executors: HashMap::new(),
+            cancel_on_shutdown: false,
+            terminal_order_retention: Duration::from_secs(3600),
+            market_subscriptions: None,
             market_data_tx,
+            account_event_tx,
             order_tx,
             order_rx,
+            cancel_tx,
+            cancel_rx,
+            report_tx,
+            report_rx,
         }
     }
 
+    /// Route orders for the executor's venue to it (replaces any previous one)
+    pub fn with_executor(mut self, executor: Arc<dyn OrderExecutor>) -> Self {
+        self.executors.insert(executor.exchange(), executor);
+        self
+    }
+
+    /// Cancel every open exchange order when the engine shuts down
+    pub fn with_cancel_on_shutdown(mut self, cancel: bool) -> Self {
+        self.cancel_on_shutdown = cancel;
+        self
+    }
+
+    /// Keep filled, cancelled, rejected and expired orders queryable this long
+    pub fn with_terminal_order_retention(mut self, retention: Duration) -> Self {
+        self.terminal_order_retention = retention;
+        self
+    }
+
+    /// Let external clients add and drop Binance symbols at runtime
+    pub fn with_market_subscriptions(mut self, handle: BinanceSubscriptionHandle) -> Self {
+        self.market_subscriptions = Some(handle);
+        self
+    }
+
+    pub fn state(&self) -> Arc<AppState> {
+        self.state.clone()
+    }
+
+    /// Venues orders can be routed to
+    pub fn venues(&self) -> Vec<Exchange> {
+        let mut venues: Vec<Exchange> = self.executors.keys().cloned().collect();
+        venues.sort();
+        venues
+    }
+
     /// Returns a subscriber to market data
-    pub fn subscribe_market_data(&self) -> broadcast::Receiver<MarketTick> {
+    pub fn subscribe_market_data(&self) -> broadcast::Receiver<MarketEvent> {
         self.market_data_tx.subscribe()
     }
 
+    /// Returns a subscriber that only yields events accepted by `filter`
+    pub fn subscribe_filtered(&self, filter: MarketEventFilter) -> FilteredMarketEvents {
+        FilteredMarketEvents::new(self.market_data_tx.subscribe(), filter)
+    }
+
+    /// Returns a sender feed handlers can publish market events to
+    pub fn market_data_sender(&self) -> broadcast::Sender<MarketEvent> {
+        self.market_data_tx.clone()
+    }
+
+    /// Returns a subscriber to order updates, fills and balances
+    pub fn subscribe_account_events(&self) -> broadcast::Receiver<AccountEvent> {
+        self.account_event_tx.subscribe()
+    }
+
+    /// Returns a sender private feeds and paper executors publish account events to
+    pub fn account_event_sender(&self) -> broadcast::Sender<AccountEvent> {
+        self.account_event_tx.clone()
+    }
+
+    /// Returns the handle for changing Binance market data streams, if wired
+    pub fn market_subscriptions(&self) -> Option<BinanceSubscriptionHandle> {
+        self.market_subscriptions.clone()
+    }
+
     /// Returns a sender for orders
-    pub fn get_order_sender(&self) -> mpsc::Sender<InternalOrder> {
+    pub fn get_order_sender(&self) -> mpsc::Sender<StrategyOrder> {
         self.order_tx.clone()
     }
 
-    /// Main engine loop
-    pub async fn run(mut self) -> anyhow::Result<()> {
+    /// Returns a sender for cancel requests
+    pub fn get_cancel_sender(&self) -> mpsc::Sender<CancelRequest> {
+        self.cancel_tx.clone()
+    }
+
+    /// Main engine loop, until `shutdown` is cancelled
+    pub async fn run(mut self, shutdown: CancellationToken) -> anyhow::Result<()> {
+        let mut market_rx = self.market_data_tx.subscribe();
+        let mut account_rx = self.account_event_tx.subscribe();
+        tracing::info!("⚙️  Trading engine running, venues: {:?}", self.venues());
+        let daily_reset = tokio::time::sleep(until_utc_midnight(Utc::now()));
+        tokio::pin!(daily_reset);
+        let mut prune =
+            tokio::time::interval_at(tokio::time::Instant::now() + PRUNE_INTERVAL, PRUNE_INTERVAL);
+
         loop {
             tokio::select! {
-                Some(order) = self.order_rx.recv() => {
-                    // TODO: Pass order to Risk Engine
-                    tracing::debug!("Received order: {:?}", order);
+                _ = shutdown.cancelled() => break,
+                () = &mut daily_reset => {
+                    self.state.risk_engine.reset_daily_risk();
+                    tracing::info!("🌅 Daily risk budget reset");
+                    daily_reset
+                        .as_mut()
+                        .reset(tokio::time::Instant::now() + until_utc_midnight(Utc::now()));
                 }
+                _ = prune.tick() => self.prune_terminal_orders(),
+                Some(order) = self.order_rx.recv() => self.route_order(order),
+                Some(cancel) = self.cancel_rx.recv() => self.route_cancel(cancel),
+                Some(report) = self.report_rx.recv() => self.on_execution_report(report),
+                event = account_rx.recv() => match event {
+                    Ok(event) => self.on_account_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("⚠️  Engine lagged, skipped {} account events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                event = market_rx.recv() => match event {
+                    Ok(event) => self.state.risk_engine.positions().on_market_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
                 else => break,
             }
         }
+
+        self.stop(&mut account_rx).await;
         Ok(())
     }
+
+    /// Stop taking orders, settle in-flight submissions and optionally
+    /// cancel open orders, logging whatever is left working on a venue
+    async fn stop(&mut self, account_rx: &mut broadcast::Receiver<AccountEvent>) {
+        tracing::info!("🛑 Trading engine stopping, no new orders accepted");
+        self.order_rx.close();
+        while let Ok(intent) = self.order_rx.try_recv() {
+            self.refuse(&intent, "engine shutting down".to_string());
+        }
+        // Cancels are covered by `cancel_on_shutdown`
+        self.cancel_rx.close();
+
+        // Submissions already handed to an executor still report back, but
+        // the engine holds a report sender itself, so a venue that never
+        // answers would otherwise keep the channel open forever
+        self.apply_queued_account_events(account_rx);
+        let deadline = tokio::time::Instant::now() + ACK_DRAIN_TIMEOUT;
+        while self.awaiting_acks() > 0 {
+            match tokio::time::timeout_at(deadline, self.report_rx.recv()).await {
+                Ok(Some(report)) => {
+                    self.on_execution_report(report);
+                    self.apply_queued_account_events(account_rx);
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    tracing::warn!(
+                        "⏱️  {} submissions unanswered after {:?}, not waiting",
+                        self.awaiting_acks(),
+                        ACK_DRAIN_TIMEOUT
+                    );
+                    break;
+                }
+            }
+        }
+
+        if self.cancel_on_shutdown {
+            self.cancel_open_orders().await;
+            self.apply_queued_account_events(account_rx);
+        }
+
+        let open = self.state.orders.open_orders();
+        if open.is_empty() {
+            tracing::info!("✅ Trading engine stopped with no open orders");
+        }
+        for order in open {
+            tracing::warn!(
+                "⚠️  Order {} left {:?} on {:?}: {:?} {} {} ({} filled)",
+                order.client_order_id,
+                order.state,
+                order.exchange,
+                order.side,
+                order.quantity,
+                order.symbol,
+                order.filled_quantity
+            );
+        }
+    }
+
+    /// Drop finished orders past the retention so the OMS does not grow unbounded
+    fn prune_terminal_orders(&self) {
+        let retention = chrono::Duration::from_std(self.terminal_order_retention)
+            .unwrap_or(chrono::Duration::MAX);
+        let cutoff = Utc::now()
+            .checked_sub_signed(retention)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let pruned = self.state.orders.prune_terminal(cutoff);
+        if pruned > 0 {
+            tracing::debug!("🧹 Pruned {} finished orders", pruned);
+        }
+    }
+
+    /// Orders sent to a venue that has not answered yet
+    fn awaiting_acks(&self) -> usize {
+        self.state
+            .orders
+            .open_orders()
+            .iter()
+            .filter(|order| order.state == OrderState::Sent)
+            .count()
+    }
+
+    /// Cancel every open order that has an exchange order ID, concurrently
+    async fn cancel_open_orders(&self) {
+        let orders = &self.state.orders;
+        let cancels: Vec<_> = orders
+            .open_orders()
+            .into_iter()
+            .filter_map(|order| {
+                let executor = self.executors.get(&order.exchange)?.clone();
+                let exchange_order_id = order.exchange_order_id.clone()?;
+                orders.request_cancel(&order.client_order_id).ok()?;
+                Some(async move {
+                    let result = executor
+                        .cancel_order(&order.symbol, &exchange_order_id)
+                        .await;
+                    ExecutionReport {
+                        client_order_id: order.client_order_id,
+                        kind: RequestKind::Cancel,
+                        result,
+                    }
+                })
+            })
+            .collect();
+
+        tracing::info!("🧹 Cancelling {} open orders", cancels.len());
+        for report in futures::future::join_all(cancels).await {
+            self.on_execution_report(report);
+        }
+    }
+
+    fn apply_queued_account_events(&self, account_rx: &mut broadcast::Receiver<AccountEvent>) {
+        loop {
+            match account_rx.try_recv() {
+                Ok(event) => self.on_account_event(&event),
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    tracing::warn!("⚠️  Engine lagged, skipped {} account events", skipped);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Validate, register, fund and submit a strategy order
+    fn route_order(&self, intent: StrategyOrder) {
+        let orders = &self.state.orders;
+        let risk = &self.state.risk_engine;
+
+        let Some(executor) = self.executors.get(&intent.exchange).cloned() else {
+            self.refuse(&intent, format!("no executor for {:?}", intent.exchange));
+            return;
+        };
+
+        let order = match self.state.validate_order(&intent.exchange, &intent.order) {
+            Ok(order) => order,
+            Err(e) => {
+                self.refuse(&intent, e.to_string());
+                return;
+            }
+        };
+
+        let managed = orders.create_with_id(
+            &intent.client_order_id,
+            &intent.strategy,
+            intent.exchange.clone(),
+            &order,
+        );
+        if let Err(e) = risk.reserve(&managed.client_order_id, &intent.exchange, &order) {
+            let _ = orders.reject(&managed.client_order_id, e.to_string());
+            tracing::warn!("🚫 {} order refused: {}", intent.strategy, e);
+            return;
+        }
+        if let Err(e) = orders.mark_sent(&managed.client_order_id) {
+            tracing::error!("❌ Order {} not sent: {}", managed.client_order_id, e);
+            risk.release(&managed.client_order_id);
+            return;
+        }
+        risk.add_risk(order.risk_score);
+
+        let request = match order.price {
+            Some(price) => {
+                OrderRequest::limit(&order.symbol, order.side.clone(), order.quantity, price)
+            }
+            None => OrderRequest::market(&order.symbol, order.side.clone(), order.quantity),
+        }
+        .with_client_order_id(&managed.client_order_id);
+
+        tracing::info!(
+            "📤 {} -> {:?}: {:?} {} {} @ {}",
+            intent.strategy,
+            intent.exchange,
+            order.side,
+            order.quantity,
+            order.symbol,
+            order
+                .price
+                .map(|price| price.to_string())
+                .unwrap_or_else(|| "market".to_string())
+        );
+
+        // Venue round trips must not stall the engine loop
+        let report_tx = self.report_tx.clone();
+        tokio::spawn(async move {
+            let result = executor.submit_order(&request).await;
+            let _ = report_tx
+                .send(ExecutionReport {
+                    client_order_id: request.client_order_id,
+                    kind: RequestKind::Submit,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    /// Send a cancel for an acknowledged order owned by the requester
+    fn route_cancel(&self, request: CancelRequest) {
+        let orders = &self.state.orders;
+        let Some(order) = orders.get(&request.client_order_id) else {
+            tracing::warn!("⚠️  Cancel for unknown order {}", request.client_order_id);
+            return;
+        };
+        if order.strategy != request.strategy {
+            tracing::warn!(
+                "🚫 {} may not cancel order {} of {}",
+                request.strategy,
+                order.client_order_id,
+                order.strategy
+            );
+            return;
+        }
+        if !order.is_open() {
+            return;
+        }
+        let (Some(executor), Some(exchange_order_id)) = (
+            self.executors.get(&order.exchange).cloned(),
+            order.exchange_order_id.clone(),
+        ) else {
+            tracing::warn!(
+                "⚠️  Order {} not acknowledged yet, cancel skipped",
+                order.client_order_id
+            );
+            return;
+        };
+        if let Err(e) = orders.request_cancel(&order.client_order_id) {
+            tracing::warn!("⚠️  Cancel of {} refused: {}", order.client_order_id, e);
+            return;
+        }
+
+        let report_tx = self.report_tx.clone();
+        tokio::spawn(async move {
+            let result = executor
+                .cancel_order(&order.symbol, &exchange_order_id)
+                .await;
+            let _ = report_tx
+                .send(ExecutionReport {
+                    client_order_id: order.client_order_id,
+                    kind: RequestKind::Cancel,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    /// Record an order refused before reaching a venue
+    fn refuse(&self, intent: &StrategyOrder, reason: String) {
+        let managed = self.state.orders.create_with_id(
+            &intent.client_order_id,
+            &intent.strategy,
+            intent.exchange.clone(),
+            &intent.order,
+        );
+        let _ = self.state.orders.reject(&managed.client_order_id, &reason);
+        tracing::warn!("🚫 {} order refused: {}", intent.strategy, reason);
+    }
+
+    /// Publish an ack as an order update, or settle a failed request
+    fn on_execution_report(&self, report: ExecutionReport) {
+        match report.result {
+            Err(e) if report.kind == RequestKind::Cancel => {
+                tracing::error!("❌ Cancel of {} failed: {}", report.client_order_id, e);
+                let _ = self.state.orders.cancel_rejected(&report.client_order_id);
+            }
+            Ok(ack) => {
+                tracing::info!(
+                    "📨 {:?} acked {} as {} ({:?})",
+                    ack.exchange,
+                    report.client_order_id,
+                    ack.exchange_order_id,
+                    ack.status
+                );
+                self.publish_ack(report.client_order_id, ack);
+            }
+            Err(e) => {
+                tracing::error!("❌ Order {} failed: {}", report.client_order_id, e);
+                let _ = self
+                    .state
+                    .orders
+                    .reject(&report.client_order_id, e.to_string());
+                self.state.risk_engine.release(&report.client_order_id);
+            }
+        }
+    }
+
+    fn publish_ack(&self, client_order_id: String, ack: OrderAck) {
+        let Some(order) = self.state.orders.get(&client_order_id) else {
+            return;
+        };
+        let symbol = ack
+            .symbol
+            .parse::<Symbol>()
+            .map(|symbol| symbol.to_exchange(&ack.exchange))
+            .unwrap_or(ack.symbol);
+        let _ = self
+            .account_event_tx
+            .send(AccountEvent::OrderUpdate(OrderUpdate {
+                exchange: ack.exchange,
+                symbol,
+                exchange_order_id: ack.exchange_order_id,
+                client_order_id: Some(client_order_id),
+                side: order.side,
+                status: ack.status,
+                filled_quantity: ack.filled_quantity,
+                average_price: ack.average_price,
+                timestamp: ack.timestamp,
+            }));
+    }
+
+    /// Keep balances, positions and order states in line with venue events
+    fn on_account_event(&self, event: &AccountEvent) {
+        let risk = &self.state.risk_engine;
+        risk.ledger().on_account_event(event);
+
+        let order = match event {
+            AccountEvent::Fill(fill) => {
+                risk.positions().on_fill(fill);
+                self.state.orders.apply_fill(fill)
+            }
+            AccountEvent::OrderUpdate(update) => self.state.orders.apply_update(update),
+            AccountEvent::Balance(_) | AccountEvent::Position(_) => return,
+        };
+
+        match order {
+            Ok(order) if !order.is_open() => {
+                tracing::info!(
+                    "🏁 Order {} {:?} ({} filled)",
+                    order.client_order_id,
+                    order.state,
+                    order.filled_quantity
+                );
+                risk.release(&order.client_order_id);
+            }
+            Ok(_) => {}
+            // Orders placed outside this engine
+            Err(DomainError::OrderNotFound(id)) => {
+                tracing::debug!("Account event for unmanaged order {}", id)
+            }
+            Err(e) => tracing::warn!("⚠️  Account event not applied: {}", e),
+        }
+    }
+}
+
+/// Market data subscription restricted to the event kinds/symbols a consumer cares about
+pub struct FilteredMarketEvents {
+    rx: broadcast::Receiver<MarketEvent>,
+    filter: MarketEventFilter,
+}
+
+impl FilteredMarketEvents {
+    pub fn new(rx: broadcast::Receiver<MarketEvent>, filter: MarketEventFilter) -> Self {
+        Self { rx, filter }
+    }
+
+    /// Wait for the next matching event, skipping everything else
+    pub async fn recv(&mut self) -> Result<MarketEvent, broadcast::error::RecvError> {
+        loop {
+            let event = self.rx.recv().await?;
+            if self.filter.matches(&event) {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+/// Time left until the next UTC midnight
+fn until_utc_midnight(now: DateTime<Utc>) -> std::time::Duration {
+    let midnight = (now.date_naive() + Days::new(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    (midnight - now).to_std().unwrap_or_default()
 }
 
-impl Default for TradingEngine {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::execution::{PaperConfig, PaperExecutor};
+    use crate::domain::risk::RiskEngine;
+    use async_trait::async_trait;
+    use kairos_domain::{Instrument, InstrumentRegistry, OrderSide};
+
+    /// Venue that accepts submissions and never answers them
+    struct SilentExecutor;
+
+    #[async_trait]
+    impl OrderExecutor for SilentExecutor {
+        fn exchange(&self) -> Exchange {
+            Exchange::Binance
+        }
+
+        async fn submit_order(&self, _request: &OrderRequest) -> ExecutionResult<OrderAck> {
+            std::future::pending().await
+        }
+
+        async fn cancel_order(&self, _symbol: &str, _id: &str) -> ExecutionResult<OrderAck> {
+            std::future::pending().await
+        }
+
+        async fn query_order(&self, _symbol: &str, _id: &str) -> ExecutionResult<OrderAck> {
+            std::future::pending().await
+        }
+    }
+
+    fn limit_buy(quantity: &str, price: &str) -> InternalOrder {
+        InternalOrder {
+            symbol: "BTC/USDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: quantity.parse().unwrap(),
+            price: Some(price.parse().unwrap()),
+            risk_score: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_order_without_executor_is_rejected() {
+        let state = Arc::new(AppState::new(
+            RiskEngine::new(100.0),
+            InstrumentRegistry::new(),
+        ));
+        let engine = TradingEngine::new(state.clone());
+
+        engine.route_order(StrategyOrder::new(
+            "arb",
+            Exchange::Kraken,
+            limit_buy("1", "100"),
+        ));
+
+        let orders = state.orders.by_strategy("arb");
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].state, OrderState::Rejected);
+        assert!(orders[0]
+            .reject_reason
+            .as_deref()
+            .unwrap()
+            .contains("no executor"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_open_orders() {
+        let btc = Symbol::new("BTC", "USDT");
+        let mut instruments = InstrumentRegistry::new();
+        instruments.insert(Instrument::new(
+            Exchange::Binance,
+            btc.clone(),
+            "0.01".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            "1".parse().unwrap(),
+        ));
+        let state = Arc::new(AppState::new(RiskEngine::new(100.0), instruments));
+        state
+            .risk_engine
+            .ledger()
+            .deposit(&Exchange::Binance, "USDT", "1000".parse().unwrap());
+
+        let engine = TradingEngine::new(state.clone()).with_cancel_on_shutdown(true);
+        let paper = PaperExecutor::new(
+            Exchange::Binance,
+            vec![btc],
+            PaperConfig {
+                slippage_bps: 0.0,
+                maker_fee_bps: 0.0,
+                taker_fee_bps: 0.0,
+                initial_balances: HashMap::from([("USDT".to_string(), "1000".parse().unwrap())]),
+            },
+            engine.account_event_sender(),
+        );
+        let engine = engine.with_executor(Arc::new(paper));
+        let orders = engine.get_order_sender();
+        let shutdown = CancellationToken::new();
+        let run = tokio::spawn(engine.run(shutdown.clone()));
+
+        // No quote yet, so the limit order rests on the paper book
+        orders
+            .send(StrategyOrder::new(
+                "arb",
+                Exchange::Binance,
+                limit_buy("1", "100"),
+            ))
+            .await
+            .unwrap();
+        for _ in 0..100 {
+            let acked = state
+                .orders
+                .by_strategy("arb")
+                .iter()
+                .any(|order| order.state == OrderState::Acknowledged);
+            if acked {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let usdt = state
+            .risk_engine
+            .ledger()
+            .balance(&Exchange::Binance, "USDT");
+        assert_eq!(usdt.locked, "100".parse().unwrap());
+
+        shutdown.cancel();
+        run.await.unwrap().unwrap();
+
+        let order = &state.orders.by_strategy("arb")[0];
+        assert_eq!(order.state, OrderState::Cancelled);
+        assert!(state.orders.open_orders().is_empty());
+        let usdt = state
+            .risk_engine
+            .ledger()
+            .balance(&Exchange::Binance, "USDT");
+        assert!(usdt.locked.is_zero());
+
+        // Intake is closed
+        let late = StrategyOrder::new("arb", Exchange::Binance, limit_buy("1", "100"));
+        assert!(orders.send(late).await.is_err());
+    }
+
+    #[test]
+    fn test_until_utc_midnight() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            until_utc_midnight(at("2024-03-01T23:59:30Z")),
+            std::time::Duration::from_secs(30)
+        );
+        assert_eq!(
+            until_utc_midnight(at("2024-03-01T00:00:00Z")),
+            std::time::Duration::from_secs(86_400)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_daily_risk_reset_at_midnight() {
+        let state = Arc::new(AppState::new(
+            RiskEngine::new(100.0),
+            InstrumentRegistry::new(),
+        ));
+        state
+            .risk_engine
+            .ledger()
+            .deposit(&Exchange::Binance, "USDT", "1000".parse().unwrap());
+        state.risk_engine.add_risk(100.0);
+        let order = limit_buy("1", "100");
+        assert!(state
+            .risk_engine
+            .validate_order(&Exchange::Binance, &order)
+            .is_err());
+
+        let shutdown = CancellationToken::new();
+        let run = tokio::spawn(TradingEngine::new(state.clone()).run(shutdown.clone()));
+
+        // The paused clock jumps straight to the reset timer
+        tokio::time::sleep(std::time::Duration::from_secs(86_401)).await;
+        assert!(state
+            .risk_engine
+            .validate_order(&Exchange::Binance, &order)
+            .is_ok());
+
+        shutdown.cancel();
+        run.await.unwrap().unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_does_not_wait_forever_for_acks() {
+        let mut instruments = InstrumentRegistry::new();
+        instruments.insert(Instrument::new(
+            Exchange::Binance,
+            Symbol::new("BTC", "USDT"),
+            "0.01".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            "1".parse().unwrap(),
+        ));
+        let state = Arc::new(AppState::new(RiskEngine::new(100.0), instruments));
+        state
+            .risk_engine
+            .ledger()
+            .deposit(&Exchange::Binance, "USDT", "1000".parse().unwrap());
+        let engine = TradingEngine::new(state.clone()).with_executor(Arc::new(SilentExecutor));
+        engine.route_order(StrategyOrder::new(
+            "arb",
+            Exchange::Binance,
+            limit_buy("1", "100"),
+        ));
+
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+        tokio::time::timeout(ACK_DRAIN_TIMEOUT * 2, engine.run(shutdown))
+            .await
+            .expect("engine stop waited past the ack timeout")
+            .unwrap();
+
+        // Still unanswered, so it is reported as left working
+        assert_eq!(state.orders.by_strategy("arb")[0].state, OrderState::Sent);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_finished_orders_pruned() {
+        let state = Arc::new(AppState::new(
+            RiskEngine::new(100.0),
+            InstrumentRegistry::new(),
+        ));
+        let engine =
+            TradingEngine::new(state.clone()).with_terminal_order_retention(Duration::ZERO);
+        engine.route_order(StrategyOrder::new(
+            "arb",
+            Exchange::Kraken,
+            limit_buy("1", "100"),
+        ));
+        assert_eq!(state.orders.by_strategy("arb").len(), 1);
+
+        let shutdown = CancellationToken::new();
+        let run = tokio::spawn(engine.run(shutdown.clone()));
+        tokio::time::sleep(PRUNE_INTERVAL + Duration::from_secs(1)).await;
+        assert!(state.orders.by_strategy("arb").is_empty());
+
+        shutdown.cancel();
+        run.await.unwrap().unwrap();
     }
 }
@@ -1,4 +1,6 @@
 // Application layer - orchestration and state management
 
 pub mod engine;
+pub mod oms;
 pub mod state;
+pub mod strategy_runtime;
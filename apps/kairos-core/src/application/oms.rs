@@ -0,0 +1,539 @@
+// Order management system - owns every order from strategy intent to terminal state
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use kairos_domain::{
+    CheckedDiv, DomainError, DomainResult, Exchange, Fill, InternalOrder, Notional, OrderSide,
+    OrderState, OrderStatus, OrderType, OrderUpdate, Price, Quantity,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Order tracked by the OMS
+#[derive(Debug, Clone)]
+pub struct ManagedOrder {
+    /// Our ID, sent to the venue as the client order ID
+    pub client_order_id: String,
+    /// Venue ID, known once the venue acknowledges the order
+    pub exchange_order_id: Option<String>,
+    /// Strategy that created the order
+    pub strategy: String,
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: Quantity,
+    pub price: Option<Price>,
+    pub state: OrderState,
+    pub filled_quantity: Quantity,
+    pub average_price: Option<Price>,
+    /// Why the order was rejected, when it was
+    pub reject_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Quantity and notional summed from individual fills
+    fill_quantity: Quantity,
+    fill_notional: Notional,
+    /// Trade IDs already applied, so replayed fills are not double counted
+    trade_ids: HashSet<String>,
+}
+
+impl ManagedOrder {
+    pub fn is_open(&self) -> bool {
+        !self.state.is_terminal()
+    }
+
+    pub fn remaining_quantity(&self) -> Quantity {
+        if self.filled_quantity >= self.quantity {
+            Quantity::ZERO
+        } else {
+            self.quantity - self.filled_quantity
+        }
+    }
+
+    fn is_fully_filled(&self) -> bool {
+        self.filled_quantity >= self.quantity
+    }
+}
+
+/// Order book of record for the engine
+///
+/// Every order is keyed by its client order ID; the exchange order ID is
+/// mapped back once the venue acknowledges it so account events can be
+/// matched by either. State changes go through [`OrderState::can_transition_to`].
+///
+/// # Example
+/// ```rust,ignore
+/// let oms = OrderManager::new();
+/// let order = oms.create("arbitrage", Exchange::Binance, &intent);
+/// oms.mark_sent(&order.client_order_id)?;
+/// oms.acknowledge(&order.client_order_id, &ack.exchange_order_id)?;
+/// oms.apply_fill(&fill)?;
+/// ```
+#[derive(Default)]
+pub struct OrderManager {
+    orders: DashMap<String, ManagedOrder>,
+    /// (venue, exchange order ID) -> client order ID
+    exchange_ids: DashMap<(Exchange, String), String>,
+}
+
+impl OrderManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a strategy's order intent in the `New` state
+    ///
+    /// Orders with a price become limit orders, the rest market orders.
+    pub fn create(
+        &self,
+        strategy: &str,
+        exchange: Exchange,
+        intent: &InternalOrder,
+    ) -> ManagedOrder {
+        self.create_with_id(&new_client_order_id(), strategy, exchange, intent)
+    }
+
+    /// Register an order under a client order ID chosen by the caller
+    pub fn create_with_id(
+        &self,
+        client_order_id: &str,
+        strategy: &str,
+        exchange: Exchange,
+        intent: &InternalOrder,
+    ) -> ManagedOrder {
+        let now = Utc::now();
+        let order = ManagedOrder {
+            client_order_id: client_order_id.to_string(),
+            exchange_order_id: None,
+            strategy: strategy.to_string(),
+            exchange,
+            symbol: intent.symbol.clone(),
+            side: intent.side.clone(),
+            order_type: if intent.price.is_some() {
+                OrderType::Limit
+            } else {
+                OrderType::Market
+            },
+            quantity: intent.quantity,
+            price: intent.price,
+            state: OrderState::New,
+            filled_quantity: Quantity::ZERO,
+            average_price: None,
+            reject_reason: None,
+            created_at: now,
+            updated_at: now,
+            fill_quantity: Quantity::ZERO,
+            fill_notional: Notional::ZERO,
+            trade_ids: HashSet::new(),
+        };
+
+        self.orders
+            .insert(order.client_order_id.clone(), order.clone());
+        order
+    }
+
+    /// The order was handed to the executor
+    pub fn mark_sent(&self, client_order_id: &str) -> DomainResult<ManagedOrder> {
+        self.update(client_order_id, |order| transition(order, OrderState::Sent))
+    }
+
+    /// The venue accepted the order under `exchange_order_id`
+    pub fn acknowledge(
+        &self,
+        client_order_id: &str,
+        exchange_order_id: &str,
+    ) -> DomainResult<ManagedOrder> {
+        let order = self.update(client_order_id, |order| {
+            order.exchange_order_id = Some(exchange_order_id.to_string());
+            // A fill may have beaten the acknowledgement
+            if order.state == OrderState::Sent {
+                transition(order, OrderState::Acknowledged)?;
+            }
+            Ok(())
+        })?;
+
+        self.exchange_ids.insert(
+            (order.exchange.clone(), exchange_order_id.to_string()),
+            client_order_id.to_string(),
+        );
+        Ok(order)
+    }
+
+    /// The order was refused by risk checks or by the venue
+    pub fn reject(
+        &self,
+        client_order_id: &str,
+        reason: impl Into<String>,
+    ) -> DomainResult<ManagedOrder> {
+        let reason = reason.into();
+        self.update(client_order_id, |order| {
+            transition(order, OrderState::Rejected)?;
+            order.reject_reason = Some(reason);
+            Ok(())
+        })
+    }
+
+    /// A cancel was sent for the order
+    pub fn request_cancel(&self, client_order_id: &str) -> DomainResult<ManagedOrder> {
+        self.update(client_order_id, |order| {
+            transition(order, OrderState::CancelPending)
+        })
+    }
+
+    /// The venue refused the cancel; the order keeps working
+    pub fn cancel_rejected(&self, client_order_id: &str) -> DomainResult<ManagedOrder> {
+        self.update(client_order_id, |order| {
+            if order.state != OrderState::CancelPending {
+                return Ok(());
+            }
+            let working = if order.filled_quantity.is_positive() {
+                OrderState::PartiallyFilled
+            } else {
+                OrderState::Acknowledged
+            };
+            transition(order, working)
+        })
+    }
+
+    /// Force a state change (e.g. `Expired` for a time-in-force expiry)
+    pub fn transition(
+        &self,
+        client_order_id: &str,
+        next: OrderState,
+    ) -> DomainResult<ManagedOrder> {
+        self.update(client_order_id, |order| transition(order, next))
+    }
+
+    /// Apply an execution, matched by client or exchange order ID
+    ///
+    /// Fills already applied (same trade ID) are ignored.
+    pub fn apply_fill(&self, fill: &Fill) -> DomainResult<ManagedOrder> {
+        let client_order_id = self.resolve_id(
+            &fill.exchange,
+            fill.client_order_id.as_deref(),
+            &fill.exchange_order_id,
+        )?;
+
+        self.update(&client_order_id, |order| {
+            if !order.trade_ids.insert(fill.trade_id.clone()) {
+                return Ok(());
+            }
+            order.fill_quantity += fill.quantity;
+            order.fill_notional += fill.price * fill.quantity;
+            if order.fill_quantity.is_positive() && order.fill_quantity >= order.filled_quantity {
+                order.filled_quantity = order.fill_quantity;
+                order.average_price = order.fill_notional.checked_div(order.fill_quantity);
+            }
+            advance_fill_state(order)
+        })
+    }
+
+    /// Apply a venue order update, matched by client or exchange order ID
+    ///
+    /// The venue's cumulative filled quantity is taken when it is ahead of
+    /// the fills seen so far.
+    pub fn apply_update(&self, update: &OrderUpdate) -> DomainResult<ManagedOrder> {
+        let client_order_id = self.resolve_id(
+            &update.exchange,
+            update.client_order_id.as_deref(),
+            &update.exchange_order_id,
+        )?;
+
+        let order = self.update(&client_order_id, |order| {
+            if order.exchange_order_id.is_none() {
+                order.exchange_order_id = Some(update.exchange_order_id.clone());
+            }
+            if update.filled_quantity > order.filled_quantity {
+                order.filled_quantity = update.filled_quantity;
+                order.average_price = update.average_price.or(order.average_price);
+            }
+
+            match update.status {
+                OrderStatus::Pending => Ok(()),
+                OrderStatus::Approved if order.state == OrderState::Sent => {
+                    transition(order, OrderState::Acknowledged)?;
+                    advance_fill_state(order)
+                }
+                OrderStatus::Approved => advance_fill_state(order),
+                OrderStatus::Executed => transition(order, OrderState::Filled),
+                OrderStatus::Cancelled => transition(order, OrderState::Cancelled),
+                OrderStatus::Expired => transition(order, OrderState::Expired),
+                OrderStatus::Rejected => transition(order, OrderState::Rejected),
+            }
+        })?;
+
+        self.exchange_ids.insert(
+            (update.exchange.clone(), update.exchange_order_id.clone()),
+            client_order_id,
+        );
+        Ok(order)
+    }
+
+    pub fn get(&self, client_order_id: &str) -> Option<ManagedOrder> {
+        self.orders.get(client_order_id).map(|order| order.clone())
+    }
+
+    pub fn get_by_exchange_id(
+        &self,
+        exchange: &Exchange,
+        exchange_order_id: &str,
+    ) -> Option<ManagedOrder> {
+        self.exchange_ids
+            .get(&(exchange.clone(), exchange_order_id.to_string()))
+            .and_then(|client_order_id| self.get(&client_order_id))
+    }
+
+    pub fn by_symbol(&self, symbol: &str) -> Vec<ManagedOrder> {
+        self.filter(|order| order.symbol == symbol)
+    }
+
+    pub fn by_strategy(&self, strategy: &str) -> Vec<ManagedOrder> {
+        self.filter(|order| order.strategy == strategy)
+    }
+
+    /// Orders not yet in a terminal state
+    pub fn open_orders(&self) -> Vec<ManagedOrder> {
+        self.filter(ManagedOrder::is_open)
+    }
+
+    /// Drop terminal orders last updated before `cutoff`, returning how many were removed
+    pub fn prune_terminal(&self, cutoff: DateTime<Utc>) -> usize {
+        let before = self.orders.len();
+        self.orders
+            .retain(|_, order| order.is_open() || order.updated_at >= cutoff);
+        self.exchange_ids
+            .retain(|_, client_order_id| self.orders.contains_key(client_order_id.as_str()));
+        before - self.orders.len()
+    }
+
+    fn filter(&self, predicate: impl Fn(&ManagedOrder) -> bool) -> Vec<ManagedOrder> {
+        let mut orders: Vec<ManagedOrder> = self
+            .orders
+            .iter()
+            .filter(|order| predicate(order))
+            .map(|order| order.clone())
+            .collect();
+        orders.sort_by_key(|order| order.created_at);
+        orders
+    }
+
+    fn resolve_id(
+        &self,
+        exchange: &Exchange,
+        client_order_id: Option<&str>,
+        exchange_order_id: &str,
+    ) -> DomainResult<String> {
+        client_order_id
+            .filter(|id| self.orders.contains_key(*id))
+            .map(str::to_string)
+            .or_else(|| {
+                self.exchange_ids
+                    .get(&(exchange.clone(), exchange_order_id.to_string()))
+                    .map(|id| id.clone())
+            })
+            .ok_or_else(|| DomainError::OrderNotFound(exchange_order_id.to_string()))
+    }
+
+    fn update(
+        &self,
+        client_order_id: &str,
+        apply: impl FnOnce(&mut ManagedOrder) -> DomainResult<()>,
+    ) -> DomainResult<ManagedOrder> {
+        let mut entry = self
+            .orders
+            .get_mut(client_order_id)
+            .ok_or_else(|| DomainError::OrderNotFound(client_order_id.to_string()))?;
+
+        // Changes are applied to a copy so a rejected transition leaves the order untouched
+        let mut order = entry.clone();
+        apply(&mut order)?;
+        order.updated_at = Utc::now();
+        *entry = order.clone();
+        Ok(order)
+    }
+}
+
+/// Fresh client order ID (a UUID without dashes, accepted by every venue)
+pub fn new_client_order_id() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Move an order to `next`, enforcing the lifecycle
+///
+/// Repeating the current state is a no-op so duplicate venue events are harmless.
+fn transition(order: &mut ManagedOrder, next: OrderState) -> DomainResult<()> {
+    if order.state == next {
+        return Ok(());
+    }
+    if !order.state.can_transition_to(next) {
+        return Err(DomainError::InvalidStateTransition {
+            order_id: order.client_order_id.clone(),
+            from: order.state,
+            to: next,
+        });
+    }
+
+    tracing::debug!(
+        "📋 Order {} ({} {:?} {}) {:?} -> {:?}",
+        order.client_order_id,
+        order.strategy,
+        order.side,
+        order.symbol,
+        order.state,
+        next
+    );
+    order.state = next;
+    Ok(())
+}
+
+/// Derive the fill state from the filled quantity
+///
+/// A pending cancel is kept until the venue confirms it or the order fills.
+fn advance_fill_state(order: &mut ManagedOrder) -> DomainResult<()> {
+    if order.is_fully_filled() {
+        transition(order, OrderState::Filled)
+    } else if order.filled_quantity.is_positive() && order.state != OrderState::CancelPending {
+        transition(order, OrderState::PartiallyFilled)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(quantity: f64) -> InternalOrder {
+        InternalOrder {
+            symbol: "BTC/USDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: Quantity::try_from(quantity).unwrap(),
+            price: Some("100".parse().unwrap()),
+            risk_score: 0.0,
+        }
+    }
+
+    fn fill(order: &ManagedOrder, trade_id: &str, quantity: &str, price: &str) -> Fill {
+        Fill {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "42".to_string(),
+            client_order_id: None,
+            trade_id: trade_id.to_string(),
+            side: order.side.clone(),
+            price: price.parse().unwrap(),
+            quantity: quantity.parse().unwrap(),
+            fee: Default::default(),
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_state_transitions() {
+        use OrderState::*;
+
+        assert!(New.can_transition_to(Sent));
+        assert!(Sent.can_transition_to(Filled));
+        assert!(CancelPending.can_transition_to(PartiallyFilled));
+        assert!(!New.can_transition_to(Filled));
+        assert!(!Acknowledged.can_transition_to(Sent));
+        for terminal in [Filled, Cancelled, Rejected, Expired] {
+            assert!(terminal.is_terminal());
+            assert!(!terminal.can_transition_to(Acknowledged));
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_with_fills_and_id_mapping() {
+        let oms = OrderManager::new();
+        let order = oms.create("arb", Exchange::Binance, &intent(2.0));
+        assert_eq!(order.state, OrderState::New);
+        assert_eq!(order.order_type, OrderType::Limit);
+
+        oms.mark_sent(&order.client_order_id).unwrap();
+        oms.acknowledge(&order.client_order_id, "42").unwrap();
+        assert_eq!(
+            oms.get_by_exchange_id(&Exchange::Binance, "42")
+                .unwrap()
+                .client_order_id,
+            order.client_order_id
+        );
+
+        // Matched by exchange ID; replayed trade IDs are ignored
+        let partial = oms.apply_fill(&fill(&order, "t1", "0.5", "100")).unwrap();
+        assert_eq!(partial.state, OrderState::PartiallyFilled);
+        let replay = oms.apply_fill(&fill(&order, "t1", "0.5", "100")).unwrap();
+        assert_eq!(replay.filled_quantity, "0.5".parse().unwrap());
+
+        let filled = oms.apply_fill(&fill(&order, "t2", "1.5", "104")).unwrap();
+        assert_eq!(filled.state, OrderState::Filled);
+        assert_eq!(filled.average_price, Some("103".parse().unwrap()));
+        assert!(oms.open_orders().is_empty());
+
+        // Terminal orders cannot change and the failed transition is not applied
+        assert!(matches!(
+            oms.request_cancel(&order.client_order_id),
+            Err(DomainError::InvalidStateTransition { .. })
+        ));
+        assert_eq!(
+            oms.get(&order.client_order_id).unwrap().state,
+            OrderState::Filled
+        );
+    }
+
+    #[test]
+    fn test_cancel_flow_and_queries() {
+        let oms = OrderManager::new();
+        let order = oms.create("mm", Exchange::OKX, &intent(1.0));
+        let other = oms.create("arb", Exchange::OKX, &intent(1.0));
+        oms.mark_sent(&order.client_order_id).unwrap();
+
+        let update = |status| OrderUpdate {
+            exchange: Exchange::OKX,
+            symbol: "BTC-USDT".to_string(),
+            exchange_order_id: "7".to_string(),
+            client_order_id: Some(order.client_order_id.clone()),
+            side: OrderSide::Buy,
+            status,
+            filled_quantity: Quantity::ZERO,
+            average_price: None,
+            timestamp: Utc::now(),
+        };
+        let acked = oms.apply_update(&update(OrderStatus::Approved)).unwrap();
+        assert_eq!(acked.state, OrderState::Acknowledged);
+        assert_eq!(acked.exchange_order_id.as_deref(), Some("7"));
+
+        oms.request_cancel(&order.client_order_id).unwrap();
+        let restored = oms.cancel_rejected(&order.client_order_id).unwrap();
+        assert_eq!(restored.state, OrderState::Acknowledged);
+
+        oms.request_cancel(&order.client_order_id).unwrap();
+        let cancelled = oms.apply_update(&update(OrderStatus::Cancelled)).unwrap();
+        assert_eq!(cancelled.state, OrderState::Cancelled);
+
+        assert_eq!(oms.by_strategy("mm").len(), 1);
+        assert_eq!(oms.by_symbol("BTC/USDT").len(), 2);
+        assert_eq!(oms.open_orders()[0].client_order_id, other.client_order_id);
+        assert_eq!(
+            oms.prune_terminal(Utc::now() + chrono::Duration::seconds(1)),
+            1
+        );
+        assert!(oms.get_by_exchange_id(&Exchange::OKX, "7").is_none());
+
+        // A venue expiry is not a cancel
+        oms.mark_sent(&other.client_order_id).unwrap();
+        let expired = oms
+            .apply_update(&OrderUpdate {
+                exchange_order_id: "8".to_string(),
+                client_order_id: Some(other.client_order_id.clone()),
+                status: OrderStatus::Expired,
+                ..update(OrderStatus::Approved)
+            })
+            .unwrap();
+        assert_eq!(expired.state, OrderState::Expired);
+        assert!(oms.open_orders().is_empty());
+    }
+}
@@ -1,20 +1,108 @@
 // Global application state
 
+use super::oms::OrderManager;
 use crate::domain::risk::RiskEngine;
+use kairos_domain::{DomainResult, Exchange, InstrumentRegistry, InternalOrder, Symbol};
 use std::sync::Arc;
 
 /// Shared application state
 pub struct AppState {
     pub risk_engine: Arc<RiskEngine>,
+    pub instruments: Arc<InstrumentRegistry>,
+    pub orders: Arc<OrderManager>,
+    /// Accept orders for symbols missing from the registry, unnormalized
+    allow_unlisted_symbols: bool,
     // Add more shared state as needed
     // pub order_book: Arc<OrderBook>,
     // pub market_data: Arc<MarketDataStore>,
 }
 
 impl AppState {
-    pub fn new(initial_balance: f64, max_daily_risk: f64) -> Self {
+    pub fn new(risk_engine: RiskEngine, instruments: InstrumentRegistry) -> Self {
         Self {
-            risk_engine: Arc::new(RiskEngine::new(initial_balance, max_daily_risk)),
+            risk_engine: Arc::new(risk_engine),
+            instruments: Arc::new(instruments),
+            orders: Arc::new(OrderManager::new()),
+            allow_unlisted_symbols: false,
         }
     }
+
+    /// Let orders for symbols without instrument metadata through without
+    /// tick/lot normalization (paper trading only; live venues reject them)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let state = AppState::new(risk_engine, instruments)
+    ///     .with_unlisted_symbols(settings.features.enable_paper_trading);
+    /// ```
+    pub fn with_unlisted_symbols(mut self, allowed: bool) -> Self {
+        self.allow_unlisted_symbols = allowed;
+        self
+    }
+
+    /// Round an order onto the venue's tick/lot grid, check exchange minimums,
+    /// then run the risk checks
+    ///
+    /// Returns the normalized order, with its canonical `BASE/QUOTE` symbol,
+    /// that should be sent to the exchange.
+    pub fn validate_order(
+        &self,
+        exchange: &Exchange,
+        order: &InternalOrder,
+    ) -> DomainResult<InternalOrder> {
+        let normalized = match self.instruments.resolve(exchange, &order.symbol) {
+            Ok(instrument) => InternalOrder {
+                symbol: instrument.symbol.to_string(),
+                ..instrument.normalize_order(order)?
+            },
+            Err(e) if self.allow_unlisted_symbols => {
+                let symbol = order
+                    .symbol
+                    .parse::<Symbol>()
+                    .or_else(|_| Symbol::from_exchange(exchange, &order.symbol))
+                    .map_err(|_| e)?;
+                InternalOrder {
+                    symbol: symbol.to_string(),
+                    ..order.clone()
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        self.risk_engine.validate_order(exchange, &normalized)?;
+        Ok(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kairos_domain::{DomainError, OrderSide};
+
+    #[test]
+    fn test_unlisted_symbols_only_in_paper_mode() {
+        let risk = || {
+            let risk = RiskEngine::new(100.0);
+            risk.ledger()
+                .deposit(&Exchange::Binance, "USDT", "1000".parse().unwrap());
+            risk
+        };
+        let order = InternalOrder {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: "0.5".parse().unwrap(),
+            price: Some("100".parse().unwrap()),
+            risk_score: 1.0,
+        };
+
+        let live = AppState::new(risk(), InstrumentRegistry::new());
+        assert!(matches!(
+            live.validate_order(&Exchange::Binance, &order),
+            Err(DomainError::SymbolNotFound { .. })
+        ));
+
+        let paper = AppState::new(risk(), InstrumentRegistry::new()).with_unlisted_symbols(true);
+        let accepted = paper.validate_order(&Exchange::Binance, &order).unwrap();
+        assert_eq!(accepted.symbol, "BTC/USDT");
+        assert_eq!(accepted.quantity, order.quantity);
+    }
 }
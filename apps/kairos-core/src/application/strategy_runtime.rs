@@ -0,0 +1,489 @@
+// Strategy runtime - runs every strategy on its own task, isolated from the core
+
+use super::engine::{CancelRequest, FilteredMarketEvents, StrategyOrder, TradingEngine};
+use super::state::AppState;
+use crate::config::{ConfigError, ConfigResult, Settings};
+use crate::domain::strategies::{
+    ArbitrageStrategy, Strategy, StrategyContext, TriangulationStrategy,
+};
+use kairos_domain::{
+    AccountEvent, Amount, DomainError, DomainResult, Exchange, Fill, Instrument, InternalOrder,
+    MarketEvent, Notional, Position, Quantity, Symbol,
+};
+use rust_decimal::Decimal;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, Interval};
+use tokio_util::sync::CancellationToken;
+
+/// Runs strategies against the engine's market and account buses
+///
+/// Each strategy gets its own task, a filtered market data subscription,
+/// fills for its own orders, an optional timer and a [`StrategyContext`]
+/// that queues orders and cancels to the engine. A strategy whose callback
+/// panics is stopped and its open orders are cancelled; other strategies
+/// and the engine keep running.
+///
+/// # Example
+/// ```rust,ignore
+/// let tasks = StrategyRuntime::new(&engine)
+///     .with_strategy(Box::new(ArbitrageStrategy::new(Decimal::new(1, 3))))
+///     .spawn(shutdown.clone());
+/// ```
+pub struct StrategyRuntime {
+    state: Arc<AppState>,
+    market_data_tx: broadcast::Sender<MarketEvent>,
+    account_event_tx: broadcast::Sender<AccountEvent>,
+    order_tx: mpsc::Sender<StrategyOrder>,
+    cancel_tx: mpsc::Sender<CancelRequest>,
+    strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl StrategyRuntime {
+    pub fn new(engine: &TradingEngine) -> Self {
+        Self {
+            state: engine.state(),
+            market_data_tx: engine.market_data_sender(),
+            account_event_tx: engine.account_event_sender(),
+            order_tx: engine.get_order_sender(),
+            cancel_tx: engine.get_cancel_sender(),
+            strategies: Vec::new(),
+        }
+    }
+
+    /// Add a strategy (skipped if one with the same name is registered)
+    pub fn with_strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
+        if self.names().iter().any(|name| name == strategy.name()) {
+            tracing::warn!("⚠️  Strategy '{}' already registered", strategy.name());
+            return self;
+        }
+        self.strategies.push(strategy);
+        self
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.strategies
+            .iter()
+            .map(|strategy| strategy.name().to_string())
+            .collect()
+    }
+
+    /// Start every strategy; tasks end once `shutdown` is cancelled
+    pub fn spawn(self, shutdown: CancellationToken) -> Vec<JoinHandle<()>> {
+        self.strategies
+            .into_iter()
+            .map(|strategy| {
+                let ctx = RuntimeContext {
+                    strategy: strategy.name().to_string(),
+                    state: self.state.clone(),
+                    order_tx: self.order_tx.clone(),
+                    cancel_tx: self.cancel_tx.clone(),
+                };
+                let market = FilteredMarketEvents::new(
+                    self.market_data_tx.subscribe(),
+                    strategy.event_filter(),
+                );
+                let account_rx = self.account_event_tx.subscribe();
+                tokio::spawn(run_strategy(
+                    strategy,
+                    ctx,
+                    market,
+                    account_rx,
+                    shutdown.clone(),
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Build a strategy from its name in `strategies.enabled`
+pub fn build_strategy(name: &str, settings: &Settings) -> ConfigResult<Box<dyn Strategy>> {
+    match name {
+        "arbitrage" => {
+            let config = &settings.strategies;
+            Ok(Box::new(
+                ArbitrageStrategy::new(decimal_setting(
+                    "min_profit_threshold",
+                    config.min_profit_threshold,
+                )?)
+                .with_costs(
+                    decimal_setting("taker_fee_bps", config.taker_fee_bps)?,
+                    decimal_setting("slippage_bps", config.slippage_bps)?,
+                )
+                .with_max_order_notional(Notional::new(decimal_setting(
+                    "max_order_notional",
+                    config.max_order_notional,
+                )?))
+                .with_max_quote_age(Duration::from_millis(config.max_quote_age_ms))
+                .with_leg_timeout(Duration::from_millis(config.leg_timeout_ms)),
+            ))
+        }
+        "triangulation" => {
+            let config = &settings.strategies;
+            Ok(Box::new(
+                TriangulationStrategy::new()
+                    .with_min_profit(config.min_profit_threshold)
+                    .with_taker_fee_bps(config.taker_fee_bps)
+                    .with_max_order_notional(config.max_order_notional)
+                    .with_valuation_currency(&config.valuation_currency)
+                    .with_max_cycle_length(config.max_cycle_length)
+                    .with_leg_timeout(Duration::from_millis(config.leg_timeout_ms)),
+            ))
+        }
+        other => Err(ConfigError::InvalidValue {
+            field: "strategies.enabled".to_string(),
+            reason: format!("unknown strategy '{}'", other),
+        }),
+    }
+}
+
+/// A `strategies` value as an exact decimal
+fn decimal_setting(field: &str, value: f64) -> ConfigResult<Decimal> {
+    value
+        .to_string()
+        .parse()
+        .map_err(|_| ConfigError::InvalidValue {
+            field: format!("strategies.{}", field),
+            reason: format!("{} is not a finite number", value),
+        })
+}
+
+/// Drive one strategy until shutdown, a closed bus or a panic
+async fn run_strategy(
+    mut strategy: Box<dyn Strategy>,
+    ctx: RuntimeContext,
+    mut market: FilteredMarketEvents,
+    mut account_rx: broadcast::Receiver<AccountEvent>,
+    shutdown: CancellationToken,
+) {
+    let name = ctx.strategy.clone();
+    let mut timer = strategy
+        .timer_interval()
+        .map(|period| tokio::time::interval_at(Instant::now() + period, period));
+
+    tracing::info!("🧠 Strategy '{}' started", name);
+    if guarded(&name, "on_start", || strategy.on_start(&ctx)).is_err() {
+        ctx.abandon();
+        return;
+    }
+
+    loop {
+        let outcome = tokio::select! {
+            _ = shutdown.cancelled() => {
+                let _ = guarded(&name, "on_stop", || strategy.on_stop(&ctx));
+                break;
+            }
+            event = market.recv() => match event {
+                Ok(event) => guarded(&name, "on_market_event", || {
+                    strategy.on_market_event(&event, &ctx)
+                }),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("⚠️  Strategy '{}' lagged, skipped {} market events", name, skipped);
+                    Ok(())
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            event = account_rx.recv() => match event {
+                Ok(AccountEvent::Fill(fill)) if ctx.owns(&fill) => {
+                    guarded(&name, "on_fill", || strategy.on_fill(&fill, &ctx))
+                }
+                Ok(_) => Ok(()),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("⚠️  Strategy '{}' lagged, skipped {} account events", name, skipped);
+                    Ok(())
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            _ = next_tick(&mut timer) => guarded(&name, "on_timer", || strategy.on_timer(&ctx)),
+        };
+
+        if outcome.is_err() {
+            ctx.abandon();
+            return;
+        }
+    }
+
+    tracing::info!("🛑 Strategy '{}' stopped", name);
+}
+
+/// Run a callback, turning a panic into an error instead of unwinding the task
+fn guarded(strategy: &str, callback: &str, f: impl FnOnce()) -> Result<(), ()> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        tracing::error!(
+            "💥 Strategy '{}' panicked in {}: {}",
+            strategy,
+            callback,
+            message
+        );
+    })
+}
+
+/// Wait for the next timer tick, or forever when the strategy has no timer
+async fn next_tick(timer: &mut Option<Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// [`StrategyContext`] backed by the engine's channels and shared state
+struct RuntimeContext {
+    strategy: String,
+    state: Arc<AppState>,
+    order_tx: mpsc::Sender<StrategyOrder>,
+    cancel_tx: mpsc::Sender<CancelRequest>,
+}
+
+impl RuntimeContext {
+    /// Whether a fill belongs to one of this strategy's orders
+    fn owns(&self, fill: &Fill) -> bool {
+        let orders = &self.state.orders;
+        fill.client_order_id
+            .as_ref()
+            .and_then(|id| orders.get(id))
+            .or_else(|| orders.get_by_exchange_id(&fill.exchange, &fill.exchange_order_id))
+            .is_some_and(|order| order.strategy == self.strategy)
+    }
+
+    /// Stop a failed strategy: cancel what it left working
+    fn abandon(&self) {
+        tracing::error!(
+            "🛑 Strategy '{}' disabled, cancelling its open orders",
+            self.strategy
+        );
+        if let Err(e) = self.cancel_all() {
+            tracing::error!(
+                "❌ Strategy '{}' orders not cancelled: {}",
+                self.strategy,
+                e
+            );
+        }
+    }
+}
+
+impl StrategyContext for RuntimeContext {
+    fn submit(&self, exchange: Exchange, order: InternalOrder) -> DomainResult<String> {
+        let intent = StrategyOrder::new(self.strategy.clone(), exchange, order);
+        let client_order_id = intent.client_order_id.clone();
+        self.order_tx.try_send(intent).map_err(|e| {
+            DomainError::ExchangeError(format!("engine not accepting orders: {}", e))
+        })?;
+        Ok(client_order_id)
+    }
+
+    fn cancel(&self, client_order_id: &str) -> DomainResult<()> {
+        self.cancel_tx
+            .try_send(CancelRequest {
+                strategy: self.strategy.clone(),
+                client_order_id: client_order_id.to_string(),
+            })
+            .map_err(|e| DomainError::ExchangeError(format!("engine not accepting cancels: {}", e)))
+    }
+
+    fn cancel_all(&self) -> DomainResult<()> {
+        // Try every order, reporting the first failure
+        let results: Vec<_> = self
+            .open_orders()
+            .iter()
+            .map(|id| self.cancel(id))
+            .collect();
+        results.into_iter().collect()
+    }
+
+    fn open_orders(&self) -> Vec<String> {
+        self.state
+            .orders
+            .by_strategy(&self.strategy)
+            .into_iter()
+            .filter(|order| order.is_open())
+            .map(|order| order.client_order_id)
+            .collect()
+    }
+
+    fn free_balance(&self, exchange: &Exchange, currency: &str) -> Amount {
+        self.state
+            .risk_engine
+            .ledger()
+            .balance(exchange, currency)
+            .free
+    }
+
+    fn instrument(&self, exchange: &Exchange, symbol: &Symbol) -> Option<Instrument> {
+        self.state.instruments.get(exchange, symbol).cloned()
+    }
+
+    fn position(&self, exchange: &Exchange, symbol: &Symbol) -> Option<Position> {
+        self.state.risk_engine.positions().get(exchange, symbol)
+    }
+
+    fn net_position(&self, symbol: &Symbol) -> Quantity {
+        self.state.risk_engine.positions().net_quantity(symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::risk::RiskEngine;
+    use chrono::Utc;
+    use kairos_domain::{InstrumentRegistry, MarketTick, OrderSide, OrderState};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    struct Panicker;
+
+    impl Strategy for Panicker {
+        fn name(&self) -> &str {
+            "panicker"
+        }
+
+        fn on_market_event(&mut self, _event: &MarketEvent, _ctx: &dyn StrategyContext) {
+            panic!("bad strategy");
+        }
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        events: Arc<AtomicUsize>,
+        stopped: Arc<AtomicBool>,
+    }
+
+    impl Strategy for Counter {
+        fn name(&self) -> &str {
+            "counter"
+        }
+
+        fn on_market_event(&mut self, _event: &MarketEvent, _ctx: &dyn StrategyContext) {
+            self.events.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_stop(&mut self, _ctx: &dyn StrategyContext) {
+            self.stopped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    struct Submitter {
+        order_id: Arc<Mutex<Option<String>>>,
+    }
+
+    impl Strategy for Submitter {
+        fn name(&self) -> &str {
+            "submitter"
+        }
+
+        fn on_start(&mut self, ctx: &dyn StrategyContext) {
+            let order = InternalOrder {
+                symbol: "BTC/USDT".to_string(),
+                side: OrderSide::Buy,
+                quantity: "1".parse().unwrap(),
+                price: Some("100".parse().unwrap()),
+                risk_score: 1.0,
+            };
+            *self.order_id.lock().unwrap() = ctx.submit(Exchange::Kraken, order).ok();
+        }
+
+        fn on_market_event(&mut self, _event: &MarketEvent, _ctx: &dyn StrategyContext) {}
+    }
+
+    fn engine() -> TradingEngine {
+        TradingEngine::new(Arc::new(AppState::new(
+            RiskEngine::new(100.0),
+            InstrumentRegistry::new(),
+        )))
+    }
+
+    fn trade() -> MarketEvent {
+        MarketEvent::Trade(MarketTick {
+            id: Uuid::new_v4(),
+            symbol: "BTCUSDT".to_string(),
+            price: "100".parse().unwrap(),
+            volume: "1".parse().unwrap(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            exchange: Exchange::Binance,
+            trade_id: None,
+            side: None,
+        })
+    }
+
+    async fn eventually(condition: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[tokio::test]
+    async fn test_panicking_strategy_is_isolated() {
+        let engine = engine();
+        let counter = Counter::default();
+        let (events, stopped) = (counter.events.clone(), counter.stopped.clone());
+        let shutdown = CancellationToken::new();
+        let mut tasks = StrategyRuntime::new(&engine)
+            .with_strategy(Box::new(Panicker))
+            .with_strategy(Box::new(counter))
+            .spawn(shutdown.clone());
+
+        let market = engine.market_data_sender();
+        for _ in 0..3 {
+            market.send(trade()).unwrap();
+        }
+
+        // The panic ends only the panicking strategy's task, without unwinding it
+        let counter_task = tasks.pop().unwrap();
+        let panicker_task = tasks.pop().unwrap();
+        tokio::time::timeout(Duration::from_secs(1), panicker_task)
+            .await
+            .unwrap()
+            .unwrap();
+        eventually(|| events.load(Ordering::SeqCst) == 3).await;
+
+        shutdown.cancel();
+        counter_task.await.unwrap();
+        assert!(stopped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_context_submits_to_engine() {
+        let engine = engine();
+        let state = engine.state();
+        let order_id = Arc::new(Mutex::new(None));
+        let shutdown = CancellationToken::new();
+        let tasks = StrategyRuntime::new(&engine)
+            .with_strategy(Box::new(Submitter {
+                order_id: order_id.clone(),
+            }))
+            .spawn(shutdown.clone());
+        let run = tokio::spawn(engine.run(shutdown.clone()));
+
+        // No Kraken executor: the engine records the order and rejects it
+        eventually(|| !state.orders.by_strategy("submitter").is_empty()).await;
+        let order = &state.orders.by_strategy("submitter")[0];
+        assert_eq!(
+            Some(&order.client_order_id),
+            order_id.lock().unwrap().as_ref()
+        );
+        assert_eq!(order.state, OrderState::Rejected);
+
+        shutdown.cancel();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        run.await.unwrap().unwrap();
+    }
+}
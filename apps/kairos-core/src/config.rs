@@ -1,5 +1,7 @@
 use config::{Config, Environment as ConfigEnvironment, File};
+use kairos_domain::{Exchange, Symbol};
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use thiserror::Error;
 
@@ -78,9 +80,13 @@ pub struct Settings {
     pub database: DatabaseSettings,
     pub exchange: ExchangeSettings,
     pub trading: TradingSettings,
+    pub markets: MarketsSettings,
     pub performance: PerformanceSettings,
     pub monitoring: MonitoringSettings,
     pub features: FeatureFlags,
+    pub paper: PaperTradingSettings,
+    pub shutdown: ShutdownSettings,
+    pub strategies: StrategySettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -110,6 +116,13 @@ pub struct DatabaseSettings {
     pub max_connections: u32,
     pub min_connections: u32,
     pub connect_timeout_sec: u64,
+    /// TimescaleDB connection string; persistence is disabled when unset
+    #[serde(default)]
+    pub timescale_url: Option<String>,
+    /// Rows buffered before a batch insert
+    pub write_batch_size: usize,
+    /// Maximum time a buffered row waits before being written
+    pub write_flush_interval_ms: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -119,9 +132,17 @@ pub struct ExchangeSettings {
     pub okx_ws_private_url: String,
     pub okx_ws_business_url: String,
     pub okx_rest_url: String,
+    pub okx_simulated_trading: bool,
+    pub binance_ws_url: String,
+    pub binance_rest_url: String,
+    pub binance_recv_window_ms: u64,
+    pub kraken_ws_url: String,
+    pub kraken_rest_url: String,
     pub ws_reconnect_delay_ms: u64,
+    pub ws_max_reconnect_delay_ms: u64,
     pub ws_max_reconnect_attempts: u32,
     pub ws_ping_interval_sec: u64,
+    pub ws_stale_timeout_sec: u64,
 
     // API Credentials (ONLY from environment variables, NEVER in TOML)
     // These are optional because they should only be set via .env or environment
@@ -136,6 +157,11 @@ pub struct ExchangeSettings {
     pub binance_api_key: Option<String>,
     #[serde(default)]
     pub binance_api_secret: Option<String>,
+
+    #[serde(default)]
+    pub kraken_api_key: Option<String>,
+    #[serde(default)]
+    pub kraken_api_secret: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -143,13 +169,89 @@ pub struct TradingSettings {
     pub order_timeout_sec: u64,
     pub max_pending_orders: u32,
     pub order_retry_attempts: u32,
+    /// Largest absolute net position per instrument (summed across venues),
+    /// in quote currency: `|quantity| * price`, not a base-asset quantity.
+    /// Orders that would grow a position past it are rejected; reducing
+    /// orders are always allowed
     pub max_position_size: f64,
+    /// Sum of order risk scores accepted per day
+    pub max_daily_risk: f64,
     pub max_leverage: f64,
     pub stop_loss_percentage: f64,
     pub take_profit_percentage: f64,
     pub tick_buffer_size: usize,
     pub orderbook_depth: u32,
     pub kline_intervals: String,
+    pub stream_book_ticker: bool,
+    pub stream_klines: bool,
+}
+
+/// Instruments traded per exchange, as canonical `BASE/QUOTE` symbols (e.g. "BTC/USDT")
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct MarketsSettings {
+    #[serde(default)]
+    pub binance: Vec<String>,
+    #[serde(default)]
+    pub okx: Vec<String>,
+    #[serde(default)]
+    pub kraken: Vec<String>,
+    /// JSON file caching instrument metadata for offline startup
+    #[serde(default)]
+    pub instrument_cache: String,
+}
+
+impl MarketsSettings {
+    /// Canonical symbols configured for an exchange
+    pub fn symbols(&self, exchange: &Exchange) -> ConfigResult<Vec<Symbol>> {
+        let (field, entries) = match exchange {
+            Exchange::Binance => ("markets.binance", &self.binance),
+            Exchange::OKX => ("markets.okx", &self.okx),
+            Exchange::Kraken => ("markets.kraken", &self.kraken),
+        };
+
+        let mut seen = HashSet::new();
+        entries
+            .iter()
+            .map(|entry| {
+                let symbol = entry
+                    .parse::<Symbol>()
+                    .map_err(|_| ConfigError::InvalidValue {
+                        field: field.to_string(),
+                        reason: format!("'{}' is not a BASE/QUOTE symbol", entry),
+                    })?;
+                if !seen.insert(symbol.clone()) {
+                    return Err(ConfigError::InvalidValue {
+                        field: field.to_string(),
+                        reason: format!("'{}' is listed more than once", symbol),
+                    });
+                }
+                Ok(symbol)
+            })
+            .collect()
+    }
+
+    /// Exchange-native symbols (e.g. "BTCUSDT" on Binance, "BTC-USDT" on OKX)
+    pub fn exchange_symbols(&self, exchange: &Exchange) -> ConfigResult<Vec<String>> {
+        Ok(self
+            .symbols(exchange)?
+            .iter()
+            .map(|symbol| symbol.to_exchange(exchange))
+            .collect())
+    }
+
+    /// Check every exchange list parses and at least one instrument is configured
+    pub fn validate(&self) -> ConfigResult<()> {
+        let mut total = 0;
+        for exchange in [Exchange::Binance, Exchange::OKX, Exchange::Kraken] {
+            total += self.symbols(&exchange)?.len();
+        }
+        if total == 0 {
+            return Err(ConfigError::MissingConfig(
+                "markets: no instruments configured for any exchange".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -175,6 +277,53 @@ pub struct FeatureFlags {
     pub enable_backtesting: bool,
 }
 
+/// Simulated execution used when `features.enable_paper_trading` is on
+#[derive(Debug, Deserialize, Clone)]
+pub struct PaperTradingSettings {
+    /// Price penalty applied to market orders, in basis points of the BBO
+    pub slippage_bps: f64,
+    pub maker_fee_bps: f64,
+    pub taker_fee_bps: f64,
+    /// Starting balance per currency on each simulated venue
+    #[serde(default)]
+    pub initial_balances: HashMap<String, f64>,
+}
+
+/// Strategies started by the strategy runtime
+#[derive(Debug, Deserialize, Clone)]
+pub struct StrategySettings {
+    /// Names of the strategies to run ("arbitrage", "triangulation")
+    #[serde(default)]
+    pub enabled: Vec<String>,
+    /// Minimum relative edge after fees (0.001 = 0.1%)
+    pub min_profit_threshold: f64,
+    /// Taker fee charged on each leg, in basis points
+    pub taker_fee_bps: f64,
+    /// Arbitrage: expected slippage on each leg, in basis points
+    pub slippage_bps: f64,
+    /// Largest quote-currency value of one arbitrage leg, and the largest
+    /// value of a triangulation cycle's starting leg in `valuation_currency`
+    pub max_order_notional: f64,
+    /// Triangulation: currency `max_order_notional` is expressed in
+    pub valuation_currency: String,
+    /// Arbitrage: quotes older than this are not traded against
+    pub max_quote_age_ms: u64,
+    /// Legs still working after this are cancelled (arbitrage hedges the
+    /// other leg, triangulation abandons the cycle)
+    pub leg_timeout_ms: u64,
+    /// Triangulation: longest currency cycle searched for (3 = triangles)
+    pub max_cycle_length: usize,
+}
+
+/// Coordinated shutdown on Ctrl-C
+#[derive(Debug, Deserialize, Clone)]
+pub struct ShutdownSettings {
+    /// Time allowed for stopping trading, cancelling orders and flushing writes
+    pub timeout_sec: u64,
+    /// Cancel every open exchange order before exiting
+    pub cancel_open_orders: bool,
+}
+
 impl Settings {
     /// Load configuration from environment-specific .env file and environment variables
     pub fn new() -> ConfigResult<Self> {
@@ -218,6 +367,7 @@ impl Settings {
         // Deserialize into our Settings struct
         let mut settings: Settings = config.try_deserialize()?;
         settings.environment = env;
+        settings.markets.validate()?;
         Ok(settings)
     }
 
@@ -262,33 +412,55 @@ impl Default for Settings {
                 max_connections: 10,
                 min_connections: 2,
                 connect_timeout_sec: 5,
+                timescale_url: None,
+                write_batch_size: 500,
+                write_flush_interval_ms: 1000,
             },
             exchange: ExchangeSettings {
                 okx_ws_public_url: "wss://ws.okx.com:8443/ws/v5/public".to_string(),
                 okx_ws_private_url: "wss://ws.okx.com:8443/ws/v5/private".to_string(),
                 okx_ws_business_url: "wss://ws.okx.com:8443/ws/v5/business".to_string(),
                 okx_rest_url: "https://www.okx.com".to_string(),
+                okx_simulated_trading: false,
+                binance_ws_url: "wss://stream.binance.com:9443".to_string(),
+                binance_rest_url: "https://api.binance.com".to_string(),
+                binance_recv_window_ms: 5000,
+                kraken_ws_url: "wss://ws.kraken.com/v2".to_string(),
+                kraken_rest_url: "https://api.kraken.com".to_string(),
                 ws_reconnect_delay_ms: 5000,
+                ws_max_reconnect_delay_ms: 60000,
                 ws_max_reconnect_attempts: 10,
                 ws_ping_interval_sec: 20,
+                ws_stale_timeout_sec: 60,
                 // API credentials default to None (must be set via environment)
                 okx_api_key: None,
                 okx_api_secret: None,
                 okx_api_passphrase: None,
                 binance_api_key: None,
                 binance_api_secret: None,
+                kraken_api_key: None,
+                kraken_api_secret: None,
             },
             trading: TradingSettings {
                 order_timeout_sec: 30,
                 max_pending_orders: 100,
                 order_retry_attempts: 3,
                 max_position_size: 1000.0,
+                max_daily_risk: 100.0,
                 max_leverage: 3.0,
                 stop_loss_percentage: 2.0,
                 take_profit_percentage: 5.0,
                 tick_buffer_size: 1000,
                 orderbook_depth: 20,
                 kline_intervals: "1m,5m,15m,1h,4h,1d".to_string(),
+                stream_book_ticker: true,
+                stream_klines: false,
+            },
+            markets: MarketsSettings {
+                binance: vec!["BTC/USDT".to_string(), "ETH/USDT".to_string()],
+                okx: vec!["BTC/USDT".to_string(), "ETH/USDT".to_string()],
+                kraken: Vec::new(),
+                instrument_cache: "data/instruments.json".to_string(),
             },
             performance: PerformanceSettings {
                 tokio_worker_threads: 4,
@@ -304,6 +476,31 @@ impl Default for Settings {
                 enable_debug_endpoints: false,
                 enable_backtesting: false,
             },
+            paper: PaperTradingSettings {
+                slippage_bps: 5.0,
+                maker_fee_bps: 10.0,
+                taker_fee_bps: 10.0,
+                initial_balances: HashMap::from([
+                    ("USDT".to_string(), 100_000.0),
+                    ("BTC".to_string(), 1.0),
+                    ("ETH".to_string(), 10.0),
+                ]),
+            },
+            shutdown: ShutdownSettings {
+                timeout_sec: 10,
+                cancel_open_orders: true,
+            },
+            strategies: StrategySettings {
+                enabled: Vec::new(),
+                min_profit_threshold: 0.001,
+                taker_fee_bps: 10.0,
+                slippage_bps: 2.0,
+                max_order_notional: 1000.0,
+                valuation_currency: "USDT".to_string(),
+                max_quote_age_ms: 1000,
+                leg_timeout_ms: 2000,
+                max_cycle_length: 3,
+            },
         }
     }
 }
@@ -331,4 +528,35 @@ mod tests {
         let intervals = settings.get_kline_intervals();
         assert_eq!(intervals, vec!["1m", "5m", "15m", "1h", "4h", "1d"]);
     }
+
+    #[test]
+    fn test_markets_map_to_exchange_symbols() {
+        let settings = Settings::default();
+        assert!(settings.markets.validate().is_ok());
+        assert_eq!(
+            settings
+                .markets
+                .exchange_symbols(&Exchange::Binance)
+                .unwrap(),
+            vec!["BTCUSDT", "ETHUSDT"]
+        );
+        assert_eq!(
+            settings.markets.exchange_symbols(&Exchange::OKX).unwrap(),
+            vec!["BTC-USDT", "ETH-USDT"]
+        );
+    }
+
+    #[test]
+    fn test_markets_validation() {
+        let mut markets = MarketsSettings {
+            binance: vec!["BTCUSDT".to_string()],
+            ..Default::default()
+        };
+        assert!(markets.validate().is_err());
+
+        markets.binance = vec!["BTC/USDT".to_string(), "btc/usdt".to_string()];
+        assert!(markets.validate().is_err());
+
+        assert!(MarketsSettings::default().validate().is_err());
+    }
 }
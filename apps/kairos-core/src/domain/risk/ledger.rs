@@ -0,0 +1,308 @@
+// Balance ledger - free/locked funds per venue and asset
+
+use dashmap::DashMap;
+use kairos_domain::{
+    AccountEvent, Amount, BalanceUpdate, DomainError, DomainResult, Exchange, Fill, OrderSide,
+    Symbol,
+};
+use serde::Serialize;
+
+/// Funds held in one asset on one venue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct AssetBalance {
+    /// Available for new orders
+    pub free: Amount,
+    /// Reserved by open orders
+    pub locked: Amount,
+}
+
+impl AssetBalance {
+    pub fn total(&self) -> Amount {
+        self.free + self.locked
+    }
+}
+
+/// Funds reserved for one open order
+#[derive(Debug, Clone)]
+struct Reservation {
+    exchange: Exchange,
+    currency: String,
+    remaining: Amount,
+}
+
+/// Per-venue, per-asset balances
+///
+/// Exchange account snapshots are authoritative and overwrite the local
+/// view; fills move funds in between snapshots. Open orders reserve funds
+/// (free -> locked) under their client order ID until they fill or are
+/// released. Amounts are fixed-point, so repeated fills and releases do
+/// not drift.
+///
+/// # Example
+/// ```rust,ignore
+/// let ledger = BalanceLedger::new();
+/// ledger.deposit(&Exchange::Binance, "USDT", "10000".parse()?);
+/// ledger.reserve("order-1", &Exchange::Binance, "USDT", "650".parse()?)?;
+/// ledger.apply_fill(&fill);
+/// ledger.release("order-1");
+/// ```
+#[derive(Default)]
+pub struct BalanceLedger {
+    balances: DashMap<(Exchange, String), AssetBalance>,
+    reservations: DashMap<String, Reservation>,
+}
+
+impl BalanceLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Credit free funds (initial capital, transfers)
+    pub fn deposit(&self, exchange: &Exchange, currency: &str, amount: Amount) {
+        self.balances
+            .entry(key(exchange, currency))
+            .or_default()
+            .free += amount;
+    }
+
+    /// Apply balance snapshots and fills from a private account stream
+    pub fn on_account_event(&self, event: &AccountEvent) {
+        match event {
+            AccountEvent::Balance(update) => self.apply_snapshot(update),
+            AccountEvent::Fill(fill) => self.apply_fill(fill),
+            _ => {}
+        }
+    }
+
+    /// Replace the local view of an asset with the exchange-reported balance
+    pub fn apply_snapshot(&self, update: &BalanceUpdate) {
+        self.balances.insert(
+            key(&update.exchange, &update.currency),
+            AssetBalance {
+                free: update.available,
+                locked: update.locked,
+            },
+        );
+    }
+
+    /// Move funds for a fill, spending the order's reservation first
+    ///
+    /// Fills whose symbol cannot be mapped to base/quote assets are ignored.
+    pub fn apply_fill(&self, fill: &Fill) {
+        let symbol = match Symbol::from_exchange(&fill.exchange, &fill.symbol) {
+            Ok(symbol) => symbol,
+            Err(e) => {
+                tracing::warn!("⚠️  Fill {} not applied to ledger: {}", fill.trade_id, e);
+                return;
+            }
+        };
+
+        let (base, quote) = (
+            Amount::from(fill.quantity),
+            Amount::from(fill.quantity * fill.price),
+        );
+        let (spent, spent_amount, received, received_amount) = match fill.side {
+            OrderSide::Buy => (&symbol.quote, quote, &symbol.base, base),
+            OrderSide::Sell => (&symbol.base, base, &symbol.quote, quote),
+        };
+
+        let from_reservation = fill
+            .client_order_id
+            .as_ref()
+            .and_then(|id| self.reservations.get_mut(id))
+            .filter(|reservation| reservation.currency == *spent)
+            .map(|mut reservation| {
+                let used = reservation.remaining.min(spent_amount);
+                reservation.remaining -= used;
+                used
+            })
+            .unwrap_or_default();
+
+        {
+            let mut balance = self.balances.entry(key(&fill.exchange, spent)).or_default();
+            balance.locked = (balance.locked - from_reservation).max(Amount::ZERO);
+            balance.free -= spent_amount - from_reservation;
+        }
+        self.balances
+            .entry(key(&fill.exchange, received))
+            .or_default()
+            .free += received_amount;
+        self.balances
+            .entry(key(&fill.exchange, &fill.fee_currency))
+            .or_default()
+            .free -= fill.fee;
+    }
+
+    /// Lock `amount` of free funds for an open order
+    pub fn reserve(
+        &self,
+        order_id: &str,
+        exchange: &Exchange,
+        currency: &str,
+        amount: Amount,
+    ) -> DomainResult<()> {
+        if amount < Amount::ZERO {
+            return Err(DomainError::InvalidAmount(format!(
+                "cannot reserve {} {}",
+                amount, currency
+            )));
+        }
+
+        {
+            let mut balance = self.balances.entry(key(exchange, currency)).or_default();
+            if balance.free < amount {
+                return Err(DomainError::InsufficientBalance {
+                    required: amount.to_f64(),
+                    available: balance.free.to_f64(),
+                });
+            }
+            balance.free -= amount;
+            balance.locked += amount;
+        }
+
+        self.reservations.insert(
+            order_id.to_string(),
+            Reservation {
+                exchange: exchange.clone(),
+                currency: currency.to_uppercase(),
+                remaining: amount,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return whatever an order still has reserved to free funds
+    pub fn release(&self, order_id: &str) {
+        let Some((_, reservation)) = self.reservations.remove(order_id) else {
+            return;
+        };
+
+        let mut balance = self
+            .balances
+            .entry(key(&reservation.exchange, &reservation.currency))
+            .or_default();
+        // A snapshot may already have unlocked part of it
+        let released = reservation.remaining.min(balance.locked);
+        balance.locked -= released;
+        balance.free += released;
+    }
+
+    pub fn balance(&self, exchange: &Exchange, currency: &str) -> AssetBalance {
+        self.balances
+            .get(&key(exchange, currency))
+            .map(|balance| *balance)
+            .unwrap_or_default()
+    }
+
+    /// Balance of `currency` summed over every venue
+    pub fn total(&self, currency: &str) -> AssetBalance {
+        let currency = currency.to_uppercase();
+        self.balances
+            .iter()
+            .filter(|entry| entry.key().1 == currency)
+            .fold(AssetBalance::default(), |sum, entry| AssetBalance {
+                free: sum.free + entry.free,
+                locked: sum.locked + entry.locked,
+            })
+    }
+
+    /// Every (venue, currency, balance) entry
+    pub fn balances(&self) -> Vec<(Exchange, String, AssetBalance)> {
+        self.balances
+            .iter()
+            .map(|entry| (entry.key().0.clone(), entry.key().1.clone(), *entry.value()))
+            .collect()
+    }
+}
+
+fn key(exchange: &Exchange, currency: &str) -> (Exchange, String) {
+    (exchange.clone(), currency.to_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn dec(s: &str) -> Amount {
+        s.parse().unwrap()
+    }
+
+    fn fill(side: OrderSide, quantity: &str, price: &str) -> Fill {
+        Fill {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: Some("o1".to_string()),
+            trade_id: "1".to_string(),
+            side,
+            price: price.parse().unwrap(),
+            quantity: quantity.parse().unwrap(),
+            fee: dec("0.5"),
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_reserve_fill_release() {
+        let ledger = BalanceLedger::new();
+        ledger.deposit(&Exchange::Binance, "usdt", dec("1000"));
+
+        assert!(matches!(
+            ledger.reserve("big", &Exchange::Binance, "USDT", dec("2000")),
+            Err(DomainError::InsufficientBalance { .. })
+        ));
+
+        ledger
+            .reserve("o1", &Exchange::Binance, "USDT", dec("300"))
+            .unwrap();
+        assert_eq!(
+            ledger.balance(&Exchange::Binance, "USDT"),
+            AssetBalance {
+                free: dec("700"),
+                locked: dec("300")
+            }
+        );
+
+        // Half of the order fills at 100, paying the fee from free funds
+        ledger.apply_fill(&fill(OrderSide::Buy, "1", "100"));
+        assert_eq!(
+            ledger.balance(&Exchange::Binance, "USDT"),
+            AssetBalance {
+                free: dec("699.5"),
+                locked: dec("200")
+            }
+        );
+        assert_eq!(ledger.balance(&Exchange::Binance, "BTC").free, dec("1"));
+
+        ledger.release("o1");
+        assert_eq!(
+            ledger.balance(&Exchange::Binance, "USDT"),
+            AssetBalance {
+                free: dec("899.5"),
+                locked: Amount::ZERO
+            }
+        );
+    }
+
+    #[test]
+    fn test_snapshot_overrides_and_totals() {
+        let ledger = BalanceLedger::new();
+        ledger.deposit(&Exchange::Binance, "BTC", dec("1"));
+        ledger.on_account_event(&AccountEvent::Balance(BalanceUpdate {
+            exchange: Exchange::OKX,
+            currency: "BTC".to_string(),
+            available: dec("0.25"),
+            locked: dec("0.5"),
+            total: dec("0.75"),
+            timestamp: Utc::now(),
+        }));
+
+        let total = ledger.total("btc");
+        assert_eq!(total.free, dec("1.25"));
+        assert_eq!(total.total(), dec("1.75"));
+        assert_eq!(ledger.total("ETH"), AssetBalance::default());
+    }
+}
@@ -1,40 +1,77 @@
 // Risk management module
 
-use kairos_domain::{DomainError, DomainResult, InternalOrder};
-use std::sync::atomic::{AtomicU64, Ordering};
+pub mod ledger;
+pub mod positions;
+
+pub use ledger::{AssetBalance, BalanceLedger};
+pub use positions::PositionTracker;
+
+use kairos_domain::{
+    Amount, DomainError, DomainResult, Exchange, InternalOrder, Notional, OrderSide, Price, Symbol,
+};
+use std::sync::{Arc, Mutex};
 
 /// The Gatekeeper - validates orders before execution
 pub struct RiskEngine {
-    // Atomic balance in cents to avoid floating point precision issues
-    balance_cents: AtomicU64,
+    ledger: Arc<BalanceLedger>,
+    /// Budget for the sum of accepted orders' unitless risk scores
     max_daily_risk: f64,
-    current_daily_risk: AtomicU64, // in cents
+    current_daily_risk: Mutex<f64>,
+    positions: Arc<PositionTracker>,
+    /// Largest absolute net position per instrument, in quote currency
+    max_position_notional: Option<Notional>,
 }
 
 impl RiskEngine {
-    pub fn new(initial_balance: f64, max_daily_risk: f64) -> Self {
+    pub fn new(max_daily_risk: f64) -> Self {
         Self {
-            balance_cents: AtomicU64::new((initial_balance * 100.0) as u64),
+            ledger: Arc::new(BalanceLedger::new()),
             max_daily_risk,
-            current_daily_risk: AtomicU64::new(0),
+            current_daily_risk: Mutex::new(0.0),
+            positions: Arc::new(PositionTracker::new()),
+            max_position_notional: None,
         }
     }
 
-    /// Validates an order against risk limits
-    pub fn validate_order(&self, order: &InternalOrder) -> DomainResult<()> {
-        let balance = self.get_balance();
-        let order_value = order.quantity * order.price.unwrap_or(0.0);
+    /// Reject orders that would grow an instrument's net position
+    /// (summed across venues) beyond `max_notional`
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let risk = RiskEngine::new(500.0)
+    ///     .with_max_position_notional("10000".parse()?);
+    /// ```
+    pub fn with_max_position_notional(mut self, max_notional: Notional) -> Self {
+        self.max_position_notional = Some(max_notional);
+        self
+    }
 
-        // Check sufficient balance
-        if order_value > balance {
-            return Err(DomainError::InsufficientBalance {
-                required: order_value,
-                available: balance,
-            });
-        }
+    /// Positions the exposure checks run against; feed it fills and market data
+    pub fn positions(&self) -> Arc<PositionTracker> {
+        self.positions.clone()
+    }
+
+    /// Balances the funding checks run against; feed it account events
+    pub fn ledger(&self) -> Arc<BalanceLedger> {
+        self.ledger.clone()
+    }
+
+    /// Validates an order for `exchange` against balances and risk limits
+    ///
+    /// Orders the checks cannot evaluate (unknown symbol, market buy without
+    /// a mark price) are rejected rather than waved through.
+    pub fn validate_order(&self, exchange: &Exchange, order: &InternalOrder) -> DomainResult<()> {
+        let symbol = resolve_symbol(exchange, &order.symbol)?;
+        self.check_balance(exchange, &symbol, order)?;
 
         // Check daily risk limit
-        let current_risk = (self.current_daily_risk.load(Ordering::Relaxed) as f64) / 100.0;
+        if !order.risk_score.is_finite() || order.risk_score < 0.0 {
+            return Err(DomainError::InvalidOrder(format!(
+                "risk score {} is not a finite non-negative number",
+                order.risk_score
+            )));
+        }
+        let current_risk = *self.current_daily_risk.lock().unwrap();
         if current_risk + order.risk_score > self.max_daily_risk {
             return Err(DomainError::RiskLimitExceeded(format!(
                 "Daily risk: {}, Order risk: {}",
@@ -42,34 +79,263 @@ impl RiskEngine {
             )));
         }
 
-        Ok(())
+        self.check_position_limit(&symbol, order)
+    }
+
+    /// Lock the funds an accepted order needs until it fills or is released
+    pub fn reserve(
+        &self,
+        order_id: &str,
+        exchange: &Exchange,
+        order: &InternalOrder,
+    ) -> DomainResult<()> {
+        let symbol = resolve_symbol(exchange, &order.symbol)?;
+        let (currency, amount) = self.required_funds(&symbol, order)?;
+        self.ledger.reserve(order_id, exchange, currency, amount)
+    }
+
+    /// Return an order's unused reservation
+    pub fn release(&self, order_id: &str) {
+        self.ledger.release(order_id);
     }
 
-    /// Updates balance after order execution
-    pub fn update_balance(&self, amount: f64) {
-        let cents = (amount * 100.0) as i64;
-        if cents > 0 {
-            self.balance_cents.fetch_add(cents as u64, Ordering::SeqCst);
-        } else {
-            self.balance_cents
-                .fetch_sub((-cents) as u64, Ordering::SeqCst);
+    /// Currency and amount an order spends: quote to buy, base to sell
+    ///
+    /// Buys without a limit price or a known mark cannot be valued.
+    fn required_funds<'a>(
+        &self,
+        symbol: &'a Symbol,
+        order: &InternalOrder,
+    ) -> DomainResult<(&'a str, Amount)> {
+        match order.side {
+            OrderSide::Buy => {
+                let price = self.valuation_price(symbol, order)?;
+                Ok((&symbol.quote, Amount::from(order.quantity * price)))
+            }
+            OrderSide::Sell => Ok((&symbol.base, Amount::from(order.quantity))),
         }
     }
 
-    /// Gets current balance
-    pub fn get_balance(&self) -> f64 {
-        (self.balance_cents.load(Ordering::Relaxed) as f64) / 100.0
+    /// Limit price of the order, or the instrument's mark for market orders
+    fn valuation_price(&self, symbol: &Symbol, order: &InternalOrder) -> DomainResult<Price> {
+        order
+            .price
+            .or_else(|| self.positions.mark_price(symbol))
+            .ok_or_else(|| {
+                DomainError::InvalidOrder(format!(
+                    "{} market order cannot be valued: no mark price yet",
+                    symbol
+                ))
+            })
+    }
+
+    /// Check the venue holds enough free funds for the order
+    fn check_balance(
+        &self,
+        exchange: &Exchange,
+        symbol: &Symbol,
+        order: &InternalOrder,
+    ) -> DomainResult<()> {
+        let (currency, required) = self.required_funds(symbol, order)?;
+
+        let available = self.ledger.balance(exchange, currency).free;
+        if required > available {
+            return Err(DomainError::InsufficientBalance {
+                required: required.to_f64(),
+                available: available.to_f64(),
+            });
+        }
+        Ok(())
     }
 
-    /// Adds to daily risk counter
+    /// Check the position an order would leave behind against the per-instrument limit
+    fn check_position_limit(&self, symbol: &Symbol, order: &InternalOrder) -> DomainResult<()> {
+        let Some(limit) = self.max_position_notional else {
+            return Ok(());
+        };
+        let price = self.valuation_price(symbol, order)?;
+
+        let current = self.positions.net_quantity(symbol);
+        let projected = match order.side {
+            OrderSide::Buy => current + order.quantity,
+            OrderSide::Sell => current - order.quantity,
+        };
+
+        // Reducing orders are always allowed
+        let notional = price * projected.abs();
+        if projected.abs() > current.abs() && notional > limit {
+            return Err(DomainError::RiskLimitExceeded(format!(
+                "{} position would reach {:.2} notional, limit {:.2}",
+                symbol, notional, limit
+            )));
+        }
+        Ok(())
+    }
+
+    /// Adds to daily risk counter (non-finite scores were refused by `validate_order`)
     pub fn add_risk(&self, risk: f64) {
-        let risk_cents = (risk * 100.0) as u64;
-        self.current_daily_risk
-            .fetch_add(risk_cents, Ordering::SeqCst);
+        if risk.is_finite() {
+            *self.current_daily_risk.lock().unwrap() += risk;
+        }
     }
 
     /// Resets daily risk (should be called daily)
     pub fn reset_daily_risk(&self) {
-        self.current_daily_risk.store(0, Ordering::SeqCst);
+        *self.current_daily_risk.lock().unwrap() = 0.0;
+    }
+}
+
+/// Canonical symbol of an order, given as `BASE/QUOTE` or in the venue's format
+///
+/// Orders whose symbol cannot be resolved cannot be checked, so they are refused.
+fn resolve_symbol(exchange: &Exchange, symbol: &str) -> DomainResult<Symbol> {
+    symbol
+        .parse::<Symbol>()
+        .or_else(|_| Symbol::from_exchange(exchange, symbol))
+        .map_err(|_| DomainError::SymbolNotFound {
+            symbol: symbol.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use kairos_domain::{Fill, Quantity};
+
+    fn order(side: OrderSide, quantity: f64) -> InternalOrder {
+        InternalOrder {
+            symbol: "BTC/USDT".to_string(),
+            side,
+            quantity: Quantity::try_from(quantity).unwrap(),
+            price: Some("100".parse().unwrap()),
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_position_limit() {
+        let risk = RiskEngine::new(100.0).with_max_position_notional("1000".parse().unwrap());
+        risk.ledger()
+            .deposit(&Exchange::Binance, "USDT", "1000000".parse().unwrap());
+        risk.ledger()
+            .deposit(&Exchange::Binance, "BTC", "100".parse().unwrap());
+        risk.positions().on_fill(&Fill {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: None,
+            trade_id: "1".to_string(),
+            side: OrderSide::Buy,
+            price: "100".parse().unwrap(),
+            quantity: "8".parse().unwrap(),
+            fee: Default::default(),
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        });
+
+        assert!(risk
+            .validate_order(&Exchange::Binance, &order(OrderSide::Buy, 2.0))
+            .is_ok());
+        assert!(matches!(
+            risk.validate_order(&Exchange::Binance, &order(OrderSide::Buy, 3.0)),
+            Err(DomainError::RiskLimitExceeded(_))
+        ));
+        // Selling reduces the long even when the size is large
+        assert!(risk
+            .validate_order(&Exchange::Binance, &order(OrderSide::Sell, 15.0))
+            .is_ok());
+        assert!(risk
+            .validate_order(&Exchange::Binance, &order(OrderSide::Sell, 20.0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_unvaluable_orders_rejected() {
+        let risk = RiskEngine::new(100.0);
+        risk.ledger()
+            .deposit(&Exchange::Binance, "USDT", "1000".parse().unwrap());
+
+        let unknown = InternalOrder {
+            symbol: "NOT-A-SYMBOL".to_string(),
+            ..order(OrderSide::Buy, 1.0)
+        };
+        assert!(matches!(
+            risk.validate_order(&Exchange::Binance, &unknown),
+            Err(DomainError::SymbolNotFound { .. })
+        ));
+
+        // A market buy before any mark price cannot be funded or sized
+        let market = InternalOrder {
+            price: None,
+            ..order(OrderSide::Buy, 1.0)
+        };
+        assert!(matches!(
+            risk.validate_order(&Exchange::Binance, &market),
+            Err(DomainError::InvalidOrder(_))
+        ));
+        assert!(risk.reserve("1", &Exchange::Binance, &market).is_err());
+    }
+
+    #[test]
+    fn test_daily_risk_limit() {
+        let risk = RiskEngine::new(100.0);
+        risk.ledger()
+            .deposit(&Exchange::Binance, "USDT", "1000".parse().unwrap());
+        let scored = |risk_score| InternalOrder {
+            risk_score,
+            ..order(OrderSide::Buy, 1.0)
+        };
+
+        risk.add_risk(70.5);
+        risk.add_risk(29.25);
+        assert!(risk
+            .validate_order(&Exchange::Binance, &scored(0.25))
+            .is_ok());
+        assert!(matches!(
+            risk.validate_order(&Exchange::Binance, &scored(0.5)),
+            Err(DomainError::RiskLimitExceeded(_))
+        ));
+        assert!(risk
+            .validate_order(&Exchange::Binance, &scored(f64::NAN))
+            .is_err());
+
+        risk.reset_daily_risk();
+        assert!(risk
+            .validate_order(&Exchange::Binance, &scored(99.0))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_balance_checked_per_venue_and_asset() {
+        let risk = RiskEngine::new(100.0);
+        risk.ledger()
+            .deposit(&Exchange::Binance, "USDT", "250".parse().unwrap());
+        risk.ledger()
+            .deposit(&Exchange::OKX, "BTC", "1".parse().unwrap());
+
+        assert!(risk
+            .validate_order(&Exchange::Binance, &order(OrderSide::Buy, 2.0))
+            .is_ok());
+        assert!(matches!(
+            risk.validate_order(&Exchange::Binance, &order(OrderSide::Buy, 3.0)),
+            Err(DomainError::InsufficientBalance { .. })
+        ));
+        // BTC held on OKX cannot be sold on Binance
+        assert!(risk
+            .validate_order(&Exchange::Binance, &order(OrderSide::Sell, 1.0))
+            .is_err());
+        assert!(risk
+            .validate_order(&Exchange::OKX, &order(OrderSide::Sell, 1.0))
+            .is_ok());
+
+        // Reserved funds are no longer available
+        risk.ledger()
+            .reserve("o1", &Exchange::Binance, "USDT", "100".parse().unwrap())
+            .unwrap();
+        assert!(risk
+            .validate_order(&Exchange::Binance, &order(OrderSide::Buy, 2.0))
+            .is_err());
     }
 }
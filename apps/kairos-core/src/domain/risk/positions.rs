@@ -0,0 +1,154 @@
+// Position keeping - net position per instrument and venue, built from fills
+
+use dashmap::DashMap;
+use kairos_domain::{Exchange, Fill, MarketEvent, Notional, Position, Price, Quantity, Symbol};
+
+/// Live positions keyed by venue and canonical symbol
+///
+/// Fills and market data carry exchange-native symbols; they are mapped to
+/// canonical symbols so the same instrument lines up across venues.
+#[derive(Default)]
+pub struct PositionTracker {
+    positions: DashMap<(Exchange, Symbol), Position>,
+}
+
+impl PositionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a fill, returning the updated position
+    ///
+    /// Returns `None` when the fill's symbol cannot be mapped to an instrument.
+    pub fn on_fill(&self, fill: &Fill) -> Option<Position> {
+        let symbol = match Symbol::from_exchange(&fill.exchange, &fill.symbol) {
+            Ok(symbol) => symbol,
+            Err(e) => {
+                tracing::warn!("⚠️  Fill {} not applied to positions: {}", fill.trade_id, e);
+                return None;
+            }
+        };
+
+        let mut position = self
+            .positions
+            .entry((fill.exchange.clone(), symbol.clone()))
+            .or_insert_with(|| Position::new(fill.exchange.clone(), symbol));
+        position.apply_fill(fill);
+        Some(position.clone())
+    }
+
+    /// Mark open positions to the latest trade price or quote mid
+    pub fn on_market_event(&self, event: &MarketEvent) {
+        let price = match event {
+            MarketEvent::Trade(tick) => tick.price,
+            MarketEvent::Quote(quote) => quote.mid(),
+            _ => return,
+        };
+        let Ok(symbol) = Symbol::from_exchange(event.exchange(), event.symbol()) else {
+            return;
+        };
+
+        if let Some(mut position) = self.positions.get_mut(&(event.exchange().clone(), symbol)) {
+            position.mark(price);
+        }
+    }
+
+    pub fn get(&self, exchange: &Exchange, symbol: &Symbol) -> Option<Position> {
+        self.positions
+            .get(&(exchange.clone(), symbol.clone()))
+            .map(|position| position.clone())
+    }
+
+    pub fn positions(&self) -> Vec<Position> {
+        self.positions
+            .iter()
+            .map(|position| position.clone())
+            .collect()
+    }
+
+    /// Signed quantity of `symbol` summed over every venue
+    pub fn net_quantity(&self, symbol: &Symbol) -> Quantity {
+        self.positions
+            .iter()
+            .filter(|position| position.symbol == *symbol)
+            .map(|position| position.quantity)
+            .sum()
+    }
+
+    /// Latest mark price of `symbol` on any venue
+    pub fn mark_price(&self, symbol: &Symbol) -> Option<Price> {
+        self.positions
+            .iter()
+            .filter(|position| position.symbol == *symbol)
+            .find_map(|position| position.mark_price)
+    }
+
+    /// Sum of absolute position notionals across all instruments and venues
+    pub fn gross_exposure(&self) -> Notional {
+        self.positions
+            .iter()
+            .map(|position| position.notional())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use kairos_domain::{MarketTick, OrderSide};
+    use std::fmt::Debug;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn dec<T: FromStr>(s: &str) -> T
+    where
+        T::Err: Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    fn fill(exchange: Exchange, symbol: &str, side: OrderSide, quantity: &str) -> Fill {
+        Fill {
+            exchange,
+            symbol: symbol.to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: None,
+            trade_id: "1".to_string(),
+            side,
+            price: "100".parse().unwrap(),
+            quantity: quantity.parse().unwrap(),
+            fee: Default::default(),
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_positions_net_across_venues_and_mark() {
+        let tracker = PositionTracker::new();
+        tracker.on_fill(&fill(Exchange::Binance, "BTCUSDT", OrderSide::Buy, "2"));
+        tracker.on_fill(&fill(Exchange::OKX, "BTC-USDT", OrderSide::Sell, "0.5"));
+
+        let btc = Symbol::new("BTC", "USDT");
+        assert_eq!(tracker.net_quantity(&btc), dec("1.5"));
+        assert_eq!(tracker.gross_exposure(), dec("250"));
+
+        tracker.on_market_event(&MarketEvent::Trade(MarketTick {
+            id: Uuid::new_v4(),
+            symbol: "BTCUSDT".to_string(),
+            price: "110".parse().unwrap(),
+            volume: "1".parse().unwrap(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            exchange: Exchange::Binance,
+            trade_id: None,
+            side: None,
+        }));
+        let binance = tracker.get(&Exchange::Binance, &btc).unwrap();
+        assert_eq!(binance.unrealized_pnl(), dec("20"));
+        assert_eq!(tracker.mark_price(&btc), Some(dec("110")));
+        assert_eq!(tracker.gross_exposure(), dec("270"));
+    }
+}
@@ -1,23 +1,719 @@
-use kairos_domain::{InternalOrder, MarketTick};
+// Cross-exchange arbitrage - buy on the cheap venue, sell on the rich one
 
-/// Arbitrage strategy - detects price differences across exchanges
+use super::strategy::{Strategy, StrategyContext};
+use chrono::{DateTime, Utc};
+use kairos_domain::{
+    Amount, Exchange, Fill, InternalOrder, MarketEvent, MarketEventFilter, MarketEventKind,
+    Notional, OrderSide, Price, Quantity, Quote, Symbol,
+};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A crossing between two venues that is profitable after costs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageOpportunity {
+    pub symbol: Symbol,
+    pub buy_exchange: Exchange,
+    pub sell_exchange: Exchange,
+    /// Best ask on the buy venue
+    pub buy_price: Price,
+    /// Best bid on the sell venue
+    pub sell_price: Price,
+    /// Relative edge after fees and slippage (0.001 = 0.1%)
+    pub edge: Decimal,
+    /// Size of both legs, limited by displayed liquidity and free balances
+    /// and rounded onto the lot grid of both venues
+    pub quantity: Quantity,
+}
+
+impl ArbitrageOpportunity {
+    /// Marketable limit orders for both legs at the quoted prices
+    pub fn orders(&self) -> (InternalOrder, InternalOrder) {
+        let leg = |side, price| InternalOrder {
+            symbol: self.symbol.to_string(),
+            side,
+            quantity: self.quantity,
+            price: Some(price),
+            risk_score: 1.0,
+        };
+        (
+            leg(OrderSide::Buy, self.buy_price),
+            leg(OrderSide::Sell, self.sell_price),
+        )
+    }
+}
+
+/// One side of a submitted opportunity
+#[derive(Debug, Clone)]
+struct Leg {
+    exchange: Exchange,
+    /// `None` when the order could not be queued
+    client_order_id: Option<String>,
+    filled: Quantity,
+}
+
+impl Leg {
+    fn is_working(&self, open: &[String]) -> bool {
+        self.client_order_id
+            .as_ref()
+            .is_some_and(|id| open.contains(id))
+    }
+}
+
+/// Both legs of an opportunity, tracked until they offset each other
+#[derive(Debug, Clone)]
+struct LegPair {
+    buy: Leg,
+    sell: Leg,
+    opened_at: DateTime<Utc>,
+    cancel_sent: bool,
+    /// Both legs were already done at the previous check
+    closed_seen: bool,
+}
+
+impl LegPair {
+    /// Base bought minus base sold
+    fn imbalance(&self) -> Quantity {
+        self.buy.filled - self.sell.filled
+    }
+}
+
+/// Arbitrage strategy - trades price differences for one instrument across exchanges
+///
+/// Keeps the latest best bid/offer per instrument per venue. When the best
+/// bid on one venue beats the best ask on another by more than
+/// `min_profit_threshold` after taker fees and slippage on both legs, it
+/// buys and sells the same quantity at the same time, sized by displayed
+/// liquidity and by the free balances on both venues, then rounded down
+/// to the coarser of the two venues' lot sizes so both legs trade the
+/// same amount.
+///
+/// Only one pair of legs is in flight per instrument. Legs still working
+/// after the leg timeout are cancelled; if the legs then filled unequal
+/// quantities, the difference is hedged on whichever of the two venues
+/// quotes the better price and has the funds, so the strategy returns to
+/// flat.
+///
+/// # Example
+/// ```rust,ignore
+/// let strategy = ArbitrageStrategy::new(Decimal::new(1, 3))
+///     .with_costs(Decimal::TEN, Decimal::TWO)
+///     .with_max_order_notional("1000".parse()?)
+///     .with_leg_timeout(Duration::from_secs(2));
+/// ```
 pub struct ArbitrageStrategy {
-    min_profit_threshold: f64,
+    min_profit_threshold: Decimal,
+    taker_fee_bps: Decimal,
+    slippage_bps: Decimal,
+    /// `None` leaves legs limited by liquidity and balances only
+    max_order_notional: Option<Notional>,
+    max_quote_age: Duration,
+    leg_timeout: Duration,
+    /// Latest BBO per instrument per venue
+    books: HashMap<Symbol, HashMap<Exchange, Quote>>,
+    /// Legs in flight per instrument
+    pairs: HashMap<Symbol, LegPair>,
 }
 
 impl ArbitrageStrategy {
-    pub fn new(min_profit_threshold: f64) -> Self {
+    pub fn new(min_profit_threshold: Decimal) -> Self {
         Self {
             min_profit_threshold,
+            taker_fee_bps: Decimal::TEN,
+            slippage_bps: Decimal::ZERO,
+            max_order_notional: None,
+            max_quote_age: Duration::from_secs(1),
+            leg_timeout: Duration::from_secs(2),
+            books: HashMap::new(),
+            pairs: HashMap::new(),
         }
     }
 
-    /// Analyzes market ticks to find arbitrage opportunities
-    pub fn analyze(&self, _ticks: &[MarketTick]) -> Option<InternalOrder> {
-        // TODO: Implement arbitrage logic
-        // 1. Compare prices across exchanges
-        // 2. Calculate profit potential
-        // 3. If profit > threshold, return order
-        None
+    /// Taker fee and expected slippage per leg, in basis points
+    pub fn with_costs(mut self, taker_fee_bps: Decimal, slippage_bps: Decimal) -> Self {
+        self.taker_fee_bps = taker_fee_bps;
+        self.slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Cap on the quote-currency value of one leg
+    pub fn with_max_order_notional(mut self, max_notional: Notional) -> Self {
+        self.max_order_notional = Some(max_notional);
+        self
+    }
+
+    /// Quotes older than this are not traded against
+    pub fn with_max_quote_age(mut self, max_age: Duration) -> Self {
+        self.max_quote_age = max_age;
+        self
+    }
+
+    /// Time both legs get to fill before the rest is cancelled and hedged
+    pub fn with_leg_timeout(mut self, timeout: Duration) -> Self {
+        self.leg_timeout = timeout;
+        self
+    }
+
+    /// Record a venue's BBO; returns the instrument it belongs to
+    pub fn update_quote(&mut self, quote: &Quote) -> Option<Symbol> {
+        let symbol = match Symbol::from_exchange(&quote.exchange, &quote.symbol) {
+            Ok(symbol) => symbol,
+            Err(e) => {
+                tracing::debug!("Arbitrage ignoring quote: {}", e);
+                return None;
+            }
+        };
+        self.books
+            .entry(symbol.clone())
+            .or_default()
+            .insert(quote.exchange.clone(), quote.clone());
+        Some(symbol)
+    }
+
+    /// Best opportunity for `symbol` across every pair of venues, if any
+    /// clears the threshold and can be funded
+    pub fn analyze(
+        &self,
+        symbol: &Symbol,
+        now: DateTime<Utc>,
+        ctx: &dyn StrategyContext,
+    ) -> Option<ArbitrageOpportunity> {
+        let quotes: Vec<&Quote> = self
+            .books
+            .get(symbol)?
+            .values()
+            .filter(|quote| self.is_fresh(quote, now))
+            .collect();
+
+        let mut best: Option<ArbitrageOpportunity> = None;
+        for buy in &quotes {
+            for sell in &quotes {
+                if buy.exchange == sell.exchange {
+                    continue;
+                }
+                let edge = self.edge(buy.ask_price, sell.bid_price);
+                if edge <= self.min_profit_threshold
+                    || best.as_ref().is_some_and(|best| best.edge >= edge)
+                {
+                    continue;
+                }
+                let Some(quantity) = self.size(symbol, buy, sell, ctx) else {
+                    continue;
+                };
+                best = Some(ArbitrageOpportunity {
+                    symbol: symbol.clone(),
+                    buy_exchange: buy.exchange.clone(),
+                    sell_exchange: sell.exchange.clone(),
+                    buy_price: buy.ask_price,
+                    sell_price: sell.bid_price,
+                    edge,
+                    quantity,
+                });
+            }
+        }
+        best
+    }
+
+    /// Taker fee plus slippage on one leg, as a fraction of its value
+    fn leg_cost(&self) -> Decimal {
+        (self.taker_fee_bps + self.slippage_bps) / Decimal::from(10_000)
+    }
+
+    /// Relative profit of buying at `ask` and selling at `bid`, net of costs
+    fn edge(&self, ask: Price, bid: Price) -> Decimal {
+        let cost = self.leg_cost();
+        let paid = ask.value() * (Decimal::ONE + cost);
+        let received = bid.value() * (Decimal::ONE - cost);
+        received / paid - Decimal::ONE
+    }
+
+    /// Largest quantity both venues can trade right now, on both lot grids
+    ///
+    /// `None` when nothing is left after rounding or the result is below
+    /// either venue's minimum size.
+    fn size(
+        &self,
+        symbol: &Symbol,
+        buy: &Quote,
+        sell: &Quote,
+        ctx: &dyn StrategyContext,
+    ) -> Option<Quantity> {
+        let quote_free = Notional::new(ctx.free_balance(&buy.exchange, &symbol.quote).value());
+        let base_free = Quantity::new(ctx.free_balance(&sell.exchange, &symbol.base).value());
+        let ask_with_costs = Price::new(buy.ask_price.value() * (Decimal::ONE + self.leg_cost()));
+
+        let mut quantity = buy
+            .ask_quantity
+            .min(sell.bid_quantity)
+            .min(quote_free / ask_with_costs)
+            .min(base_free);
+        if let Some(max_notional) = self.max_order_notional {
+            quantity = quantity.min(max_notional / buy.ask_price);
+        }
+
+        let instruments: Vec<_> = [
+            (&buy.exchange, buy.ask_price),
+            (&sell.exchange, sell.bid_price),
+        ]
+        .into_iter()
+        .filter_map(|(exchange, price)| Some((ctx.instrument(exchange, symbol)?, price)))
+        .collect();
+        if let Some(lot_size) = instruments
+            .iter()
+            .map(|(instrument, _)| instrument.lot_size)
+            .max()
+        {
+            quantity = quantity.round_to_step(lot_size, RoundingStrategy::ToZero);
+        }
+
+        let tradable = instruments.iter().all(|(instrument, price)| {
+            quantity >= instrument.min_quantity && quantity * *price >= instrument.min_notional
+        });
+        (quantity.is_positive() && tradable).then_some(quantity)
+    }
+
+    fn is_fresh(&self, quote: &Quote, now: DateTime<Utc>) -> bool {
+        let usable = quote.bid_price.is_positive() && quote.ask_price.is_positive();
+        let age = (now - quote.received_at).to_std().unwrap_or_default();
+        usable && age <= self.max_quote_age
+    }
+
+    /// Queue both legs of an opportunity
+    fn execute(
+        &mut self,
+        opportunity: ArbitrageOpportunity,
+        now: DateTime<Utc>,
+        ctx: &dyn StrategyContext,
+    ) {
+        let (buy_order, sell_order) = opportunity.orders();
+
+        // Without the buy leg there is nothing to pair the sell with
+        let buy_id = match ctx.submit(opportunity.buy_exchange.clone(), buy_order) {
+            Ok(id) => id,
+            Err(e) => {
+                tracing::warn!("⚠️  Arbitrage buy leg not queued: {}", e);
+                return;
+            }
+        };
+        let sell_id = match ctx.submit(opportunity.sell_exchange.clone(), sell_order) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                tracing::warn!("⚠️  Arbitrage sell leg not queued, cancelling buy: {}", e);
+                let _ = ctx.cancel(&buy_id);
+                None
+            }
+        };
+
+        tracing::info!(
+            "⚖️  Arbitrage {}: buy {} on {:?} @ {}, sell on {:?} @ {} (edge {:.4}%)",
+            opportunity.symbol,
+            opportunity.quantity,
+            opportunity.buy_exchange,
+            opportunity.buy_price,
+            opportunity.sell_exchange,
+            opportunity.sell_price,
+            opportunity.edge * Decimal::ONE_HUNDRED
+        );
+
+        self.pairs.insert(
+            opportunity.symbol,
+            LegPair {
+                buy: Leg {
+                    exchange: opportunity.buy_exchange,
+                    client_order_id: Some(buy_id),
+                    filled: Quantity::ZERO,
+                },
+                sell: Leg {
+                    exchange: opportunity.sell_exchange,
+                    client_order_id: sell_id,
+                    filled: Quantity::ZERO,
+                },
+                opened_at: now,
+                cancel_sent: false,
+                closed_seen: false,
+            },
+        );
+    }
+
+    /// Count a fill against the leg it belongs to
+    fn record_fill(&mut self, fill: &Fill) {
+        let Some(id) = fill.client_order_id.as_ref() else {
+            return;
+        };
+        for pair in self.pairs.values_mut() {
+            for leg in [&mut pair.buy, &mut pair.sell] {
+                if leg.client_order_id.as_ref() == Some(id) {
+                    leg.filled += fill.quantity;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Cancel legs past the timeout and settle pairs whose legs are done
+    pub fn check_legs(&mut self, now: DateTime<Utc>, ctx: &dyn StrategyContext) {
+        if self.pairs.is_empty() {
+            return;
+        }
+        let open = ctx.open_orders();
+        let mut settled = Vec::new();
+
+        for (symbol, pair) in self.pairs.iter_mut() {
+            let working: Vec<&Leg> = [&pair.buy, &pair.sell]
+                .into_iter()
+                .filter(|leg| leg.is_working(&open))
+                .collect();
+
+            if !working.is_empty() {
+                let expired =
+                    (now - pair.opened_at).to_std().unwrap_or_default() >= self.leg_timeout;
+                if expired && !pair.cancel_sent {
+                    tracing::warn!("⏱️  Arbitrage {} legs timed out, cancelling", symbol);
+                    for id in working
+                        .iter()
+                        .filter_map(|leg| leg.client_order_id.as_ref())
+                    {
+                        if let Err(e) = ctx.cancel(id) {
+                            tracing::warn!("⚠️  Arbitrage cancel not queued: {}", e);
+                        }
+                    }
+                    pair.cancel_sent = true;
+                }
+                continue;
+            }
+
+            // A fill can reach us just after its order closes: give an
+            // uneven pair one more check before hedging it
+            if pair.imbalance().is_zero() || pair.closed_seen {
+                settled.push(symbol.clone());
+            } else {
+                pair.closed_seen = true;
+            }
+        }
+
+        for symbol in settled {
+            if let Some(pair) = self.pairs.remove(&symbol) {
+                self.settle(&symbol, &pair, now, ctx);
+            }
+        }
+    }
+
+    /// Close out whatever the legs left unmatched
+    fn settle(
+        &self,
+        symbol: &Symbol,
+        pair: &LegPair,
+        now: DateTime<Utc>,
+        ctx: &dyn StrategyContext,
+    ) {
+        let imbalance = pair.imbalance();
+        if imbalance.is_zero() {
+            if pair.buy.filled.is_positive() {
+                tracing::info!(
+                    "✅ Arbitrage {} completed ({} filled)",
+                    symbol,
+                    pair.buy.filled
+                );
+            }
+            return;
+        }
+
+        let side = if imbalance.is_positive() {
+            OrderSide::Sell
+        } else {
+            OrderSide::Buy
+        };
+        let quantity = imbalance.abs();
+        let (exchange, price) = self.hedge_venue(symbol, pair, &side, quantity, now, ctx);
+        tracing::warn!(
+            "🩹 Arbitrage {} legs uneven by {}, hedging with {:?} on {:?}",
+            symbol,
+            imbalance,
+            side,
+            exchange
+        );
+
+        let order = InternalOrder {
+            symbol: symbol.to_string(),
+            side,
+            quantity,
+            price,
+            // Reduces exposure, so it is not charged to the daily budget
+            risk_score: 0.0,
+        };
+        if let Err(e) = ctx.submit(exchange, order) {
+            tracing::error!("❌ Arbitrage hedge for {} not queued: {}", symbol, e);
+        }
+    }
+
+    /// Venue and limit price for a hedge
+    ///
+    /// Prefers the better quote of the two venues the pair traded on, among
+    /// those with funds for it. Completing the missing leg keeps the edge;
+    /// otherwise the filled leg is unwound where it was opened. The limit is
+    /// set through the touch by the slippage allowance; without a fresh quote
+    /// the hedge goes out as a market order.
+    fn hedge_venue(
+        &self,
+        symbol: &Symbol,
+        pair: &LegPair,
+        side: &OrderSide,
+        quantity: Quantity,
+        now: DateTime<Utc>,
+        ctx: &dyn StrategyContext,
+    ) -> (Exchange, Option<Price>) {
+        let slippage = self.slippage_bps / Decimal::from(10_000);
+        let (missing, opened) = match side {
+            OrderSide::Sell => (&pair.sell.exchange, &pair.buy.exchange),
+            OrderSide::Buy => (&pair.buy.exchange, &pair.sell.exchange),
+        };
+
+        let candidates = [missing, opened].into_iter().filter_map(|exchange| {
+            let quote = self
+                .books
+                .get(symbol)?
+                .get(exchange)
+                .filter(|quote| self.is_fresh(quote, now))?;
+            let (price, funded) = match side {
+                OrderSide::Sell => {
+                    let price = Price::new(quote.bid_price.value() * (Decimal::ONE - slippage));
+                    let free = ctx.free_balance(exchange, &symbol.base);
+                    (price, free >= Amount::from(quantity))
+                }
+                OrderSide::Buy => {
+                    let price = Price::new(quote.ask_price.value() * (Decimal::ONE + slippage));
+                    let free = ctx.free_balance(exchange, &symbol.quote);
+                    (price, free >= Amount::from(quantity * price))
+                }
+            };
+            funded.then_some((exchange, price))
+        });
+
+        let best = candidates.reduce(|best, candidate| {
+            let better = match side {
+                OrderSide::Sell => candidate.1 > best.1,
+                OrderSide::Buy => candidate.1 < best.1,
+            };
+            if better {
+                candidate
+            } else {
+                best
+            }
+        });
+
+        match best {
+            Some((exchange, price)) => (exchange.clone(), Some(price)),
+            None => (opened.clone(), None),
+        }
+    }
+}
+
+impl Strategy for ArbitrageStrategy {
+    fn name(&self) -> &str {
+        "arbitrage"
+    }
+
+    /// Best bid/ask per venue
+    fn event_filter(&self) -> MarketEventFilter {
+        MarketEventFilter::kinds(&[MarketEventKind::Quote])
+    }
+
+    /// Often enough to notice a timed-out leg promptly
+    fn timer_interval(&self) -> Option<Duration> {
+        Some((self.leg_timeout / 4).max(Duration::from_millis(50)))
+    }
+
+    fn on_market_event(&mut self, event: &MarketEvent, ctx: &dyn StrategyContext) {
+        let MarketEvent::Quote(quote) = event else {
+            return;
+        };
+        let Some(symbol) = self.update_quote(quote) else {
+            return;
+        };
+        if self.pairs.contains_key(&symbol) {
+            return;
+        }
+        let now = Utc::now();
+        if let Some(opportunity) = self.analyze(&symbol, now, ctx) {
+            self.execute(opportunity, now, ctx);
+        }
+    }
+
+    fn on_fill(&mut self, fill: &Fill, _ctx: &dyn StrategyContext) {
+        self.record_fill(fill);
+    }
+
+    fn on_timer(&mut self, ctx: &dyn StrategyContext) {
+        self.check_legs(Utc::now(), ctx);
+    }
+
+    fn on_stop(&mut self, ctx: &dyn StrategyContext) {
+        if let Err(e) = ctx.cancel_all() {
+            tracing::warn!("⚠️  Arbitrage orders not cancelled: {}", e);
+        }
+        for (symbol, pair) in &self.pairs {
+            if !pair.imbalance().is_zero() {
+                tracing::warn!(
+                    "⚠️  Arbitrage {} stopped with legs uneven by {}",
+                    symbol,
+                    pair.imbalance()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::strategies::mock::MockContext;
+    use kairos_domain::Instrument;
+
+    fn quote(exchange: Exchange, native: &str, bid: &str, ask: &str) -> MarketEvent {
+        MarketEvent::Quote(Quote {
+            exchange,
+            symbol: native.to_string(),
+            bid_price: bid.parse().unwrap(),
+            bid_quantity: "5".parse().unwrap(),
+            ask_price: ask.parse().unwrap(),
+            ask_quantity: "5".parse().unwrap(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        })
+    }
+
+    fn funded() -> MockContext {
+        MockContext::default()
+            .with_balance(Exchange::Binance, "USDT", "250.25")
+            .with_balance(Exchange::OKX, "BTC", "3")
+    }
+
+    /// 0.1% threshold, 10 bps taker fee, no slippage
+    fn strategy() -> ArbitrageStrategy {
+        ArbitrageStrategy::new(Decimal::new(1, 3)).with_costs(Decimal::TEN, Decimal::ZERO)
+    }
+
+    fn fill(client_order_id: &str, side: OrderSide, quantity: Quantity) -> Fill {
+        Fill {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: Some(client_order_id.to_string()),
+            trade_id: "t1".to_string(),
+            side,
+            price: "100".parse().unwrap(),
+            quantity,
+            fee: Amount::ZERO,
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_emits_paired_orders_sized_by_balances() {
+        let ctx = funded();
+        let mut strategy = strategy();
+
+        strategy.on_market_event(&quote(Exchange::Binance, "BTCUSDT", "99.9", "100.0"), &ctx);
+        strategy.on_market_event(&quote(Exchange::OKX, "BTC-USDT", "101.0", "101.1"), &ctx);
+
+        let submitted = ctx.submitted.borrow();
+        assert_eq!(submitted.len(), 2);
+        let (buy_exchange, buy, _) = &submitted[0];
+        let (sell_exchange, sell, _) = &submitted[1];
+        assert_eq!(*buy_exchange, Exchange::Binance);
+        assert_eq!(buy.side, OrderSide::Buy);
+        assert_eq!(buy.price, Some("100".parse().unwrap()));
+        assert_eq!(*sell_exchange, Exchange::OKX);
+        assert_eq!(sell.side, OrderSide::Sell);
+        assert_eq!(sell.symbol, "BTC/USDT");
+
+        // 250.25 USDT buys 2.5 BTC at 100 plus the 0.1% fee
+        assert_eq!(buy.quantity, sell.quantity);
+        assert_eq!(buy.quantity, "2.5".parse().unwrap());
+    }
+
+    #[test]
+    fn test_legs_rounded_to_coarser_lot() {
+        let symbol = Symbol::new("BTC", "USDT");
+        let mut ctx = MockContext::default()
+            .with_balance(Exchange::Binance, "USDT", "250.7")
+            .with_balance(Exchange::OKX, "BTC", "3")
+            .with_instrument(Exchange::Binance, symbol.clone());
+        let okx = Instrument::new(
+            Exchange::OKX,
+            symbol.clone(),
+            "0.1".parse().unwrap(),
+            "0.01".parse().unwrap(),
+            "0.01".parse().unwrap(),
+            Notional::ZERO,
+        );
+        ctx.instruments.insert((Exchange::OKX, symbol), okx);
+        let mut strategy = strategy();
+
+        strategy.on_market_event(&quote(Exchange::Binance, "BTCUSDT", "99.9", "100.0"), &ctx);
+        strategy.on_market_event(&quote(Exchange::OKX, "BTC-USDT", "101.0", "101.1"), &ctx);
+
+        // 250.7 USDT affords 2.5044.. BTC: the 0.001 lot would allow 2.504,
+        // OKX's 0.01 lot only 2.5, so both legs trade 2.5
+        let submitted = ctx.submitted.borrow();
+        assert_eq!(submitted.len(), 2);
+        assert_eq!(submitted[0].1.quantity, "2.5".parse().unwrap());
+        assert_eq!(submitted[1].1.quantity, "2.5".parse().unwrap());
+    }
+
+    #[test]
+    fn test_fees_consume_small_spread() {
+        let ctx = funded();
+        let mut strategy = strategy();
+
+        // 0.1% gross is less than two 0.1% taker fees
+        strategy.on_market_event(&quote(Exchange::Binance, "BTCUSDT", "99.9", "100.0"), &ctx);
+        strategy.on_market_event(&quote(Exchange::OKX, "BTC-USDT", "100.1", "100.2"), &ctx);
+
+        assert!(ctx.submitted.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_hedges_when_one_leg_misses() {
+        let ctx = funded();
+        let mut strategy = strategy()
+            .with_leg_timeout(Duration::from_secs(2))
+            .with_max_quote_age(Duration::from_secs(10));
+
+        strategy.on_market_event(&quote(Exchange::Binance, "BTCUSDT", "99.9", "100.0"), &ctx);
+        strategy.on_market_event(&quote(Exchange::OKX, "BTC-USDT", "101.0", "101.1"), &ctx);
+        let (buy_id, sell_id) = {
+            let submitted = ctx.submitted.borrow();
+            (submitted[0].2.clone(), submitted[1].2.clone())
+        };
+
+        // The buy fills, the sell rests
+        strategy.on_fill(&fill(&buy_id, OrderSide::Buy, "2".parse().unwrap()), &ctx);
+        ctx.close(&buy_id);
+
+        strategy.check_legs(Utc::now(), &ctx);
+        assert!(ctx.cancelled.borrow().is_empty());
+
+        let later = Utc::now() + chrono::Duration::seconds(3);
+        strategy.check_legs(later, &ctx);
+        assert_eq!(*ctx.cancelled.borrow(), vec![sell_id.clone()]);
+
+        // Cancel confirmed: after one grace check the long 2 BTC is sold
+        // where the missing leg was meant to go
+        ctx.open.borrow_mut().clear();
+        strategy.check_legs(later, &ctx);
+        assert_eq!(ctx.submitted.borrow().len(), 2);
+        strategy.check_legs(later, &ctx);
+
+        let submitted = ctx.submitted.borrow();
+        assert_eq!(submitted.len(), 3);
+        let (exchange, hedge, _) = &submitted[2];
+        assert_eq!(*exchange, Exchange::OKX);
+        assert_eq!(hedge.side, OrderSide::Sell);
+        assert_eq!(hedge.quantity, "2".parse().unwrap());
+        assert!(strategy.pairs.is_empty());
     }
 }
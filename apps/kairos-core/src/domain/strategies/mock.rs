@@ -0,0 +1,93 @@
+// Test double for StrategyContext - records what a strategy asks for
+
+use super::strategy::StrategyContext;
+use kairos_domain::{
+    Amount, DomainResult, Exchange, Instrument, InternalOrder, Notional, Position, Quantity, Symbol,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Context that queues nothing: submits and cancels are recorded, and
+/// submitted orders stay open until a test removes them
+#[derive(Default)]
+pub struct MockContext {
+    pub balances: HashMap<(Exchange, String), Amount>,
+    pub instruments: HashMap<(Exchange, Symbol), Instrument>,
+    pub submitted: RefCell<Vec<(Exchange, InternalOrder, String)>>,
+    pub cancelled: RefCell<Vec<String>>,
+    pub open: RefCell<Vec<String>>,
+}
+
+impl MockContext {
+    pub fn with_balance(mut self, exchange: Exchange, currency: &str, amount: &str) -> Self {
+        self.balances
+            .insert((exchange, currency.to_string()), amount.parse().unwrap());
+        self
+    }
+
+    /// Instrument with a 0.01 tick and a 0.001 lot
+    pub fn with_instrument(mut self, exchange: Exchange, symbol: Symbol) -> Self {
+        let instrument = Instrument::new(
+            exchange.clone(),
+            symbol.clone(),
+            "0.01".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            Notional::default(),
+        );
+        self.instruments.insert((exchange, symbol), instrument);
+        self
+    }
+
+    /// Mark an order as no longer working
+    pub fn close(&self, client_order_id: &str) {
+        self.open.borrow_mut().retain(|id| id != client_order_id);
+    }
+}
+
+impl StrategyContext for MockContext {
+    fn submit(&self, exchange: Exchange, order: InternalOrder) -> DomainResult<String> {
+        let id = format!("order-{}", self.submitted.borrow().len());
+        self.submitted
+            .borrow_mut()
+            .push((exchange, order, id.clone()));
+        self.open.borrow_mut().push(id.clone());
+        Ok(id)
+    }
+
+    fn cancel(&self, client_order_id: &str) -> DomainResult<()> {
+        self.cancelled
+            .borrow_mut()
+            .push(client_order_id.to_string());
+        Ok(())
+    }
+
+    fn cancel_all(&self) -> DomainResult<()> {
+        Ok(())
+    }
+
+    fn open_orders(&self) -> Vec<String> {
+        self.open.borrow().clone()
+    }
+
+    fn free_balance(&self, exchange: &Exchange, currency: &str) -> Amount {
+        self.balances
+            .get(&(exchange.clone(), currency.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn instrument(&self, exchange: &Exchange, symbol: &Symbol) -> Option<Instrument> {
+        self.instruments
+            .get(&(exchange.clone(), symbol.clone()))
+            .cloned()
+    }
+
+    fn position(&self, _exchange: &Exchange, _symbol: &Symbol) -> Option<Position> {
+        None
+    }
+
+    fn net_position(&self, _symbol: &Symbol) -> Quantity {
+        Quantity::ZERO
+    }
+}
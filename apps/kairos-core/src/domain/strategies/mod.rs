@@ -1,7 +1,11 @@
 // Trading strategies module
 
 pub mod arbitrage;
+#[cfg(test)]
+pub(crate) mod mock;
+pub mod strategy;
 pub mod triangulation;
 
 pub use arbitrage::*;
+pub use strategy::{Strategy, StrategyContext};
 pub use triangulation::*;
@@ -0,0 +1,91 @@
+// Strategy contract - what the runtime calls and what a strategy may do
+
+use kairos_domain::{
+    Amount, DomainResult, Exchange, Fill, Instrument, InternalOrder, MarketEvent,
+    MarketEventFilter, Position, Quantity, Symbol,
+};
+use std::time::Duration;
+
+/// Actions and state available to a strategy inside its callbacks
+///
+/// Submissions and cancels are queued to the trading engine without
+/// blocking; risk checks and venue round trips happen there. Fills for the
+/// strategy's orders come back through [`Strategy::on_fill`].
+pub trait StrategyContext {
+    /// Queue an order for `exchange`, returning the client order ID it is
+    /// tracked under
+    fn submit(&self, exchange: Exchange, order: InternalOrder) -> DomainResult<String>;
+
+    /// Queue a cancel for one of this strategy's open orders
+    fn cancel(&self, client_order_id: &str) -> DomainResult<()>;
+
+    /// Queue a cancel for every open order of this strategy
+    fn cancel_all(&self) -> DomainResult<()>;
+
+    /// Client order IDs of this strategy's open orders
+    fn open_orders(&self) -> Vec<String>;
+
+    /// Funds in `currency` on `exchange` not reserved by open orders
+    fn free_balance(&self, exchange: &Exchange, currency: &str) -> Amount;
+
+    /// Trading rules (tick, lot, minimums) of `symbol` on `exchange`
+    fn instrument(&self, exchange: &Exchange, symbol: &Symbol) -> Option<Instrument>;
+
+    /// Position in `symbol` on one venue
+    fn position(&self, exchange: &Exchange, symbol: &Symbol) -> Option<Position>;
+
+    /// Signed quantity of `symbol` summed over every venue
+    fn net_position(&self, symbol: &Symbol) -> Quantity;
+}
+
+/// A trading strategy driven by the strategy runtime
+///
+/// Callbacks run sequentially on the strategy's own task, so implementations
+/// keep plain `&mut self` state without locking. Callbacks should return
+/// quickly; a panicking callback stops the strategy (not the core) and its
+/// open orders are cancelled.
+///
+/// # Example
+/// ```rust,ignore
+/// struct Momentum { last: Option<Price> }
+///
+/// impl Strategy for Momentum {
+///     fn name(&self) -> &str {
+///         "momentum"
+///     }
+///
+///     fn on_market_event(&mut self, event: &MarketEvent, ctx: &dyn StrategyContext) {
+///         if let MarketEvent::Trade(tick) = event {
+///             // ... decide, then:
+///             let _ = ctx.submit(tick.exchange.clone(), order);
+///         }
+///     }
+/// }
+/// ```
+pub trait Strategy: Send {
+    /// Unique name, recorded on every order the strategy places
+    fn name(&self) -> &str;
+
+    /// Market events delivered to [`Strategy::on_market_event`]
+    fn event_filter(&self) -> MarketEventFilter {
+        MarketEventFilter::all()
+    }
+
+    /// Period of [`Strategy::on_timer`] calls (`None` = no timer)
+    fn timer_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Called once before any event
+    fn on_start(&mut self, _ctx: &dyn StrategyContext) {}
+
+    fn on_market_event(&mut self, event: &MarketEvent, ctx: &dyn StrategyContext);
+
+    /// A fill of one of this strategy's orders
+    fn on_fill(&mut self, _fill: &Fill, _ctx: &dyn StrategyContext) {}
+
+    fn on_timer(&mut self, _ctx: &dyn StrategyContext) {}
+
+    /// Called once on shutdown; open orders may still be cancelled here
+    fn on_stop(&mut self, _ctx: &dyn StrategyContext) {}
+}
@@ -1,32 +1,431 @@
-use kairos_domain::{InternalOrder, MarketTick};
-use std::collections::HashMap;
+// Triangular arbitrage - profitable currency cycles within one venue
+
+use super::strategy::{Strategy, StrategyContext};
+use chrono::{DateTime, Utc};
+use kairos_domain::{
+    Amount, ArbitrageCycle, DomainError, DomainResult, Exchange, Fill, InternalOrder, MarketEvent,
+    MarketEventFilter, MarketEventKind, OrderSide, Price, Quantity, Quote, RateGraph, Symbol,
+};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// A cycle being executed, one leg at a time
+#[derive(Debug)]
+struct ActiveCycle {
+    exchange: Exchange,
+    /// Legs not submitted yet, in execution order
+    pending: VecDeque<InternalOrder>,
+    /// Client order ID of the leg in flight
+    working: String,
+    /// The leg in flight, as submitted
+    leg: InternalOrder,
+    filled: Quantity,
+    /// Currency the leg in flight delivers, and how much of it arrived net
+    /// of fees charged in it
+    receiving: String,
+    received: Amount,
+    /// Currency the leg in flight spends
+    holding: String,
+    step_started: DateTime<Utc>,
+}
 
 /// Triangulation strategy - uses Bellman-Ford to find negative cycles
+///
+/// Keeps a currency graph per venue built from best bid/ask, with `-ln(rate)`
+/// edge weights net of the taker fee. Each quote updates its two edges and
+/// only cycles through them are checked, up to `max_cycle_length` legs.
+///
+/// A cycle clearing `min_profit_threshold` is started from a currency the
+/// venue holds, sized by that balance, `max_order_notional` (valued in
+/// `valuation_currency` through the rate graph) and the displayed sizes,
+/// with every leg on its instrument's lot grid. Legs are sent one at a
+/// time: the next leg goes out once the previous one has filled, sized from
+/// what that fill actually delivered after fees. A leg that does not fill
+/// within the leg timeout is cancelled and the cycle abandoned. One cycle
+/// runs at a time.
+///
+/// # Example
+/// ```rust,ignore
+/// let strategy = TriangulationStrategy::new()
+///     .with_min_profit(0.001)
+///     .with_taker_fee_bps(10.0)
+///     .with_max_order_notional(1_000.0)
+///     .with_valuation_currency("USDT")
+///     .with_max_cycle_length(3);
+/// ```
 pub struct TriangulationStrategy {
-    // Graph representation: symbol -> (neighbor, exchange_rate)
-    graph: HashMap<String, Vec<(String, f64)>>,
+    min_profit_threshold: f64,
+    taker_fee_bps: f64,
+    max_order_notional: f64,
+    valuation_currency: String,
+    max_cycle_length: usize,
+    leg_timeout: Duration,
+    /// Currency graph per venue
+    graphs: HashMap<Exchange, RateGraph>,
+    active: Option<ActiveCycle>,
 }
 
 impl TriangulationStrategy {
     pub fn new() -> Self {
         Self {
-            graph: HashMap::new(),
+            min_profit_threshold: 0.001,
+            taker_fee_bps: 10.0,
+            max_order_notional: f64::INFINITY,
+            valuation_currency: "USDT".to_string(),
+            max_cycle_length: 3,
+            leg_timeout: Duration::from_secs(2),
+            graphs: HashMap::new(),
+            active: None,
+        }
+    }
+
+    /// Minimum gain of one trip round a cycle, after fees (0.001 = 0.1%)
+    pub fn with_min_profit(mut self, min_profit_threshold: f64) -> Self {
+        self.min_profit_threshold = min_profit_threshold;
+        self
+    }
+
+    /// Taker fee charged on every leg, in basis points
+    pub fn with_taker_fee_bps(mut self, taker_fee_bps: f64) -> Self {
+        self.taker_fee_bps = taker_fee_bps;
+        self
+    }
+
+    /// Cap on the value of the starting leg, in the valuation currency
+    pub fn with_max_order_notional(mut self, max_notional: f64) -> Self {
+        self.max_order_notional = max_notional;
+        self
+    }
+
+    /// Currency `max_order_notional` is expressed in (default USDT)
+    pub fn with_valuation_currency(mut self, currency: impl Into<String>) -> Self {
+        self.valuation_currency = currency.into().to_uppercase();
+        self
+    }
+
+    /// Longest cycle searched for (3 = triangles)
+    pub fn with_max_cycle_length(mut self, max_cycle_length: usize) -> Self {
+        self.max_cycle_length = max_cycle_length.max(2);
+        self
+    }
+
+    /// Time a leg gets to fill before the cycle is abandoned
+    pub fn with_leg_timeout(mut self, timeout: Duration) -> Self {
+        self.leg_timeout = timeout;
+        self
+    }
+
+    /// Updates the internal graph with a new best bid/ask
+    ///
+    /// Returns the most profitable cycle through the updated instrument.
+    pub fn update_graph(&mut self, quote: &Quote) -> Option<ArbitrageCycle> {
+        let symbol = match Symbol::from_exchange(&quote.exchange, &quote.symbol) {
+            Ok(symbol) => symbol,
+            Err(e) => {
+                tracing::debug!("Triangulation ignoring quote: {}", e);
+                return None;
+            }
+        };
+        let taker_fee_bps = self.taker_fee_bps;
+        let graph = self
+            .graphs
+            .entry(quote.exchange.clone())
+            .or_insert_with(|| RateGraph::new(quote.exchange.clone(), taker_fee_bps));
+
+        let edges = graph.update_quote(
+            &symbol,
+            quote.bid_price.to_f64(),
+            quote.bid_quantity.to_f64(),
+            quote.ask_price.to_f64(),
+            quote.ask_quantity.to_f64(),
+        );
+        edges
+            .iter()
+            .filter_map(|&edge| {
+                graph.find_cycle_through(edge, self.max_cycle_length, self.min_profit_threshold)
+            })
+            .max_by(|a, b| a.profit.total_cmp(&b.profit))
+    }
+
+    /// Every profitable cycle on every venue, best first
+    pub fn find_opportunities(&self) -> Vec<ArbitrageCycle> {
+        let mut cycles: Vec<_> = self
+            .graphs
+            .values()
+            .flat_map(|graph| graph.find_cycles(self.max_cycle_length, self.min_profit_threshold))
+            .collect();
+        cycles.sort_by(|a, b| b.profit.total_cmp(&a.profit));
+        cycles
+    }
+
+    /// Orders for `cycle`, started from the first currency on it that the
+    /// venue holds and that yields valid orders
+    fn plan(
+        &self,
+        cycle: &ArbitrageCycle,
+        ctx: &dyn StrategyContext,
+    ) -> Option<(ArbitrageCycle, Vec<InternalOrder>)> {
+        let exchange = &cycle.exchange;
+        cycle.legs.iter().find_map(|leg| {
+            let cap = self.start_cap(exchange, &leg.from)?;
+            let budget = ctx.free_balance(exchange, &leg.from).to_f64().min(cap);
+            if budget <= 0.0 {
+                return None;
+            }
+            let rotated = cycle.rotate_to(&leg.from)?;
+            match rotated.plan_orders(budget, |symbol| ctx.instrument(exchange, symbol)) {
+                Ok(orders) => Some((rotated, orders)),
+                Err(e) => {
+                    tracing::debug!("Triangulation cycle from {} not tradable: {}", leg.from, e);
+                    None
+                }
+            }
+        })
+    }
+
+    /// Most of `currency` a cycle may start with: `max_order_notional`
+    /// converted from the valuation currency at the venue's touch prices
+    ///
+    /// `None` when the currency cannot be valued, so the cap cannot be applied.
+    fn start_cap(&self, exchange: &Exchange, currency: &str) -> Option<f64> {
+        if self.max_order_notional.is_infinite() {
+            return Some(f64::INFINITY);
+        }
+        let value = self
+            .graphs
+            .get(exchange)
+            .and_then(|graph| graph.conversion_rate(currency, &self.valuation_currency));
+        if value.is_none() {
+            tracing::debug!(
+                "Triangulation cannot value {} in {}, not starting from it",
+                currency,
+                self.valuation_currency
+            );
+        }
+        Some(self.max_order_notional / value?)
+    }
+
+    fn start(
+        &mut self,
+        cycle: &ArbitrageCycle,
+        orders: Vec<InternalOrder>,
+        now: DateTime<Utc>,
+        ctx: &dyn StrategyContext,
+    ) {
+        let route: Vec<&str> = cycle.legs.iter().map(|leg| leg.from.as_str()).collect();
+        tracing::info!(
+            "🔺 Triangulation on {:?}: {} -> {} ({:.4}%)",
+            cycle.exchange,
+            route.join(" -> "),
+            route[0],
+            cycle.profit * 100.0
+        );
+        self.advance(cycle.exchange.clone(), orders.into(), None, now, ctx);
+    }
+
+    /// Submit the next leg, or finish the cycle when none is left
+    ///
+    /// `delivered` is what the previous leg actually returned; the next leg
+    /// is re-sized to spend exactly that instead of its planned quantity.
+    fn advance(
+        &mut self,
+        exchange: Exchange,
+        mut pending: VecDeque<InternalOrder>,
+        delivered: Option<Amount>,
+        now: DateTime<Utc>,
+        ctx: &dyn StrategyContext,
+    ) {
+        self.active = None;
+        let Some(planned) = pending.pop_front() else {
+            tracing::info!("✅ Triangulation cycle on {:?} completed", exchange);
+            return;
+        };
+
+        let holding = order_currency(&planned, true);
+        let order = match delivered {
+            Some(amount) => match resize_leg(&exchange, planned, amount, ctx) {
+                Ok(order) => order,
+                Err(e) => {
+                    tracing::warn!(
+                        "⚠️  Triangulation leg not sized, cycle abandoned holding {}: {}",
+                        holding,
+                        e
+                    );
+                    return;
+                }
+            },
+            None => planned,
+        };
+
+        let leg = order.clone();
+        match ctx.submit(exchange.clone(), order) {
+            Ok(working) => {
+                self.active = Some(ActiveCycle {
+                    exchange,
+                    pending,
+                    working,
+                    receiving: order_currency(&leg, false),
+                    leg,
+                    filled: Quantity::ZERO,
+                    received: Amount::ZERO,
+                    holding,
+                    step_started: now,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️  Triangulation leg not queued, cycle abandoned holding {}: {}",
+                    holding,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Count a fill against the leg in flight, moving on once it completes
+    fn record_fill(&mut self, fill: &Fill, now: DateTime<Utc>, ctx: &dyn StrategyContext) {
+        let Some(active) = self.active.as_mut() else {
+            return;
+        };
+        if fill.client_order_id.as_ref() != Some(&active.working) {
+            return;
+        }
+        active.filled += fill.quantity;
+        active.received += match active.leg.side {
+            OrderSide::Buy => Amount::from(fill.quantity),
+            OrderSide::Sell => Amount::from(fill.price * fill.quantity),
+        };
+        if fill.fee_currency.eq_ignore_ascii_case(&active.receiving) {
+            active.received -= fill.fee;
+        }
+
+        if active.filled >= active.leg.quantity {
+            if let Some(active) = self.active.take() {
+                self.advance(
+                    active.exchange,
+                    active.pending,
+                    Some(active.received),
+                    now,
+                    ctx,
+                );
+            }
+        }
+    }
+
+    /// Abandon the cycle if its leg in flight has timed out
+    pub fn check_cycle(&mut self, now: DateTime<Utc>, ctx: &dyn StrategyContext) {
+        let Some(active) = self.active.as_ref() else {
+            return;
+        };
+        let elapsed = (now - active.step_started).to_std().unwrap_or_default();
+        if elapsed < self.leg_timeout {
+            return;
+        }
+
+        if ctx.open_orders().contains(&active.working) {
+            if let Err(e) = ctx.cancel(&active.working) {
+                tracing::warn!("⚠️  Triangulation cancel not queued: {}", e);
+            }
+        }
+        tracing::warn!(
+            "🩹 Triangulation leg on {:?} timed out ({}/{} filled), cycle abandoned holding {}",
+            active.exchange,
+            active.filled,
+            active.leg.quantity,
+            active.holding
+        );
+        self.active = None;
+    }
+}
+
+/// Currency an order spends (quote for buys, base for sells), or the one
+/// it delivers when `spent` is false
+fn order_currency(order: &InternalOrder, spent: bool) -> String {
+    let symbol: Option<Symbol> = order.symbol.parse().ok();
+    match (symbol, order.side == OrderSide::Buy) {
+        (Some(symbol), buy) if buy == spent => symbol.quote,
+        (Some(symbol), _) => symbol.base,
+        (None, _) => order.symbol.clone(),
+    }
+}
+
+/// Re-size a planned leg to spend `available` of its input currency,
+/// rounded down onto the instrument's lot grid
+fn resize_leg(
+    exchange: &Exchange,
+    order: InternalOrder,
+    available: Amount,
+    ctx: &dyn StrategyContext,
+) -> DomainResult<InternalOrder> {
+    let quantity = match order.side {
+        OrderSide::Sell => available.value(),
+        OrderSide::Buy => {
+            let price = order.price.filter(Price::is_positive).ok_or_else(|| {
+                DomainError::InvalidPrice(format!("{} leg has no limit price", order.symbol))
+            })?;
+            available.value() / price.value()
         }
+    };
+    let symbol: Symbol = order.symbol.parse()?;
+    let instrument =
+        ctx.instrument(exchange, &symbol)
+            .ok_or_else(|| DomainError::SymbolNotFound {
+                symbol: order.symbol.clone(),
+            })?;
+    instrument.normalize_order(&InternalOrder {
+        quantity: Quantity::new(quantity),
+        ..order
+    })
+}
+
+impl Strategy for TriangulationStrategy {
+    fn name(&self) -> &str {
+        "triangulation"
+    }
+
+    /// Best bid/ask drives the rate graph
+    fn event_filter(&self) -> MarketEventFilter {
+        MarketEventFilter::kinds(&[MarketEventKind::Quote])
     }
 
-    /// Updates the internal graph with new market data
-    pub fn update_graph(&mut self, tick: &MarketTick) {
-        // TODO: Update graph edges with new prices
-        // Convert prices to logarithms for Bellman-Ford
+    /// Often enough to notice a timed-out leg promptly
+    fn timer_interval(&self) -> Option<Duration> {
+        Some((self.leg_timeout / 4).max(Duration::from_millis(50)))
     }
 
-    /// Runs Bellman-Ford to detect negative cycles (arbitrage opportunities)
-    pub fn find_opportunities(&self) -> Vec<InternalOrder> {
-        // TODO: Implement optimized Bellman-Ford
-        // 1. Run algorithm on graph
-        // 2. Detect negative cycles
-        // 3. Convert cycles to orders
-        Vec::new()
+    fn on_market_event(&mut self, event: &MarketEvent, ctx: &dyn StrategyContext) {
+        let MarketEvent::Quote(quote) = event else {
+            return;
+        };
+        let Some(cycle) = self.update_graph(quote) else {
+            return;
+        };
+        if self.active.is_some() {
+            return;
+        }
+        if let Some((cycle, orders)) = self.plan(&cycle, ctx) {
+            self.start(&cycle, orders, Utc::now(), ctx);
+        }
+    }
+
+    fn on_fill(&mut self, fill: &Fill, ctx: &dyn StrategyContext) {
+        self.record_fill(fill, Utc::now(), ctx);
+    }
+
+    fn on_timer(&mut self, ctx: &dyn StrategyContext) {
+        self.check_cycle(Utc::now(), ctx);
+    }
+
+    fn on_stop(&mut self, ctx: &dyn StrategyContext) {
+        if let Some(active) = self.active.take() {
+            tracing::warn!(
+                "⚠️  Triangulation stopped mid-cycle holding {}",
+                active.holding
+            );
+        }
+        if let Err(e) = ctx.cancel_all() {
+            tracing::warn!("⚠️  Triangulation orders not cancelled: {}", e);
+        }
     }
 }
 
@@ -35,3 +434,168 @@ impl Default for TriangulationStrategy {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::strategies::mock::MockContext;
+    use kairos_domain::{Amount, Price, Quantity};
+
+    fn quote(native: &str, price: f64) -> MarketEvent {
+        MarketEvent::Quote(Quote {
+            exchange: Exchange::Binance,
+            symbol: native.to_string(),
+            bid_price: Price::try_from(price).unwrap(),
+            bid_quantity: "100".parse().unwrap(),
+            ask_price: Price::try_from(price).unwrap(),
+            ask_quantity: "100".parse().unwrap(),
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+        })
+    }
+
+    fn fill(client_order_id: &str, quantity: Quantity) -> Fill {
+        Fill {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: Some(client_order_id.to_string()),
+            trade_id: "t1".to_string(),
+            side: OrderSide::Buy,
+            price: "100".parse().unwrap(),
+            quantity,
+            fee: Amount::ZERO,
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn venue() -> MockContext {
+        MockContext::default()
+            .with_balance(Exchange::Binance, "USDT", "250")
+            .with_instrument(Exchange::Binance, Symbol::new("BTC", "USDT"))
+            .with_instrument(Exchange::Binance, Symbol::new("ETH", "USDT"))
+            .with_instrument(Exchange::Binance, Symbol::new("ETH", "BTC"))
+    }
+
+    /// ETH/BTC at 0.09 against BTC 100 and ETH 10 USDT
+    fn mispriced(strategy: &mut TriangulationStrategy, ctx: &MockContext) {
+        strategy.on_market_event(&quote("BTCUSDT", 100.0), ctx);
+        strategy.on_market_event(&quote("ETHUSDT", 10.0), ctx);
+        strategy.on_market_event(&quote("ETHBTC", 0.09), ctx);
+    }
+
+    fn last_submitted(ctx: &MockContext) -> (InternalOrder, String) {
+        let submitted = ctx.submitted.borrow();
+        let (_, order, id) = submitted.last().unwrap();
+        (order.clone(), id.clone())
+    }
+
+    #[test]
+    fn test_executes_cycle_leg_by_leg() {
+        let ctx = venue();
+        let mut strategy = TriangulationStrategy::new();
+        mispriced(&mut strategy, &ctx);
+
+        // Starts from the funded currency and waits for each fill
+        let (first, id) = last_submitted(&ctx);
+        assert_eq!(ctx.submitted.borrow().len(), 1);
+        assert_eq!(first.symbol, "BTC/USDT");
+        assert_eq!(first.side, OrderSide::Buy);
+
+        strategy.on_fill(&fill(&id, "1".parse().unwrap()), &ctx);
+        assert_eq!(ctx.submitted.borrow().len(), 1);
+        strategy.on_fill(&fill(&id, first.quantity - "1".parse().unwrap()), &ctx);
+
+        let (second, id) = last_submitted(&ctx);
+        assert_eq!(second.symbol, "ETH/BTC");
+        assert_eq!(second.side, OrderSide::Buy);
+        strategy.on_fill(&fill(&id, second.quantity), &ctx);
+
+        let (third, id) = last_submitted(&ctx);
+        assert_eq!(third.symbol, "ETH/USDT");
+        assert_eq!(third.side, OrderSide::Sell);
+        strategy.on_fill(&fill(&id, third.quantity), &ctx);
+
+        assert_eq!(ctx.submitted.borrow().len(), 3);
+        assert!(strategy.active.is_none());
+    }
+
+    #[test]
+    fn test_start_leg_capped_by_max_order_notional() {
+        let ctx = venue();
+        let mut strategy = TriangulationStrategy::new().with_max_order_notional(50.0);
+        mispriced(&mut strategy, &ctx);
+
+        // 250 USDT free, but the first leg spends at most 50
+        let (first, _) = last_submitted(&ctx);
+        assert_eq!(first.symbol, "BTC/USDT");
+        let notional = first.quantity.to_f64() * 100.0;
+        assert!(notional <= 50.0 && notional > 49.0, "notional {}", notional);
+    }
+
+    #[test]
+    fn test_start_cap_valued_in_valuation_currency() {
+        let ctx = venue()
+            .with_balance(Exchange::Binance, "USDT", "0")
+            .with_balance(Exchange::Binance, "BTC", "100");
+        let mut strategy = TriangulationStrategy::new().with_max_order_notional(50.0);
+        mispriced(&mut strategy, &ctx);
+
+        // Starts from BTC: 50 USDT is 0.5 BTC, not 50 BTC
+        let (first, _) = last_submitted(&ctx);
+        assert_eq!(first.symbol, "ETH/BTC");
+        let spent = first.quantity * first.price.unwrap();
+        assert!(spent <= "0.5".parse().unwrap() && spent > "0.49".parse().unwrap());
+    }
+
+    #[test]
+    fn test_next_leg_sized_from_net_fill() {
+        let ctx = venue();
+        let mut strategy = TriangulationStrategy::new();
+        mispriced(&mut strategy, &ctx);
+        let (first, id) = last_submitted(&ctx);
+        assert_eq!(first.quantity, "2.5".parse().unwrap());
+
+        // 2.5 BTC bought, 0.025 BTC kept as fee (planned for the 0.1% taker fee)
+        strategy.on_fill(
+            &Fill {
+                fee: "0.025".parse().unwrap(),
+                fee_currency: "BTC".to_string(),
+                ..fill(&id, first.quantity)
+            },
+            &ctx,
+        );
+
+        // 2.475 BTC / 0.09 = 27.5 ETH, not the planned 27.75
+        let (second, _) = last_submitted(&ctx);
+        assert_eq!(second.symbol, "ETH/BTC");
+        assert_eq!(second.quantity, "27.5".parse().unwrap());
+    }
+
+    #[test]
+    fn test_abandons_cycle_when_leg_times_out() {
+        let ctx = venue();
+        let mut strategy = TriangulationStrategy::new().with_leg_timeout(Duration::from_secs(2));
+        mispriced(&mut strategy, &ctx);
+        let (_, id) = last_submitted(&ctx);
+
+        strategy.check_cycle(Utc::now(), &ctx);
+        assert!(strategy.active.is_some());
+
+        strategy.check_cycle(Utc::now() + chrono::Duration::seconds(3), &ctx);
+        assert_eq!(*ctx.cancelled.borrow(), vec![id]);
+        assert!(strategy.active.is_none());
+    }
+
+    #[test]
+    fn test_unfunded_or_unknown_instruments_are_skipped() {
+        let ctx = MockContext::default().with_balance(Exchange::Binance, "USDT", "250");
+        let mut strategy = TriangulationStrategy::new();
+        mispriced(&mut strategy, &ctx);
+
+        assert_eq!(strategy.find_opportunities().len(), 1);
+        assert!(ctx.submitted.borrow().is_empty());
+    }
+}
@@ -32,72 +32,451 @@ async fn main() -> anyhow::Result<()> {
     info!("🌍 Environment: {}", settings.environment);
     info!("📋 Configuration loaded successfully");
 
-    // 1. Create broadcast channel for market data
-    info!("📡 Creating market data broadcast channel...");
-    let (market_data_tx, _market_data_rx) =
-        tokio::sync::broadcast::channel::<kairos_domain::MarketTick>(1000);
+    // 1. Resolve the configured symbol universe (validated when settings load)
+    let binance_symbols = settings
+        .markets
+        .exchange_symbols(&kairos_domain::Exchange::Binance)?;
+    let okx_instruments = settings
+        .markets
+        .exchange_symbols(&kairos_domain::Exchange::OKX)?;
 
-    // 2. Configure symbols to track
-    let symbols = vec!["btcusdt".to_string(), "ethusdt".to_string()];
+    // Load instrument metadata (tick/lot sizes, minimums) for order normalization.
+    // Live orders cannot be normalized without it, so only paper trading may
+    // start with an empty registry
+    let instruments =
+        match adapters::inbound::reference_data::InstrumentLoader::from_settings(&settings)
+            .load(&settings.markets)
+            .await
+        {
+            Ok(instruments) => instruments,
+            Err(e) if !settings.features.enable_paper_trading => {
+                return Err(e).context("Instrument metadata is required for live trading");
+            }
+            Err(e) => {
+                tracing::error!(
+                "❌ Instrument metadata unavailable, paper orders will be sent unnormalized: {}",
+                e
+            );
+                kairos_domain::InstrumentRegistry::new()
+            }
+        };
+
+    // 2. Shared state (risk, balances, positions, orders) and the engine owning every channel
+    let max_position_notional =
+        kairos_domain::Notional::try_from(settings.trading.max_position_size)
+            .context("Invalid trading.max_position_size")?;
+    let risk_engine = domain::risk::RiskEngine::new(settings.trading.max_daily_risk)
+        .with_max_position_notional(max_position_notional);
+    let state = std::sync::Arc::new(
+        application::state::AppState::new(risk_engine, instruments)
+            .with_unlisted_symbols(settings.features.enable_paper_trading),
+    );
+    let mut engine = application::engine::TradingEngine::new(state.clone())
+        .with_cancel_on_shutdown(settings.shutdown.cancel_open_orders);
+
+    // Shutdown runs in stages: `stop_trading` stops the order producers
+    // (strategies, gRPC); `stop_engine` then ends order intake so open orders
+    // can be settled and cancelled while market data still flows;
+    // `stop_data` finally stops feeds, paper venues and persistence, which
+    // flushes its buffers last
+    let stop_trading = tokio_util::sync::CancellationToken::new();
+    let stop_engine = tokio_util::sync::CancellationToken::new();
+    let stop_data = tokio_util::sync::CancellationToken::new();
+
+    info!("📡 Wiring market data and account event channels...");
+    let market_data_tx = engine.market_data_sender();
+    let account_event_tx = engine.account_event_sender();
+
+    // Shared reconnect/keep-alive policy for every WebSocket feed
+    let reconnect_policy =
+        adapters::inbound::feed_handler::ReconnectPolicy::from_settings(&settings);
 
     // 3. Start Binance Feed Handler (The Feed Handler)
     info!("🔌 Initializing Binance WebSocket feed handler...");
     let binance_feed = adapters::inbound::feed_handler::binance::BinanceFeedHandler::new_public(
+        adapters::inbound::feed_handler::binance::BinanceConfig::from_settings(&settings),
+        market_data_tx.clone(),
+        binance_symbols.clone(),
+    )
+    .with_streams(
+        adapters::inbound::feed_handler::binance::BinanceStreams::from_settings(&settings),
+    )
+    .with_reconnect_policy(reconnect_policy.clone());
+
+    // Runtime SUBSCRIBE/UNSUBSCRIBE control for the Binance connection, served over gRPC
+    engine = engine.with_market_subscriptions(binance_feed.subscription_handle());
+
+    // Feed handlers are run (and restarted on crash) by the supervisor
+    let mut feed_supervisor = adapters::inbound::feed_handler::FeedSupervisor::new(
+        std::time::Duration::from_millis(settings.exchange.ws_reconnect_delay_ms),
+    )
+    .with_feed(std::sync::Arc::new(binance_feed));
+
+    // Start Binance depth feed (local L2 order books)
+    let binance_depth_feed =
+        adapters::inbound::feed_handler::binance_depth::BinanceDepthFeedHandler::new(
+            adapters::inbound::feed_handler::binance::BinanceConfig::from_settings(&settings),
+            market_data_tx.clone(),
+            binance_symbols.clone(),
+            settings.trading.orderbook_depth as usize,
+        )
+        .with_reconnect_policy(reconnect_policy.clone());
+    feed_supervisor = feed_supervisor.with_feed(std::sync::Arc::new(binance_depth_feed));
+
+    // Start OKX Feed Handler (public market data)
+    info!("🔌 Initializing OKX WebSocket feed handler...");
+    let okx_feed = adapters::inbound::feed_handler::okx::OkxFeedHandler::new_public(
+        adapters::inbound::feed_handler::OkxConfig::from_settings(&settings),
         market_data_tx.clone(),
-        Some(symbols.clone()),
+        okx_instruments.clone(),
+    )
+    .with_reconnect_policy(reconnect_policy.clone());
+    feed_supervisor = feed_supervisor.with_feed(std::sync::Arc::new(okx_feed));
+
+    // Start Kraken feed (trades, ticker, book) when Kraken markets are configured
+    let kraken_symbols = settings
+        .markets
+        .exchange_symbols(&kairos_domain::Exchange::Kraken)?;
+    if !kraken_symbols.is_empty() {
+        info!("🔌 Initializing Kraken WebSocket feed handler...");
+        let kraken_feed = adapters::inbound::feed_handler::kraken::KrakenFeedHandler::new(
+            adapters::inbound::feed_handler::KrakenConfig::from_settings(&settings),
+            market_data_tx.clone(),
+            kraken_symbols.clone(),
+            settings.trading.orderbook_depth as usize,
+        )
+        .with_reconnect_policy(reconnect_policy.clone());
+        feed_supervisor = feed_supervisor.with_feed(std::sync::Arc::new(kraken_feed));
+    }
+
+    // Start OKX private feed (orders, account, positions) when credentials are configured
+    match adapters::inbound::feed_handler::OkxCredentials::from_settings(&settings).and_then(
+        |credentials| {
+            adapters::inbound::feed_handler::okx_private::OkxPrivateFeedHandler::new(
+                credentials,
+                adapters::inbound::feed_handler::OkxConfig::from_settings(&settings),
+                account_event_tx.clone(),
+            )
+            .map(|handler| handler.with_reconnect_policy(reconnect_policy.clone()))
+        },
+    ) {
+        Ok(okx_private_feed) => {
+            feed_supervisor = feed_supervisor.with_feed(std::sync::Arc::new(okx_private_feed));
+        }
+        Err(e) => {
+            tracing::warn!("OKX private feed disabled: {}", e);
+        }
+    }
+
+    tokio::spawn({
+        let mut rx = engine.subscribe_account_events();
+        async move {
+            while let Ok(event) = rx.recv().await {
+                info!("🧾 {:?}", event);
+            }
+        }
+    });
+
+    // Order executors per venue: simulated against live market data when
+    // paper trading is enabled, otherwise signed REST clients for every
+    // venue with credentials
+    let mut executor_tasks = Vec::new();
+    if settings.features.enable_paper_trading {
+        for exchange in [
+            kairos_domain::Exchange::Binance,
+            kairos_domain::Exchange::OKX,
+            kairos_domain::Exchange::Kraken,
+        ] {
+            let markets = settings.markets.symbols(&exchange)?;
+            if markets.is_empty() {
+                continue;
+            }
+            let paper = std::sync::Arc::new(adapters::outbound::execution::PaperExecutor::new(
+                exchange,
+                markets,
+                adapters::outbound::execution::PaperConfig::from_settings(&settings),
+                account_event_tx.clone(),
+            ));
+            // Simulated venues start funded with the configured balances
+            for balance in paper.balances() {
+                state.risk_engine.ledger().apply_snapshot(&balance);
+            }
+            executor_tasks.push(tokio::spawn({
+                let paper = paper.clone();
+                let rx = market_data_tx.subscribe();
+                let shutdown = stop_data.clone();
+                async move { paper.run(rx, shutdown).await }
+            }));
+            engine = engine.with_executor(paper);
+        }
+    } else {
+        if let Ok(credentials) =
+            adapters::inbound::feed_handler::binance::BinanceCredentials::from_settings(&settings)
+        {
+            engine = engine.with_executor(std::sync::Arc::new(
+                adapters::outbound::execution::binance::BinanceExecutor::new(
+                    credentials.api_key,
+                    credentials.api_secret,
+                    settings
+                        .markets
+                        .symbols(&kairos_domain::Exchange::Binance)?,
+                )
+                .with_rest_url(&settings.exchange.binance_rest_url)
+                .with_recv_window(settings.exchange.binance_recv_window_ms),
+            ));
+        }
+        if let Ok(adapters::inbound::feed_handler::OkxCredentials {
+            api_key,
+            api_secret,
+            api_passphrase: Some(passphrase),
+        }) = adapters::inbound::feed_handler::OkxCredentials::from_settings(&settings)
+        {
+            engine = engine.with_executor(std::sync::Arc::new(
+                adapters::outbound::execution::okx::OkxExecutor::new(
+                    api_key,
+                    api_secret,
+                    passphrase,
+                    settings.markets.symbols(&kairos_domain::Exchange::OKX)?,
+                )
+                .with_rest_url(&settings.exchange.okx_rest_url)
+                .with_simulated_trading(settings.exchange.okx_simulated_trading),
+            ));
+        }
+        if let (Some(api_key), Some(api_secret)) = (
+            settings.exchange.kraken_api_key.clone(),
+            settings.exchange.kraken_api_secret.clone(),
+        ) {
+            engine = engine.with_executor(std::sync::Arc::new(
+                adapters::outbound::execution::kraken::KrakenExecutor::new(
+                    api_key,
+                    api_secret,
+                    settings.markets.symbols(&kairos_domain::Exchange::Kraken)?,
+                )
+                .with_rest_url(&settings.exchange.kraken_rest_url),
+            ));
+        }
+    }
+    info!(
+        "🎯 Order executors ({}): {:?}",
+        if settings.features.enable_paper_trading {
+            "paper"
+        } else {
+            "live"
+        },
+        engine.venues()
     );
 
-    // Spawn feed handler task
-    let feed_task = tokio::spawn({
+    // 4. gRPC server: external order entry and balance queries
+    let mut grpc_task = tokio::spawn(adapters::inbound::grpc_server::start_grpc_server(
+        settings.grpc_address(),
+        state.risk_engine.ledger(),
+        engine.get_order_sender(),
+        engine.market_subscriptions(),
+        stop_trading.clone(),
+    ));
+
+    // 5. Persistence: batched tick and order writes to TimescaleDB
+    let persistence_task = match &settings.database.timescale_url {
+        Some(url) => {
+            match adapters::outbound::persistence::timescale::TimescaleClient::new(url).await {
+                Ok(client) => Some(tokio::spawn(
+                    adapters::outbound::persistence::PersistenceWriter::new(
+                        client,
+                        state.orders.clone(),
+                        adapters::outbound::persistence::WriterConfig::from_settings(&settings),
+                    )
+                    .run(
+                        engine.subscribe_market_data(),
+                        engine.subscribe_account_events(),
+                        stop_data.clone(),
+                    ),
+                )),
+                Err(e) => {
+                    tracing::warn!("⚠️  Persistence disabled: {}", e);
+                    None
+                }
+            }
+        }
+        None => {
+            info!("💾 Persistence disabled (database.timescale_url not set)");
+            None
+        }
+    };
+
+    // 6. Strategies, each on its own task, publishing orders to the engine
+    let mut strategy_runtime = application::strategy_runtime::StrategyRuntime::new(&engine);
+    for name in &settings.strategies.enabled {
+        strategy_runtime = strategy_runtime.with_strategy(
+            application::strategy_runtime::build_strategy(name, &settings)?,
+        );
+    }
+    info!("🧠 Strategies: {:?}", strategy_runtime.names());
+    let strategy_tasks = strategy_runtime.spawn(stop_trading.clone());
+
+    // 7. Engine loop: strategy orders -> risk -> executors, account events -> OMS/ledger
+    let mut engine_task = tokio::spawn(engine.run(stop_engine.clone()));
+
+    info!("🚀 Starting feed handlers...");
+    let feeds = std::sync::Arc::new(feed_supervisor.spawn());
+
+    // Periodic per-feed health report
+    let health_task = tokio::spawn({
+        let feeds = feeds.clone();
+        let shutdown = stop_data.clone();
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+            settings.monitoring.metrics_interval_sec,
+        ));
         async move {
-            info!("🚀 Starting Binance feed handler...");
-            if let Err(e) = binance_feed.start().await {
-                tracing::error!("❌ Binance feed handler error: {:?}", e);
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                for status in feeds.statuses() {
+                    info!(
+                        "🩺 {} | connected: {} | last msg: {} | {:.1} msg/s | reconnects: {} | restarts: {}",
+                        status.name,
+                        status.connected,
+                        status
+                            .last_message_age
+                            .map(|age| format!("{}ms ago", age.as_millis()))
+                            .unwrap_or_else(|| "never".to_string()),
+                        status.messages_per_sec,
+                        status.reconnects,
+                        status.restarts
+                    );
+                }
             }
         }
     });
 
-    // 4. Start consumer task to display real-time prices
-    let price_monitor_task = tokio::spawn({
+    // 8. Start consumer task to display real-time prices
+    let mut price_monitor_task = tokio::spawn({
         let mut rx = market_data_tx.subscribe();
+        let shutdown = stop_data.clone();
         async move {
             info!("👁️  Starting price monitor...");
-            while let Ok(tick) = rx.recv().await {
-                info!(
-                    "📊 {} | ${:.2} | Vol: {:.4} | {}",
-                    tick.symbol,
-                    tick.price,
-                    tick.volume,
-                    tick.timestamp.format("%H:%M:%S%.3f")
-                );
+            loop {
+                let event = tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    event = rx.recv() => match event {
+                        Ok(event) => event,
+                        Err(_) => break,
+                    },
+                };
+                match event {
+                    kairos_domain::MarketEvent::Trade(tick) => info!(
+                        "📊 {:?} {} | ${:.2} | Vol: {:.4} | {} | latency {}ms",
+                        tick.exchange,
+                        tick.symbol,
+                        tick.price,
+                        tick.volume,
+                        tick.timestamp.format("%H:%M:%S%.3f"),
+                        tick.latency().num_milliseconds()
+                    ),
+                    kairos_domain::MarketEvent::Quote(quote) => tracing::debug!(
+                        "💱 {:?} {} | bid {:.2} x {:.4} | ask {:.2} x {:.4}",
+                        quote.exchange,
+                        quote.symbol,
+                        quote.bid_price,
+                        quote.bid_quantity,
+                        quote.ask_price,
+                        quote.ask_quantity
+                    ),
+                    other => tracing::trace!("{:?} event for {}", other.kind(), other.symbol()),
+                }
             }
         }
     });
 
-    // TODO: Continue with remaining components
-    // 3. Start Persistence Layer (The Logger)
-    // 4. Start Strategies (The Sprinters)
-    // 5. Create MPSC channel for orders
-    // 6. Start Risk Engine (The Gatekeeper)
-    // 7. Start Execution Engine (The Sniper)
-    // 8. Start gRPC Server for external communication
-
     info!("✅ KAIRÓS Core initialized successfully");
-    info!("📡 Listening for market data from Binance...");
-    info!("🎯 Tracking symbols: {:?}", symbols);
+    info!("📡 Listening for market data from Binance and OKX...");
+    info!("🎯 Tracking Binance symbols: {:?}", binance_symbols);
+    info!("🎯 Tracking OKX instruments: {:?}", okx_instruments);
+    if !kraken_symbols.is_empty() {
+        info!("🎯 Tracking Kraken symbols: {:?}", kraken_symbols);
+    }
 
     // Keep the main task alive
     tokio::select! {
         _ = tokio::signal::ctrl_c() => {
             info!("🛑 Received shutdown signal, shutting down KAIRÓS Core...");
         }
-        _ = feed_task => {
-            tracing::error!("Feed handler task terminated unexpectedly");
-        }
-        _ = price_monitor_task => {
+        _ = &mut price_monitor_task => {
             tracing::error!("Price monitor task terminated unexpectedly");
         }
+        result = &mut engine_task => {
+            tracing::error!("Trading engine stopped: {:?}", result);
+        }
+        result = &mut grpc_task => {
+            tracing::error!("gRPC server stopped: {:?}", result);
+        }
+    }
+
+    let deadline =
+        tokio::time::Instant::now() + std::time::Duration::from_secs(settings.shutdown.timeout_sec);
+    let mut unfinished = Vec::new();
+
+    // Stage 1: stop strategies (on_stop may still cancel) and external order entry
+    stop_trading.cancel();
+    for task in strategy_tasks {
+        unfinished.extend(await_shutdown("strategy", task, deadline).await);
+    }
+    unfinished.extend(await_shutdown("gRPC server", grpc_task, deadline).await);
+
+    // Stage 2: no new orders; the engine settles in-flight orders and cancels open ones
+    stop_engine.cancel();
+    unfinished.extend(await_shutdown("trading engine", engine_task, deadline).await);
+
+    // Stage 3: stop market data and venues, then flush persistence
+    stop_data.cancel();
+    feeds.stop_all();
+    for task in executor_tasks {
+        unfinished.extend(await_shutdown("paper executor", task, deadline).await);
+    }
+    unfinished.extend(await_shutdown("feed health report", health_task, deadline).await);
+    unfinished.extend(await_shutdown("price monitor", price_monitor_task, deadline).await);
+    if let Ok(feeds) = std::sync::Arc::try_unwrap(feeds) {
+        unfinished.extend(await_shutdown("feeds", tokio::spawn(feeds.shutdown()), deadline).await);
+    }
+    if let Some(task) = persistence_task {
+        unfinished.extend(await_shutdown("persistence writer", task, deadline).await);
+    }
+
+    if unfinished.is_empty() {
+        info!("👋 KAIRÓS Core stopped cleanly");
+    } else {
+        tracing::warn!(
+            "⚠️  Shutdown deadline of {}s reached, abandoned: {}",
+            settings.shutdown.timeout_sec,
+            unfinished.join(", ")
+        );
     }
 
     Ok(())
 }
+
+/// Wait for a task to finish until `deadline`, aborting it otherwise
+///
+/// Returns the task's name when it had to be abandoned.
+async fn await_shutdown<T>(
+    name: &'static str,
+    task: tokio::task::JoinHandle<T>,
+    deadline: tokio::time::Instant,
+) -> Option<&'static str> {
+    // Tasks that already ended (e.g. the one that triggered shutdown)
+    if task.is_finished() {
+        return None;
+    }
+
+    let abort = task.abort_handle();
+    match tokio::time::timeout_at(deadline, task).await {
+        Ok(_) => None,
+        Err(_) => {
+            abort.abort();
+            tracing::warn!("⚠️  {} did not stop before the shutdown deadline", name);
+            Some(name)
+        }
+    }
+}
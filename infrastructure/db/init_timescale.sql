@@ -5,13 +5,18 @@
 CREATE EXTENSION IF NOT EXISTS timescaledb;
 
 -- Market ticks table
+-- time        = exchange event time of the trade
+-- received_at = local receive time (received_at - time = feed latency)
 CREATE TABLE IF NOT EXISTS market_ticks (
     time TIMESTAMPTZ NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL,
     symbol VARCHAR(20) NOT NULL,
     exchange VARCHAR(20) NOT NULL,
     price DOUBLE PRECISION NOT NULL,
     volume DOUBLE PRECISION NOT NULL,
-    PRIMARY KEY (time, symbol, exchange)
+    trade_id VARCHAR(64) NOT NULL DEFAULT '',
+    side VARCHAR(4),
+    PRIMARY KEY (time, symbol, exchange, trade_id)
 );
 
 -- Convert to hypertable for time-series optimization
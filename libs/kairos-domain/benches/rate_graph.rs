@@ -0,0 +1,112 @@
+// Cycle detection latency on venue-sized currency graphs
+//
+// Run with `cargo bench -p kairos-domain --bench rate_graph`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use kairos_domain::{Exchange, RateGraph, Symbol};
+
+const TAKER_FEE_BPS: f64 = 10.0;
+const SPREAD: f64 = 0.0005;
+
+/// Small deterministic generator, so every run benches the same graph
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0 >> 33
+    }
+
+    fn unit(&mut self) -> f64 {
+        self.next() as f64 / (1u64 << 31) as f64
+    }
+}
+
+/// Fairly priced graph of `pairs` instruments over `currencies` assets
+///
+/// Every quote is consistent with one USD value per currency, so the only
+/// profitable cycles are the ones a benchmark plants.
+fn market(currencies: usize, pairs: usize) -> (RateGraph, Vec<(Symbol, f64)>) {
+    let mut rng = Lcg(42);
+    let values: Vec<f64> = (0..currencies)
+        .map(|_| 10f64.powf(rng.unit() * 6.0 - 2.0))
+        .collect();
+
+    let mut graph = RateGraph::new(Exchange::Binance, TAKER_FEE_BPS);
+    let mut quoted = Vec::with_capacity(pairs);
+    while quoted.len() < pairs {
+        let base = rng.next() as usize % currencies;
+        let quote = rng.next() as usize % currencies;
+        let symbol = Symbol::new(format!("C{}", base), format!("C{}", quote));
+        if base == quote || quoted.iter().any(|(existing, _)| *existing == symbol) {
+            continue;
+        }
+        let mid = values[base] / values[quote];
+        graph.update_quote(
+            &symbol,
+            mid * (1.0 - SPREAD),
+            1.0,
+            mid * (1.0 + SPREAD),
+            1.0,
+        );
+        quoted.push((symbol, mid));
+    }
+    (graph, quoted)
+}
+
+/// Quote `symbol` 5% under its fair price, opening cycles through it
+fn mispriced(graph: &mut RateGraph, symbol: &Symbol, mid: f64) -> [usize; 2] {
+    let cheap = mid * 0.95;
+    graph.update_quote(
+        symbol,
+        cheap * (1.0 - SPREAD),
+        1.0,
+        cheap * (1.0 + SPREAD),
+        1.0,
+    )
+}
+
+fn bench_full_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rate_graph/full_scan");
+    for (currencies, pairs) in [(30, 100), (60, 300), (80, 500)] {
+        let (mut graph, quoted) = market(currencies, pairs);
+        let (symbol, mid) = &quoted[0];
+        mispriced(&mut graph, symbol, *mid);
+
+        for max_len in [3, 4] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("max_len_{}", max_len), pairs),
+                &graph,
+                |b, graph| b.iter(|| black_box(graph.find_cycles(max_len, 0.0))),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_incremental(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rate_graph/quote_update");
+    for (currencies, pairs) in [(30, 100), (60, 300), (80, 500)] {
+        let (mut graph, quoted) = market(currencies, pairs);
+        let (symbol, mid) = quoted[0].clone();
+
+        // What the strategy does per quote: update two edges, check both
+        group.bench_function(BenchmarkId::new("max_len_3", pairs), |b| {
+            b.iter(|| {
+                let edges = mispriced(&mut graph, &symbol, mid);
+                let found = edges
+                    .iter()
+                    .filter_map(|&edge| graph.find_cycle_through(edge, 3, 0.0))
+                    .max_by(|a, b| a.profit.total_cmp(&b.profit));
+                black_box(found)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_full_scan, bench_incremental);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+use crate::models::OrderState;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -28,6 +29,22 @@ pub enum DomainError {
 
     #[error("Invalid quantity: {0}")]
     InvalidQuantity(String),
+
+    #[error("Invalid notional: {0}")]
+    InvalidNotional(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Order '{0}' not found")]
+    OrderNotFound(String),
+
+    #[error("Invalid order state transition for {order_id}: {from:?} -> {to:?}")]
+    InvalidStateTransition {
+        order_id: String,
+        from: OrderState,
+        to: OrderState,
+    },
 }
 
 pub type DomainResult<T> = Result<T, DomainError>;
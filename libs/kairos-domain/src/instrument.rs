@@ -0,0 +1,320 @@
+// Instrument reference data: tick/lot sizes, minimums and precision per venue
+
+use crate::errors::{DomainError, DomainResult};
+use crate::models::{Exchange, InternalOrder, OrderSide};
+use crate::numeric::{Notional, Price, Quantity};
+use crate::symbol::Symbol;
+use rust_decimal::RoundingStrategy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Trading rules for a single instrument on a single exchange
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Instrument {
+    pub exchange: Exchange,
+    pub symbol: Symbol,
+    /// Exchange-native symbol (e.g. "BTCUSDT", "BTC-USDT")
+    pub venue_symbol: String,
+    /// Minimum price increment
+    pub tick_size: Price,
+    /// Minimum quantity increment
+    pub lot_size: Quantity,
+    /// Smallest order quantity accepted
+    pub min_quantity: Quantity,
+    /// Smallest order value (price * quantity) in quote currency, 0 if none
+    pub min_notional: Notional,
+    /// Decimal places of `tick_size`
+    pub price_precision: u32,
+    /// Decimal places of `lot_size`
+    pub quantity_precision: u32,
+}
+
+impl Instrument {
+    /// Build an instrument, deriving the venue symbol and precisions
+    pub fn new(
+        exchange: Exchange,
+        symbol: Symbol,
+        tick_size: Price,
+        lot_size: Quantity,
+        min_quantity: Quantity,
+        min_notional: Notional,
+    ) -> Self {
+        Self {
+            venue_symbol: symbol.to_exchange(&exchange),
+            exchange,
+            symbol,
+            tick_size,
+            lot_size,
+            min_quantity,
+            min_notional,
+            price_precision: tick_size.precision(),
+            quantity_precision: lot_size.precision(),
+        }
+    }
+
+    /// Round a price onto the tick grid, never in the order's disfavour
+    /// (buys round down, sells round up)
+    pub fn round_price(&self, price: Price, side: &OrderSide) -> Price {
+        let strategy = match side {
+            OrderSide::Buy => RoundingStrategy::ToNegativeInfinity,
+            OrderSide::Sell => RoundingStrategy::ToPositiveInfinity,
+        };
+        price.round_to_step(self.tick_size, strategy)
+    }
+
+    /// Round a quantity down onto the lot grid
+    pub fn round_quantity(&self, quantity: Quantity) -> Quantity {
+        quantity.round_to_step(self.lot_size, RoundingStrategy::ToZero)
+    }
+
+    /// Check a price is positive and on the tick grid
+    pub fn validate_price(&self, price: Price) -> DomainResult<()> {
+        if !price.is_positive() {
+            return Err(DomainError::InvalidPrice(format!(
+                "{} price must be positive, got {}",
+                self.symbol, price
+            )));
+        }
+        if !price.is_multiple_of(self.tick_size) {
+            return Err(DomainError::InvalidPrice(format!(
+                "{} price {} is not a multiple of tick size {}",
+                self.symbol, price, self.tick_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check a quantity meets the minimum and is on the lot grid
+    pub fn validate_quantity(&self, quantity: Quantity) -> DomainResult<()> {
+        if !quantity.is_positive() || quantity < self.min_quantity {
+            return Err(DomainError::InvalidQuantity(format!(
+                "{} quantity {} is below minimum {}",
+                self.symbol, quantity, self.min_quantity
+            )));
+        }
+        if !quantity.is_multiple_of(self.lot_size) {
+            return Err(DomainError::InvalidQuantity(format!(
+                "{} quantity {} is not a multiple of lot size {}",
+                self.symbol, quantity, self.lot_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate price, quantity and minimum notional of an order
+    ///
+    /// Market orders (`price == None`) skip the price and notional checks.
+    pub fn validate_order(&self, order: &InternalOrder) -> DomainResult<()> {
+        self.validate_quantity(order.quantity)?;
+
+        if let Some(price) = order.price {
+            self.validate_price(price)?;
+
+            let notional = price * order.quantity;
+            if notional < self.min_notional {
+                return Err(DomainError::ValidationFailed(format!(
+                    "{} notional {} is below minimum {}",
+                    self.symbol, notional, self.min_notional
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Round an order onto the instrument's grids and validate the result
+    pub fn normalize_order(&self, order: &InternalOrder) -> DomainResult<InternalOrder> {
+        let normalized = InternalOrder {
+            quantity: self.round_quantity(order.quantity),
+            price: order
+                .price
+                .map(|price| self.round_price(price, &order.side)),
+            ..order.clone()
+        };
+        self.validate_order(&normalized)?;
+        Ok(normalized)
+    }
+}
+
+/// Lookup of instruments by exchange and canonical or venue symbol
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentRegistry {
+    instruments: HashMap<(Exchange, Symbol), Instrument>,
+    venue_symbols: HashMap<(Exchange, String), Symbol>,
+}
+
+impl InstrumentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace an instrument
+    pub fn insert(&mut self, instrument: Instrument) {
+        self.venue_symbols.insert(
+            (instrument.exchange.clone(), instrument.venue_symbol.clone()),
+            instrument.symbol.clone(),
+        );
+        self.instruments.insert(
+            (instrument.exchange.clone(), instrument.symbol.clone()),
+            instrument,
+        );
+    }
+
+    /// Instrument for a canonical symbol (e.g. "BTC/USDT")
+    pub fn get(&self, exchange: &Exchange, symbol: &Symbol) -> Option<&Instrument> {
+        self.instruments.get(&(exchange.clone(), symbol.clone()))
+    }
+
+    /// Instrument for an exchange-native symbol (e.g. "BTCUSDT")
+    pub fn get_by_venue_symbol(
+        &self,
+        exchange: &Exchange,
+        venue_symbol: &str,
+    ) -> Option<&Instrument> {
+        self.venue_symbols
+            .get(&(exchange.clone(), venue_symbol.to_uppercase()))
+            .and_then(|symbol| self.get(exchange, symbol))
+    }
+
+    /// Resolve an order's symbol, accepting either canonical or venue form
+    pub fn resolve(&self, exchange: &Exchange, symbol: &str) -> DomainResult<&Instrument> {
+        symbol
+            .parse::<Symbol>()
+            .ok()
+            .and_then(|canonical| self.get(exchange, &canonical))
+            .or_else(|| self.get_by_venue_symbol(exchange, symbol))
+            .ok_or_else(|| DomainError::SymbolNotFound {
+                symbol: format!("{:?}:{}", exchange, symbol),
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.instruments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instruments.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Instrument> {
+        self.instruments.values()
+    }
+
+    /// Serialize all instruments (for the offline cache)
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let mut instruments: Vec<&Instrument> = self.iter().collect();
+        instruments.sort_by(|a, b| (&a.exchange, &a.symbol).cmp(&(&b.exchange, &b.symbol)));
+        serde_json::to_string_pretty(&instruments)
+    }
+
+    /// Load instruments written by [`InstrumentRegistry::to_json`]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let instruments: Vec<Instrument> = serde_json::from_str(json)?;
+        Ok(instruments.into_iter().collect())
+    }
+}
+
+impl FromIterator<Instrument> for InstrumentRegistry {
+    fn from_iter<I: IntoIterator<Item = Instrument>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for instrument in iter {
+            registry.insert(instrument);
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn btc_usdt() -> Instrument {
+        Instrument::new(
+            Exchange::Binance,
+            Symbol::new("BTC", "USDT"),
+            dec("0.01"),
+            dec("0.00001"),
+            dec("0.00001"),
+            dec("5"),
+        )
+    }
+
+    fn dec<T: std::str::FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    fn order(side: OrderSide, quantity: &str, price: Option<&str>) -> InternalOrder {
+        InternalOrder {
+            symbol: "BTCUSDT".to_string(),
+            side,
+            quantity: dec(quantity),
+            price: price.map(dec),
+            risk_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_precision_and_rounding() {
+        let instrument = btc_usdt();
+        assert_eq!(instrument.venue_symbol, "BTCUSDT");
+        assert_eq!(instrument.price_precision, 2);
+        assert_eq!(instrument.quantity_precision, 5);
+
+        assert_eq!(
+            instrument.round_price(dec("100.129"), &OrderSide::Buy),
+            dec("100.12")
+        );
+        assert_eq!(
+            instrument.round_price(dec("100.121"), &OrderSide::Sell),
+            dec("100.13")
+        );
+        assert_eq!(
+            instrument.round_price(dec("100.12"), &OrderSide::Sell),
+            dec("100.12")
+        );
+        assert_eq!(instrument.round_quantity(dec("0.123456")), dec("0.12345"));
+    }
+
+    #[test]
+    fn test_normalize_and_validate_order() {
+        let instrument = btc_usdt();
+
+        let normalized = instrument
+            .normalize_order(&order(OrderSide::Buy, "0.123456", Some("100.129")))
+            .unwrap();
+        assert_eq!(normalized.quantity, dec("0.12345"));
+        assert_eq!(normalized.price, Some(dec("100.12")));
+
+        // Off-grid values are rejected without normalization
+        assert!(instrument
+            .validate_order(&order(OrderSide::Buy, "0.123456", Some("100")))
+            .is_err());
+        // 0.00001 * 100 = 0.001 USDT, below 5 USDT minimum notional
+        assert!(matches!(
+            instrument.normalize_order(&order(OrderSide::Buy, "0.00001", Some("100"))),
+            Err(DomainError::ValidationFailed(_))
+        ));
+        // Market orders skip the notional check
+        assert!(instrument
+            .normalize_order(&order(OrderSide::Sell, "0.00001", None))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_registry_lookup_and_json_round_trip() {
+        let registry: InstrumentRegistry = [btc_usdt()].into_iter().collect();
+
+        assert!(registry.resolve(&Exchange::Binance, "BTC/USDT").is_ok());
+        assert!(registry.resolve(&Exchange::Binance, "btcusdt").is_ok());
+        assert!(registry.resolve(&Exchange::OKX, "BTC/USDT").is_err());
+
+        let restored = InstrumentRegistry::from_json(&registry.to_json().unwrap()).unwrap();
+        assert_eq!(
+            restored.get(&Exchange::Binance, &Symbol::new("BTC", "USDT")),
+            Some(&btc_usdt())
+        );
+    }
+}
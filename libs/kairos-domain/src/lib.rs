@@ -1,7 +1,19 @@
 // Common domain entities shared across the KAIRÓS platform
 
 pub mod errors;
+pub mod instrument;
 pub mod models;
+pub mod numeric;
+pub mod orderbook;
+pub mod position;
+pub mod rate_graph;
+pub mod symbol;
 
 pub use errors::*;
+pub use instrument::*;
 pub use models::*;
+pub use numeric::*;
+pub use orderbook::*;
+pub use position::*;
+pub use rate_graph::*;
+pub use symbol::*;
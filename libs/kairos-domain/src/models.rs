@@ -1,5 +1,10 @@
+use crate::errors::DomainError;
+use crate::numeric::{Amount, Notional, Price, Quantity};
+use crate::orderbook::OrderBookDelta;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// Represents a market tick (price update)
@@ -7,20 +12,189 @@ use uuid::Uuid;
 pub struct MarketTick {
     pub id: Uuid,
     pub symbol: String,
-    pub price: f64,
-    pub volume: f64,
+    pub price: Price,
+    pub volume: Quantity,
+    /// Exchange event time of the trade
     pub timestamp: DateTime<Utc>,
+    /// Local time the tick was received from the exchange
+    pub received_at: DateTime<Utc>,
     pub exchange: Exchange,
+    /// Exchange trade ID (None for ticker-derived ticks)
+    pub trade_id: Option<String>,
+    /// Aggressor (taker) side, when the exchange reports it
+    pub side: Option<OrderSide>,
+}
+
+impl MarketTick {
+    /// Time between the exchange event and local receipt
+    pub fn latency(&self) -> chrono::Duration {
+        self.received_at - self.timestamp
+    }
+}
+
+/// Best bid/offer (top of book) update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quote {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub bid_price: Price,
+    pub bid_quantity: Quantity,
+    pub ask_price: Price,
+    pub ask_quantity: Quantity,
+    pub timestamp: DateTime<Utc>,
+    pub received_at: DateTime<Utc>,
+}
+
+impl Quote {
+    pub fn mid(&self) -> Price {
+        Price::new((self.bid_price + self.ask_price).value() / Decimal::TWO)
+    }
+}
+
+/// OHLCV candle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Kline {
+    pub exchange: Exchange,
+    pub symbol: String,
+    /// Candle interval as reported by the exchange (e.g. "1m", "1h")
+    pub interval: String,
+    pub open_time: DateTime<Utc>,
+    pub close_time: DateTime<Utc>,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    pub volume: Quantity,
+    /// False while the candle is still being updated
+    pub is_closed: bool,
+}
+
+/// Perpetual swap funding rate update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingRate {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub rate: f64,
+    pub next_funding_time: Option<DateTime<Utc>>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Event published on the market data bus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MarketEvent {
+    Trade(MarketTick),
+    Quote(Quote),
+    BookDelta(OrderBookDelta),
+    Kline(Kline),
+    FundingRate(FundingRate),
+}
+
+/// Discriminant of a [`MarketEvent`], used for filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarketEventKind {
+    Trade,
+    Quote,
+    BookDelta,
+    Kline,
+    FundingRate,
+}
+
+impl MarketEvent {
+    pub fn kind(&self) -> MarketEventKind {
+        match self {
+            MarketEvent::Trade(_) => MarketEventKind::Trade,
+            MarketEvent::Quote(_) => MarketEventKind::Quote,
+            MarketEvent::BookDelta(_) => MarketEventKind::BookDelta,
+            MarketEvent::Kline(_) => MarketEventKind::Kline,
+            MarketEvent::FundingRate(_) => MarketEventKind::FundingRate,
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        match self {
+            MarketEvent::Trade(e) => &e.symbol,
+            MarketEvent::Quote(e) => &e.symbol,
+            MarketEvent::BookDelta(e) => &e.symbol,
+            MarketEvent::Kline(e) => &e.symbol,
+            MarketEvent::FundingRate(e) => &e.symbol,
+        }
+    }
+
+    pub fn exchange(&self) -> &Exchange {
+        match self {
+            MarketEvent::Trade(e) => &e.exchange,
+            MarketEvent::Quote(e) => &e.exchange,
+            MarketEvent::BookDelta(e) => &e.exchange,
+            MarketEvent::Kline(e) => &e.exchange,
+            MarketEvent::FundingRate(e) => &e.exchange,
+        }
+    }
+}
+
+/// Selects the market events a consumer cares about
+///
+/// An empty kind or symbol list matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct MarketEventFilter {
+    kinds: Vec<MarketEventKind>,
+    symbols: Vec<String>,
+}
+
+impl MarketEventFilter {
+    /// Filter matching every event
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Filter matching only the given event kinds
+    pub fn kinds(kinds: &[MarketEventKind]) -> Self {
+        Self {
+            kinds: kinds.to_vec(),
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Restricts the filter to the given symbols
+    pub fn with_symbols<I, S>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.symbols = symbols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn matches(&self, event: &MarketEvent) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&event.kind()))
+            && (self.symbols.is_empty() || self.symbols.iter().any(|s| s == event.symbol()))
+    }
 }
 
 /// Supported exchanges
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Exchange {
     Binance,
     OKX,
     Kraken,
 }
 
+impl FromStr for Exchange {
+    type Err = DomainError;
+
+    /// Parse a venue name, ignoring case (e.g. "okx", "Binance")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "binance" => Ok(Exchange::Binance),
+            "okx" => Ok(Exchange::OKX),
+            "kraken" => Ok(Exchange::Kraken),
+            _ => Err(DomainError::ExchangeError(format!(
+                "unknown exchange '{}'",
+                s
+            ))),
+        }
+    }
+}
+
 /// Represents a trading order
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
@@ -28,8 +202,8 @@ pub struct Order {
     pub symbol: String,
     pub side: OrderSide,
     pub order_type: OrderType,
-    pub quantity: f64,
-    pub price: Option<f64>,
+    pub quantity: Quantity,
+    pub price: Option<Price>,
     pub status: OrderStatus,
     pub created_at: DateTime<Utc>,
 }
@@ -46,6 +220,18 @@ pub enum OrderType {
     Limit,
 }
 
+/// How long a limit order stays on the book
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TimeInForce {
+    /// Good till cancelled
+    #[default]
+    Gtc,
+    /// Immediate or cancel: fill what is possible, cancel the rest
+    Ioc,
+    /// Fill or kill: fill completely or cancel
+    Fok,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum OrderStatus {
     Pending,
@@ -53,6 +239,73 @@ pub enum OrderStatus {
     Rejected,
     Executed,
     Cancelled,
+    /// Closed by the venue on its time-in-force or expiry time, not by a cancel
+    Expired,
+}
+
+/// Lifecycle of an order owned by the order management system
+///
+/// ```text
+/// New -> Sent -> Acknowledged -> PartiallyFilled -> Filled
+///   |       |         |                |
+///   |       |         +-> CancelPending <+ -> Cancelled
+///   +-------+-> Rejected           (any live state) -> Expired
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OrderState {
+    /// Created from a strategy intent, not yet sent
+    New,
+    /// Submitted to the venue, no acknowledgement yet
+    Sent,
+    /// Accepted by the venue and working
+    Acknowledged,
+    PartiallyFilled,
+    Filled,
+    /// Cancel requested, venue has not confirmed
+    CancelPending,
+    Cancelled,
+    Rejected,
+    Expired,
+}
+
+impl OrderState {
+    /// True once the order can no longer change
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderState::Filled | OrderState::Cancelled | OrderState::Rejected | OrderState::Expired
+        )
+    }
+
+    /// Whether the lifecycle allows moving from `self` to `next`
+    ///
+    /// Fills and cancels may race the venue's acknowledgement, so `Sent` can
+    /// jump straight to any venue outcome; a rejected cancel returns a
+    /// `CancelPending` order to `Acknowledged` or `PartiallyFilled`.
+    pub fn can_transition_to(&self, next: OrderState) -> bool {
+        use OrderState::*;
+
+        matches!(
+            (self, next),
+            (New, Sent | Rejected)
+                | (
+                    Sent,
+                    Acknowledged | PartiallyFilled | Filled | Cancelled | Rejected | Expired
+                )
+                | (
+                    Acknowledged,
+                    PartiallyFilled | Filled | CancelPending | Cancelled | Expired
+                )
+                | (
+                    PartiallyFilled,
+                    PartiallyFilled | Filled | CancelPending | Cancelled | Expired
+                )
+                | (
+                    CancelPending,
+                    Acknowledged | PartiallyFilled | Filled | Cancelled | Expired
+                )
+        )
+    }
 }
 
 /// Internal order representation used by strategies
@@ -60,7 +313,79 @@ pub enum OrderStatus {
 pub struct InternalOrder {
     pub symbol: String,
     pub side: OrderSide,
-    pub quantity: f64,
-    pub price: Option<f64>,
+    pub quantity: Quantity,
+    /// Limit price (`None` = market)
+    pub price: Option<Price>,
     pub risk_score: f64,
 }
+
+/// Whether a fill added liquidity to the book or took it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// Execution of (part of) an order reported by an exchange
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fill {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub exchange_order_id: String,
+    pub client_order_id: Option<String>,
+    pub trade_id: String,
+    pub side: OrderSide,
+    pub price: Price,
+    pub quantity: Quantity,
+    /// Fee charged for this fill (positive = paid, negative = rebate)
+    pub fee: Amount,
+    pub fee_currency: String,
+    /// Maker/taker flag, when the exchange reports it
+    pub liquidity: Option<Liquidity>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Order state change reported by an exchange
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderUpdate {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub exchange_order_id: String,
+    pub client_order_id: Option<String>,
+    pub side: OrderSide,
+    pub status: OrderStatus,
+    pub filled_quantity: Quantity,
+    pub average_price: Option<Price>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Balance of a single currency on an exchange account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceUpdate {
+    pub exchange: Exchange,
+    pub currency: String,
+    pub available: Amount,
+    pub locked: Amount,
+    pub total: Amount,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Open position on an exchange account (signed quantity: negative = short)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionUpdate {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub quantity: Quantity,
+    pub average_price: Option<Price>,
+    pub unrealized_pnl: Notional,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Private account events streamed from exchanges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AccountEvent {
+    OrderUpdate(OrderUpdate),
+    Fill(Fill),
+    Balance(BalanceUpdate),
+    Position(PositionUpdate),
+}
@@ -0,0 +1,335 @@
+// Fixed-point price, quantity and notional types
+//
+// Backed by `rust_decimal::Decimal` (96-bit integer mantissa + scale), so
+// values parsed from exchange strings round-trip exactly and tick/lot
+// rounding has no binary floating point error.
+
+use crate::errors::{DomainError, DomainResult};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+macro_rules! decimal_newtype {
+    ($(#[$meta:meta])* $name:ident, $error:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(Decimal);
+
+        impl $name {
+            pub const ZERO: Self = Self(Decimal::ZERO);
+
+            pub const fn new(value: Decimal) -> Self {
+                Self(value)
+            }
+
+            pub fn value(&self) -> Decimal {
+                self.0
+            }
+
+            /// Lossy conversion for analytics and wire formats that carry doubles
+            pub fn to_f64(&self) -> f64 {
+                self.0.to_f64().unwrap_or(0.0)
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+
+            pub fn is_positive(&self) -> bool {
+                self.0.is_sign_positive() && !self.0.is_zero()
+            }
+
+            pub fn abs(&self) -> Self {
+                Self(self.0.abs())
+            }
+
+            /// Decimal places as written (e.g. "0.010" -> 3)
+            pub fn scale(&self) -> u32 {
+                self.0.scale()
+            }
+
+            /// Decimal places once trailing zeros are dropped (e.g. "0.010" -> 2)
+            pub fn precision(&self) -> u32 {
+                self.0.normalize().scale()
+            }
+
+            /// Round onto a multiple of `step` (unchanged when `step` is zero)
+            pub fn round_to_step(&self, step: Self, strategy: RoundingStrategy) -> Self {
+                if step.0.is_zero() {
+                    return *self;
+                }
+                let steps = (self.0 / step.0).round_dp_with_strategy(0, strategy);
+                Self((steps * step.0).normalize())
+            }
+
+            /// Whether the value is an exact multiple of `step`
+            pub fn is_multiple_of(&self, step: Self) -> bool {
+                step.0.is_zero() || (self.0 % step.0).is_zero()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = DomainError;
+
+            /// Parse an exchange string such as "67432.10" or "1e-8"
+            fn from_str(s: &str) -> DomainResult<Self> {
+                let s = s.trim();
+                Decimal::from_str(s)
+                    .or_else(|_| Decimal::from_scientific(s))
+                    .map(Self)
+                    .map_err(|_| DomainError::$error(format!("cannot parse '{}'", s)))
+            }
+        }
+
+        impl TryFrom<f64> for $name {
+            type Error = DomainError;
+
+            /// Convert a double (e.g. from a proto message) via its shortest
+            /// round-trip representation, so 0.1 becomes exactly 0.1
+            fn try_from(value: f64) -> DomainResult<Self> {
+                if !value.is_finite() {
+                    return Err(DomainError::$error(format!("{} is not finite", value)));
+                }
+                value.to_string().parse()
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(value: $name) -> f64 {
+                value.to_f64()
+            }
+        }
+
+        impl From<Decimal> for $name {
+            fn from(value: Decimal) -> Self {
+                Self(value)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|value| value.0).sum())
+            }
+        }
+    };
+}
+
+decimal_newtype!(
+    /// Price of one unit of base asset, in quote currency
+    Price,
+    InvalidPrice
+);
+
+decimal_newtype!(
+    /// Amount of base asset
+    Quantity,
+    InvalidQuantity
+);
+
+decimal_newtype!(
+    /// Value in quote currency (price * quantity)
+    Notional,
+    InvalidNotional
+);
+
+decimal_newtype!(
+    /// Amount of one currency held, reserved or paid (balances, fees)
+    ///
+    /// A base-asset `Quantity` or a quote-currency `Notional` becomes an
+    /// `Amount` once it is booked against a currency.
+    Amount,
+    InvalidAmount
+);
+
+impl From<Quantity> for Amount {
+    fn from(value: Quantity) -> Self {
+        Amount(value.0)
+    }
+}
+
+impl From<Notional> for Amount {
+    fn from(value: Notional) -> Self {
+        Amount(value.0)
+    }
+}
+
+impl Mul<Quantity> for Price {
+    type Output = Notional;
+
+    fn mul(self, rhs: Quantity) -> Notional {
+        Notional(self.0 * rhs.0)
+    }
+}
+
+impl Mul<Price> for Quantity {
+    type Output = Notional;
+
+    fn mul(self, rhs: Price) -> Notional {
+        Notional(self.0 * rhs.0)
+    }
+}
+
+/// Division that returns `None` instead of panicking on a zero divisor
+///
+/// # Example
+/// ```rust,ignore
+/// let average = notional.checked_div(filled).unwrap_or(Price::ZERO);
+/// ```
+pub trait CheckedDiv<Rhs> {
+    type Output;
+
+    fn checked_div(self, rhs: Rhs) -> Option<Self::Output>;
+}
+
+impl CheckedDiv<Price> for Notional {
+    type Output = Quantity;
+
+    fn checked_div(self, rhs: Price) -> Option<Quantity> {
+        self.0.checked_div(rhs.0).map(Quantity)
+    }
+}
+
+impl CheckedDiv<Quantity> for Notional {
+    type Output = Price;
+
+    fn checked_div(self, rhs: Quantity) -> Option<Price> {
+        self.0.checked_div(rhs.0).map(Price)
+    }
+}
+
+impl Div<Price> for Notional {
+    type Output = Quantity;
+
+    /// Quantity affordable at `rhs` (panics on a zero price, like integer division)
+    fn div(self, rhs: Price) -> Quantity {
+        Quantity(self.0 / rhs.0)
+    }
+}
+
+impl Div<Quantity> for Notional {
+    type Output = Price;
+
+    /// Average price paid for `rhs` (panics on a zero quantity, like integer division)
+    fn div(self, rhs: Quantity) -> Price {
+        Price(self.0 / rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exchange_strings_exactly() {
+        let price: Price = "67432.10".parse().unwrap();
+        assert_eq!(price.to_string(), "67432.10");
+        assert_eq!(price.precision(), 1);
+
+        let quantity: Quantity = "1e-8".parse().unwrap();
+        assert_eq!(quantity, "0.00000001".parse().unwrap());
+        assert!(matches!(
+            "abc".parse::<Price>(),
+            Err(DomainError::InvalidPrice(_))
+        ));
+        assert!(Quantity::try_from(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_is_exact() {
+        // 0.1 + 0.2 == 0.3, unlike f64
+        let sum = Quantity::try_from(0.1).unwrap() + Quantity::try_from(0.2).unwrap();
+        assert_eq!(sum, "0.3".parse().unwrap());
+
+        let price: Price = "19.99".parse().unwrap();
+        let quantity: Quantity = "3".parse().unwrap();
+        assert_eq!(price * quantity, "59.97".parse::<Notional>().unwrap());
+        assert_eq!((price * quantity) / price, quantity);
+        assert_eq!((price * quantity) / quantity, price);
+        assert_eq!((price * quantity).checked_div(Quantity::ZERO), None);
+        assert_eq!((price * quantity).checked_div(price), Some(quantity));
+        assert_eq!(
+            Amount::from(price * quantity),
+            "59.97".parse::<Amount>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_to_step() {
+        let tick: Price = "0.01".parse().unwrap();
+        let price: Price = "100.019".parse().unwrap();
+        assert_eq!(
+            price.round_to_step(tick, RoundingStrategy::ToZero),
+            "100.01".parse().unwrap()
+        );
+        assert_eq!(
+            price.round_to_step(tick, RoundingStrategy::AwayFromZero),
+            "100.02".parse().unwrap()
+        );
+
+        let lot: Quantity = "0.001".parse().unwrap();
+        assert!("0.123".parse::<Quantity>().unwrap().is_multiple_of(lot));
+        assert!(!"0.1234".parse::<Quantity>().unwrap().is_multiple_of(lot));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let price: Price = "0.000123".parse().unwrap();
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!(json, "\"0.000123\"");
+        assert_eq!(serde_json::from_str::<Price>(&json).unwrap(), price);
+        // Numbers are accepted too
+        assert_eq!(
+            serde_json::from_str::<Price>("0.5").unwrap(),
+            "0.5".parse().unwrap()
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use crate::models::Exchange;
+use crate::numeric::{Notional, Price, Quantity};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Single price level of an order book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderBookLevel {
+    pub price: Price,
+    pub quantity: Quantity,
+}
+
+impl OrderBookLevel {
+    pub fn new(price: Price, quantity: Quantity) -> Self {
+        Self { price, quantity }
+    }
+}
+
+/// Side of an order book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+/// Incremental order book update (a level with quantity 0 removes that price)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookDelta {
+    pub exchange: Exchange,
+    pub symbol: String,
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+    /// Exchange sequence number of the last change included in this delta
+    pub sequence: u64,
+    /// When true the delta replaces the whole book instead of patching it
+    pub is_snapshot: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// L2 order book with sorted bid/ask sides
+///
+/// Levels are kept in `BTreeMap`s so best prices, depth walks and updates are
+/// all `O(log n)`. The book never stays crossed: setting a level removes any
+/// stale levels on the opposite side that it would cross.
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    pub exchange: Exchange,
+    pub symbol: String,
+    bids: BTreeMap<Price, Quantity>,
+    asks: BTreeMap<Price, Quantity>,
+    pub sequence: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrderBook {
+    pub fn new(exchange: Exchange, symbol: impl Into<String>) -> Self {
+        Self {
+            exchange,
+            symbol: symbol.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            sequence: 0,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Removes every level and resets the sequence number
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.sequence = 0;
+    }
+
+    /// Sets the quantity at a price level (0 removes the level)
+    pub fn set_level(&mut self, side: BookSide, price: Price, quantity: Quantity) {
+        if !price.is_positive() {
+            return;
+        }
+
+        let (own, opposite) = match side {
+            BookSide::Bid => (&mut self.bids, &mut self.asks),
+            BookSide::Ask => (&mut self.asks, &mut self.bids),
+        };
+
+        if !quantity.is_positive() {
+            own.remove(&price);
+            return;
+        }
+        own.insert(price, quantity);
+
+        // Drop stale opposite levels this one would cross
+        match side {
+            BookSide::Bid => {
+                while opposite.first_key_value().is_some_and(|(k, _)| *k <= price) {
+                    opposite.pop_first();
+                }
+            }
+            BookSide::Ask => {
+                while opposite.last_key_value().is_some_and(|(k, _)| *k >= price) {
+                    opposite.pop_last();
+                }
+            }
+        }
+    }
+
+    /// Applies an incremental update or a full snapshot
+    pub fn apply_delta(&mut self, delta: &OrderBookDelta) {
+        if delta.is_snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        }
+        for level in &delta.bids {
+            self.set_level(BookSide::Bid, level.price, level.quantity);
+        }
+        for level in &delta.asks {
+            self.set_level(BookSide::Ask, level.price, level.quantity);
+        }
+        self.sequence = delta.sequence;
+        self.timestamp = delta.timestamp;
+    }
+
+    /// Highest bid
+    pub fn best_bid(&self) -> Option<OrderBookLevel> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(p, q)| OrderBookLevel::new(*p, *q))
+    }
+
+    /// Lowest ask
+    pub fn best_ask(&self) -> Option<OrderBookLevel> {
+        self.asks
+            .iter()
+            .next()
+            .map(|(p, q)| OrderBookLevel::new(*p, *q))
+    }
+
+    /// Bid levels from best to worst
+    pub fn bids(&self) -> impl Iterator<Item = OrderBookLevel> + '_ {
+        self.bids
+            .iter()
+            .rev()
+            .map(|(p, q)| OrderBookLevel::new(*p, *q))
+    }
+
+    /// Ask levels from best to worst
+    pub fn asks(&self) -> impl Iterator<Item = OrderBookLevel> + '_ {
+        self.asks.iter().map(|(p, q)| OrderBookLevel::new(*p, *q))
+    }
+
+    /// Number of (bid, ask) levels
+    pub fn level_counts(&self) -> (usize, usize) {
+        (self.bids.len(), self.asks.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// True when the best bid is at or above the best ask
+    pub fn is_crossed(&self) -> bool {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => bid.price >= ask.price,
+            _ => false,
+        }
+    }
+
+    /// Midpoint between best bid and best ask
+    pub fn mid(&self) -> Option<Price> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        Some(Price::new((bid.price + ask.price).value() / Decimal::TWO))
+    }
+
+    /// Best ask minus best bid
+    pub fn spread(&self) -> Option<Price> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        Some(ask.price - bid.price)
+    }
+
+    /// Top-of-book price weighted by the opposite side's size
+    pub fn microprice(&self) -> Option<Price> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        let total = bid.quantity + ask.quantity;
+        if !total.is_positive() {
+            return None;
+        }
+        Some((bid.price * ask.quantity + ask.price * bid.quantity) / total)
+    }
+
+    /// Average price paid to take `size` from one side of the book
+    ///
+    /// `BookSide::Ask` walks the asks (a buy), `BookSide::Bid` walks the bids
+    /// (a sell). Returns `None` if the book is not deep enough.
+    pub fn vwap(&self, side: BookSide, size: Quantity) -> Option<Price> {
+        if !size.is_positive() {
+            return None;
+        }
+
+        let levels: Box<dyn Iterator<Item = OrderBookLevel>> = match side {
+            BookSide::Bid => Box::new(self.bids()),
+            BookSide::Ask => Box::new(self.asks()),
+        };
+
+        let mut remaining = size;
+        let mut notional = Notional::ZERO;
+        for level in levels {
+            let take = remaining.min(level.quantity);
+            notional += level.price * take;
+            remaining -= take;
+            if remaining.is_zero() {
+                return Some(notional / size);
+            }
+        }
+        None
+    }
+
+    /// Depth imbalance over the top `levels` of each side, in `[-1, 1]`
+    ///
+    /// Positive values mean more resting bid size than ask size.
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bid_size: Quantity = self.bids().take(levels).map(|l| l.quantity).sum();
+        let ask_size: Quantity = self.asks().take(levels).map(|l| l.quantity).sum();
+        let total = bid_size + ask_size;
+        if !total.is_positive() {
+            return None;
+        }
+        ((bid_size - ask_size).value() / total.value()).to_f64()
+    }
+
+    /// Full-book delta that rebuilds this book when applied to an empty one
+    pub fn snapshot(&self) -> OrderBookDelta {
+        OrderBookDelta {
+            exchange: self.exchange.clone(),
+            symbol: self.symbol.clone(),
+            bids: self.bids().collect(),
+            asks: self.asks().collect(),
+            sequence: self.sequence,
+            is_snapshot: true,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Copy of the book limited to the best `depth` levels per side
+    pub fn truncated(&self, depth: usize) -> Self {
+        Self {
+            exchange: self.exchange.clone(),
+            symbol: self.symbol.clone(),
+            bids: self
+                .bids
+                .iter()
+                .rev()
+                .take(depth)
+                .map(|(p, q)| (*p, *q))
+                .collect(),
+            asks: self
+                .asks
+                .iter()
+                .take(depth)
+                .map(|(p, q)| (*p, *q))
+                .collect(),
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fmt::Debug;
+    use std::str::FromStr;
+
+    fn dec<T: FromStr>(s: &str) -> T
+    where
+        T::Err: Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    fn level(price: &str, quantity: &str) -> OrderBookLevel {
+        OrderBookLevel::new(dec(price), dec(quantity))
+    }
+
+    fn book() -> OrderBook {
+        let mut book = OrderBook::new(Exchange::Binance, "BTCUSDT");
+        book.set_level(BookSide::Bid, dec("100"), dec("2"));
+        book.set_level(BookSide::Bid, dec("99"), dec("3"));
+        book.set_level(BookSide::Ask, dec("101"), dec("1"));
+        book.set_level(BookSide::Ask, dec("102"), dec("4"));
+        book
+    }
+
+    #[test]
+    fn test_top_of_book_queries() {
+        let book = book();
+        assert_eq!(book.best_bid(), Some(level("100", "2")));
+        assert_eq!(book.best_ask(), Some(level("101", "1")));
+        assert_eq!(book.mid(), Some(dec("100.5")));
+        assert_eq!(book.spread(), Some(dec("1")));
+        // (100 * 1 + 101 * 2) / 3
+        assert_eq!(
+            book.microprice().map(|p| p.value().round_dp(6)),
+            Some(dec("100.666667"))
+        );
+        // (5 - 5) / 10
+        assert_eq!(book.imbalance(2), Some(0.0));
+    }
+
+    #[test]
+    fn test_vwap_walks_levels() {
+        let book = book();
+        // 1 @ 101 + 2 @ 102
+        assert_eq!(
+            book.vwap(BookSide::Ask, dec("3"))
+                .map(|p| p.value().round_dp(6)),
+            Some(dec("101.666667"))
+        );
+        assert_eq!(book.vwap(BookSide::Bid, dec("2")), Some(dec("100")));
+        assert_eq!(book.vwap(BookSide::Ask, dec("10")), None);
+    }
+
+    #[test]
+    fn test_crossing_level_removes_stale_opposite_side() {
+        let mut book = book();
+        book.set_level(BookSide::Bid, dec("101.5"), dec("1"));
+        assert_eq!(book.best_ask().map(|l| l.price), Some(dec("102")));
+        assert!(!book.is_crossed());
+    }
+
+    fn level_strategy() -> impl Strategy<Value = OrderBookLevel> {
+        // Integer ticks so equal prices actually collide
+        (1u32..200, 0u32..5).prop_map(|(tick, qty)| {
+            OrderBookLevel::new(
+                Price::new(Decimal::new(tick as i64 * 5, 1)),
+                Quantity::new(Decimal::from(qty)),
+            )
+        })
+    }
+
+    fn delta_strategy() -> impl Strategy<Value = OrderBookDelta> {
+        (
+            prop::collection::vec(level_strategy(), 0..20),
+            prop::collection::vec(level_strategy(), 0..20),
+            any::<bool>(),
+        )
+            .prop_map(|(bids, asks, is_snapshot)| OrderBookDelta {
+                exchange: Exchange::Binance,
+                symbol: "BTCUSDT".to_string(),
+                bids,
+                asks,
+                sequence: 0,
+                is_snapshot,
+                timestamp: Utc::now(),
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_book_never_crossed(deltas in prop::collection::vec(delta_strategy(), 1..30)) {
+            let mut book = OrderBook::new(Exchange::Binance, "BTCUSDT");
+            for delta in &deltas {
+                book.apply_delta(delta);
+                prop_assert!(!book.is_crossed());
+            }
+        }
+
+        #[test]
+        fn prop_sides_sorted_and_positive(deltas in prop::collection::vec(delta_strategy(), 1..30)) {
+            let mut book = OrderBook::new(Exchange::Binance, "BTCUSDT");
+            for delta in &deltas {
+                book.apply_delta(delta);
+            }
+            let bids: Vec<_> = book.bids().collect();
+            let asks: Vec<_> = book.asks().collect();
+            prop_assert!(bids.windows(2).all(|w| w[0].price > w[1].price));
+            prop_assert!(asks.windows(2).all(|w| w[0].price < w[1].price));
+            prop_assert!(bids.iter().chain(asks.iter()).all(|l| l.quantity.is_positive()));
+        }
+    }
+}
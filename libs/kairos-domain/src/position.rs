@@ -0,0 +1,205 @@
+use crate::models::{Exchange, Fill, OrderSide};
+use crate::numeric::{Notional, Price, Quantity};
+use crate::symbol::Symbol;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Net position in one instrument on one venue, built from fills
+///
+/// The average entry price only moves when the position grows; reducing
+/// fills realize PnL against it and a fill through zero re-opens the
+/// remainder at the fill price. Amounts are fixed-point, so a position
+/// closed by matching fills is exactly flat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub exchange: Exchange,
+    pub symbol: Symbol,
+    /// Signed base quantity (negative = short)
+    pub quantity: Quantity,
+    /// Volume-weighted entry price of the open quantity (0 when flat)
+    pub average_entry_price: Price,
+    /// PnL locked in by reducing fills, in quote currency, before fees
+    pub realized_pnl: Notional,
+    /// Fees paid, in quote currency
+    pub fees_paid: Notional,
+    /// Latest mark price, if one has been seen
+    pub mark_price: Option<Price>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Position {
+    /// Flat position
+    pub fn new(exchange: Exchange, symbol: Symbol) -> Self {
+        Self {
+            exchange,
+            symbol,
+            quantity: Quantity::ZERO,
+            average_entry_price: Price::ZERO,
+            realized_pnl: Notional::ZERO,
+            fees_paid: Notional::ZERO,
+            mark_price: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.quantity.is_zero()
+    }
+
+    /// Update quantity, entry price, realized PnL and fees from a fill
+    pub fn apply_fill(&mut self, fill: &Fill) {
+        let (quantity, price) = (fill.quantity.value(), fill.price.value());
+        let current = self.quantity.value();
+        let entry = self.average_entry_price.value();
+        let signed = match fill.side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+
+        if self.is_flat() || current.is_sign_negative() == signed.is_sign_negative() {
+            // Opening or adding
+            let size = current.abs() + quantity;
+            if let Some(average) = (current.abs() * entry + quantity * price).checked_div(size) {
+                self.average_entry_price = Price::new(average);
+            }
+        } else {
+            // Reducing, closing or flipping
+            let closed = quantity.min(current.abs());
+            let pnl = closed * (price - entry);
+            self.realized_pnl += Notional::new(if current.is_sign_negative() {
+                -pnl
+            } else {
+                pnl
+            });
+            if quantity > current.abs() {
+                self.average_entry_price = fill.price;
+            }
+        }
+
+        self.quantity = Quantity::new(current + signed);
+        if self.is_flat() {
+            self.quantity = Quantity::ZERO;
+            self.average_entry_price = Price::ZERO;
+        }
+
+        // Fees in the base asset are valued at the fill price; other currencies are not tracked
+        let fee = fill.fee.value();
+        if fill.fee_currency.eq_ignore_ascii_case(&self.symbol.quote) {
+            self.fees_paid += Notional::new(fee);
+        } else if fill.fee_currency.eq_ignore_ascii_case(&self.symbol.base) {
+            self.fees_paid += Notional::new(fee * price);
+        }
+        self.updated_at = fill.timestamp;
+    }
+
+    /// Record the latest market price
+    pub fn mark(&mut self, price: Price) {
+        self.mark_price = Some(price);
+    }
+
+    /// Open PnL at the mark price (0 until a mark is known)
+    pub fn unrealized_pnl(&self) -> Notional {
+        self.mark_price
+            .map(|mark| {
+                Notional::new(
+                    self.quantity.value() * (mark.value() - self.average_entry_price.value()),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Realized plus unrealized PnL, net of fees
+    pub fn total_pnl(&self) -> Notional {
+        self.realized_pnl + self.unrealized_pnl() - self.fees_paid
+    }
+
+    /// Absolute quote value of the open quantity, at the mark or else the entry price
+    pub fn notional(&self) -> Notional {
+        let price = self.mark_price.unwrap_or(self.average_entry_price);
+        Notional::new(self.quantity.value().abs() * price.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::Amount;
+
+    fn dec<T: std::str::FromStr>(s: &str) -> T
+    where
+        T::Err: std::fmt::Debug,
+    {
+        s.parse().unwrap()
+    }
+
+    fn fill(side: OrderSide, quantity: &str, price: &str) -> Fill {
+        Fill {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            exchange_order_id: "1".to_string(),
+            client_order_id: None,
+            trade_id: "1".to_string(),
+            side,
+            price: dec(price),
+            quantity: dec(quantity),
+            fee: Amount::ZERO,
+            fee_currency: "USDT".to_string(),
+            liquidity: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn position() -> Position {
+        Position::new(Exchange::Binance, Symbol::new("BTC", "USDT"))
+    }
+
+    #[test]
+    fn test_average_entry_and_realized_pnl() {
+        let mut position = position();
+        position.apply_fill(&fill(OrderSide::Buy, "1", "100"));
+        position.apply_fill(&fill(OrderSide::Buy, "3", "120"));
+        assert_eq!(position.quantity, dec("4"));
+        assert_eq!(position.average_entry_price, dec("115"));
+
+        position.apply_fill(&fill(OrderSide::Sell, "1", "125"));
+        assert_eq!(position.quantity, dec("3"));
+        assert_eq!(position.average_entry_price, dec("115"));
+        assert_eq!(position.realized_pnl, dec("10"));
+
+        position.mark(dec("110"));
+        assert_eq!(position.unrealized_pnl(), dec("-15"));
+        assert_eq!(position.notional(), dec("330"));
+    }
+
+    #[test]
+    fn test_flip_and_close_short() {
+        let mut position = position();
+        position.apply_fill(&fill(OrderSide::Buy, "1", "100"));
+        // Sell through zero: close the long at +10, open 1 short at 110
+        position.apply_fill(&fill(OrderSide::Sell, "2", "110"));
+        assert_eq!(position.quantity, dec("-1"));
+        assert_eq!(position.average_entry_price, dec("110"));
+        assert_eq!(position.realized_pnl, dec("10"));
+
+        position.apply_fill(&fill(OrderSide::Buy, "1", "105"));
+        assert!(position.is_flat());
+        assert_eq!(position.average_entry_price, dec("0"));
+        assert_eq!(position.realized_pnl, dec("15"));
+    }
+
+    #[test]
+    fn test_fees_in_quote_and_base() {
+        let mut position = position();
+        let mut buy = fill(OrderSide::Buy, "1", "100");
+        buy.fee = dec("0.001");
+        buy.fee_currency = "BTC".to_string();
+        position.apply_fill(&buy);
+
+        let mut sell = fill(OrderSide::Sell, "1", "100");
+        sell.fee = dec("0.2");
+        position.apply_fill(&sell);
+
+        assert_eq!(position.fees_paid, dec("0.3"));
+        assert_eq!(position.total_pnl(), dec("-0.3"));
+    }
+}
@@ -0,0 +1,508 @@
+// Currency rate graph - a negative cycle is a triangular arbitrage
+
+use crate::{
+    DomainError, DomainResult, Exchange, Instrument, InternalOrder, OrderSide, Price, Quantity,
+    Symbol,
+};
+use std::collections::{HashMap, HashSet};
+
+/// One conversion between two currencies on a single venue
+#[derive(Debug, Clone, PartialEq)]
+struct RateEdge {
+    from: usize,
+    to: usize,
+    symbol: Symbol,
+    side: OrderSide,
+    price: f64,
+    size: f64,
+    rate: f64,
+    weight: f64,
+}
+
+/// One step of a cycle: trade `symbol` on `side` to turn `from` into `to`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleLeg {
+    pub from: String,
+    pub to: String,
+    pub symbol: Symbol,
+    pub side: OrderSide,
+    /// Touch price the leg trades at (bid for sells, ask for buys)
+    pub price: f64,
+    /// Base quantity displayed at `price`
+    pub size: f64,
+    /// Units of `to` received per unit of `from`, after fees
+    pub rate: f64,
+}
+
+/// A sequence of conversions that ends in the currency it started from
+/// with more than it began with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageCycle {
+    pub exchange: Exchange,
+    pub legs: Vec<CycleLeg>,
+    /// Relative gain of one trip round the cycle, after fees (0.001 = 0.1%)
+    pub profit: f64,
+}
+
+impl ArbitrageCycle {
+    /// Currency the first leg spends and the last leg returns
+    pub fn start_currency(&self) -> &str {
+        &self.legs[0].from
+    }
+
+    /// The same cycle, starting from `currency`
+    pub fn rotate_to(&self, currency: &str) -> Option<ArbitrageCycle> {
+        let start = self.legs.iter().position(|leg| leg.from == currency)?;
+        let mut legs = self.legs.clone();
+        legs.rotate_left(start);
+        Some(ArbitrageCycle {
+            legs,
+            ..self.clone()
+        })
+    }
+
+    /// Orders for one trip round the cycle, in execution order
+    ///
+    /// Spends at most `budget` of the start currency, less if a leg's
+    /// displayed size cannot absorb it. Each leg's quantity is rounded down
+    /// onto its instrument's lot grid and checked against its minimums; the
+    /// next leg trades what the rounded leg delivers after fees.
+    pub fn plan_orders<F>(&self, budget: f64, instrument: F) -> DomainResult<Vec<InternalOrder>>
+    where
+        F: Fn(&Symbol) -> Option<Instrument>,
+    {
+        // Largest start amount every leg's displayed size can absorb
+        let mut start = budget;
+        let mut reach = 1.0;
+        for leg in &self.legs {
+            let capacity = match leg.side {
+                OrderSide::Sell => leg.size,
+                OrderSide::Buy => leg.size * leg.price,
+            };
+            start = start.min(capacity / reach);
+            reach *= leg.rate;
+        }
+        if !(start.is_finite() && start > 0.0) {
+            return Err(DomainError::InvalidQuantity(format!(
+                "no size available for cycle from {}",
+                self.start_currency()
+            )));
+        }
+
+        let mut amount = start;
+        let mut orders = Vec::with_capacity(self.legs.len());
+        for leg in &self.legs {
+            let instrument =
+                instrument(&leg.symbol).ok_or_else(|| DomainError::SymbolNotFound {
+                    symbol: leg.symbol.to_string(),
+                })?;
+            let quantity = match leg.side {
+                OrderSide::Sell => amount,
+                OrderSide::Buy => amount / leg.price,
+            };
+            let order = instrument.normalize_order(&InternalOrder {
+                symbol: leg.symbol.to_string(),
+                side: leg.side.clone(),
+                quantity: Quantity::try_from(quantity)?,
+                price: Some(Price::try_from(leg.price)?),
+                risk_score: 1.0,
+            })?;
+
+            let traded = order.quantity.to_f64();
+            amount = match leg.side {
+                OrderSide::Sell => traded * leg.rate,
+                OrderSide::Buy => traded * leg.price * leg.rate,
+            };
+            orders.push(order);
+        }
+        Ok(orders)
+    }
+}
+
+/// Best walk of each length from one source: `dist[k][v]` is the lowest
+/// weight of a `k`-edge walk ending at `v`, reached by edge `pred[k][v]`
+struct Layers {
+    dist: Vec<Vec<f64>>,
+    pred: Vec<Vec<usize>>,
+}
+
+/// Directed graph of conversion rates between the currencies of one venue
+///
+/// Every instrument `BASE/QUOTE` contributes two edges: selling base at
+/// the bid (`BASE -> QUOTE`) and buying base at the ask (`QUOTE -> BASE`),
+/// each weighted `-ln(rate)` with the taker fee taken out of the rate. A
+/// cycle whose weights sum below zero returns more than it started with.
+///
+/// Quotes update their two edges in place. Cycles are found with a
+/// Bellman-Ford relaxation bounded to the maximum cycle length, keeping one
+/// distance layer per walk length so the cycle can be read back.
+///
+/// # Example
+/// ```rust,ignore
+/// let mut graph = RateGraph::new(Exchange::Binance, 10.0);
+/// let edges = graph.update_quote(&symbol, bid, bid_size, ask, ask_size);
+/// let cycle = edges
+///     .iter()
+///     .filter_map(|&edge| graph.find_cycle_through(edge, 3, 0.001))
+///     .next();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateGraph {
+    exchange: Exchange,
+    /// Taker fee as a fraction of the traded amount
+    fee: f64,
+    currencies: Vec<String>,
+    nodes: HashMap<String, usize>,
+    edges: Vec<RateEdge>,
+    edge_ids: HashMap<(usize, usize), usize>,
+}
+
+impl RateGraph {
+    pub fn new(exchange: Exchange, taker_fee_bps: f64) -> Self {
+        Self {
+            exchange,
+            fee: taker_fee_bps / 10_000.0,
+            currencies: Vec::new(),
+            nodes: HashMap::new(),
+            edges: Vec::new(),
+            edge_ids: HashMap::new(),
+        }
+    }
+
+    pub fn exchange(&self) -> &Exchange {
+        &self.exchange
+    }
+
+    pub fn currency_count(&self) -> usize {
+        self.currencies.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Units of `to` one unit of `from` is worth at the touch, before fees
+    ///
+    /// Uses the instrument between the two currencies, or a route through one
+    /// other currency when they share none. `None` when no quoted route exists.
+    pub fn conversion_rate(&self, from: &str, to: &str) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        let (&from, &to) = (self.nodes.get(from)?, self.nodes.get(to)?);
+        self.touch_rate(from, to).or_else(|| {
+            (0..self.currencies.len())
+                .find_map(|via| Some(self.touch_rate(from, via)? * self.touch_rate(via, to)?))
+        })
+    }
+
+    /// Touch rate of the edge `from -> to`: the bid selling base, 1/ask buying it
+    fn touch_rate(&self, from: usize, to: usize) -> Option<f64> {
+        let edge = &self.edges[*self.edge_ids.get(&(from, to))?];
+        let rate = match edge.side {
+            OrderSide::Sell => edge.price,
+            OrderSide::Buy => 1.0 / edge.price,
+        };
+        (rate.is_finite() && rate > 0.0).then_some(rate)
+    }
+
+    /// Apply an instrument's best bid/ask, returning the IDs of its two edges
+    ///
+    /// A side with no price (0) disables its edge until the next quote.
+    pub fn update_quote(
+        &mut self,
+        symbol: &Symbol,
+        bid: f64,
+        bid_size: f64,
+        ask: f64,
+        ask_size: f64,
+    ) -> [usize; 2] {
+        let base = self.node(&symbol.base);
+        let quote = self.node(&symbol.quote);
+        let keep = 1.0 - self.fee;
+        let buy_rate = if ask > 0.0 { keep / ask } else { 0.0 };
+
+        [
+            self.set_edge(
+                base,
+                quote,
+                symbol,
+                OrderSide::Sell,
+                bid,
+                bid_size,
+                bid * keep,
+            ),
+            self.set_edge(quote, base, symbol, OrderSide::Buy, ask, ask_size, buy_rate),
+        ]
+    }
+
+    /// Most profitable cycle of at most `max_len` legs that uses `edge`
+    ///
+    /// Only cycles through a changed edge can have appeared since the last
+    /// check, so this is all a single quote update needs to look at.
+    pub fn find_cycle_through(
+        &self,
+        edge: usize,
+        max_len: usize,
+        min_profit: f64,
+    ) -> Option<ArbitrageCycle> {
+        let closing = self.edges.get(edge)?;
+        if !closing.weight.is_finite() || max_len < 2 {
+            return None;
+        }
+        let layers = self.relax_from(closing.to, max_len - 1);
+        let threshold = -(1.0 + min_profit).ln();
+
+        let (length, weight) = (1..max_len)
+            .map(|k| (k, layers.dist[k][closing.from] + closing.weight))
+            .filter(|(_, weight)| *weight < threshold)
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        let mut path = self.walk(&layers, length, closing.from);
+        path.push(edge);
+        self.cycle(&path, weight)
+    }
+
+    /// Every profitable cycle of at most `max_len` legs, best first
+    pub fn find_cycles(&self, max_len: usize, min_profit: f64) -> Vec<ArbitrageCycle> {
+        let threshold = -(1.0 + min_profit).ln();
+        let mut seen = HashSet::new();
+        let mut cycles = Vec::new();
+
+        for source in 0..self.currencies.len() {
+            let layers = self.relax_from(source, max_len);
+            for length in 2..=max_len {
+                let weight = layers.dist[length][source];
+                if weight >= threshold {
+                    continue;
+                }
+                let path = self.walk(&layers, length, source);
+                // The same cycle is found once from each currency on it
+                let mut key = path.clone();
+                key.sort_unstable();
+                if !seen.insert(key) {
+                    continue;
+                }
+                cycles.extend(self.cycle(&path, weight));
+            }
+        }
+
+        cycles.sort_by(|a, b| b.profit.total_cmp(&a.profit));
+        cycles
+    }
+
+    fn node(&mut self, currency: &str) -> usize {
+        if let Some(&id) = self.nodes.get(currency) {
+            return id;
+        }
+        let id = self.currencies.len();
+        self.currencies.push(currency.to_string());
+        self.nodes.insert(currency.to_string(), id);
+        id
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn set_edge(
+        &mut self,
+        from: usize,
+        to: usize,
+        symbol: &Symbol,
+        side: OrderSide,
+        price: f64,
+        size: f64,
+        rate: f64,
+    ) -> usize {
+        let weight = if rate > 0.0 && rate.is_finite() {
+            -rate.ln()
+        } else {
+            f64::INFINITY
+        };
+
+        if let Some(&id) = self.edge_ids.get(&(from, to)) {
+            let edge = &mut self.edges[id];
+            edge.price = price;
+            edge.size = size;
+            edge.rate = rate;
+            edge.weight = weight;
+            return id;
+        }
+
+        let id = self.edges.len();
+        self.edges.push(RateEdge {
+            from,
+            to,
+            symbol: symbol.clone(),
+            side,
+            price,
+            size,
+            rate,
+            weight,
+        });
+        self.edge_ids.insert((from, to), id);
+        id
+    }
+
+    /// Bellman-Ford from `source`, one relaxation pass per walk length
+    fn relax_from(&self, source: usize, max_edges: usize) -> Layers {
+        let nodes = self.currencies.len();
+        let mut dist = vec![vec![f64::INFINITY; nodes]; max_edges + 1];
+        let mut pred = vec![vec![usize::MAX; nodes]; max_edges + 1];
+        dist[0][source] = 0.0;
+
+        for k in 1..=max_edges {
+            let (done, next) = dist.split_at_mut(k);
+            let (previous, current) = (&done[k - 1], &mut next[0]);
+            for (id, edge) in self.edges.iter().enumerate() {
+                let candidate = previous[edge.from] + edge.weight;
+                if candidate < current[edge.to] {
+                    current[edge.to] = candidate;
+                    pred[k][edge.to] = id;
+                }
+            }
+        }
+        Layers { dist, pred }
+    }
+
+    /// Edges of the best `length`-edge walk ending at `node`, in order
+    fn walk(&self, layers: &Layers, length: usize, mut node: usize) -> Vec<usize> {
+        let mut path = Vec::with_capacity(length + 1);
+        for k in (1..=length).rev() {
+            let id = layers.pred[k][node];
+            path.push(id);
+            node = self.edges[id].from;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Build a cycle from closed walk edges, rejecting walks that revisit a
+    /// currency (their profitable part is found as a shorter cycle)
+    fn cycle(&self, path: &[usize], weight: f64) -> Option<ArbitrageCycle> {
+        let mut visited = HashSet::with_capacity(path.len());
+        if !path.iter().all(|&id| visited.insert(self.edges[id].from)) {
+            return None;
+        }
+
+        let legs = path
+            .iter()
+            .map(|&id| {
+                let edge = &self.edges[id];
+                CycleLeg {
+                    from: self.currencies[edge.from].clone(),
+                    to: self.currencies[edge.to].clone(),
+                    symbol: edge.symbol.clone(),
+                    side: edge.side.clone(),
+                    price: edge.price,
+                    size: edge.size,
+                    rate: edge.rate,
+                }
+            })
+            .collect();
+
+        Some(ArbitrageCycle {
+            exchange: self.exchange.clone(),
+            legs,
+            profit: (-weight).exp() - 1.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Notional;
+
+    /// BTC/USDT 100, ETH/USDT 10, ETH/BTC quoted too cheap at 0.09
+    fn mispriced() -> RateGraph {
+        let mut graph = RateGraph::new(Exchange::Binance, 10.0);
+        graph.update_quote(&Symbol::new("BTC", "USDT"), 100.0, 10.0, 100.0, 10.0);
+        graph.update_quote(&Symbol::new("ETH", "USDT"), 10.0, 100.0, 10.0, 100.0);
+        graph.update_quote(&Symbol::new("ETH", "BTC"), 0.09, 100.0, 0.09, 100.0);
+        graph
+    }
+
+    fn instrument(symbol: &Symbol) -> Option<Instrument> {
+        Some(Instrument::new(
+            Exchange::Binance,
+            symbol.clone(),
+            "0.01".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            "0.001".parse().unwrap(),
+            Notional::default(),
+        ))
+    }
+
+    #[test]
+    fn test_conversion_rate() {
+        let mut graph = mispriced();
+        graph.update_quote(&Symbol::new("SOL", "ETH"), 0.5, 1.0, 0.5, 1.0);
+
+        assert_eq!(graph.conversion_rate("BTC", "USDT"), Some(100.0));
+        assert_eq!(graph.conversion_rate("USDT", "USDT"), Some(1.0));
+        assert!((graph.conversion_rate("USDT", "ETH").unwrap() - 0.1).abs() < 1e-12);
+        // No SOL/USDT instrument: valued through ETH
+        assert_eq!(graph.conversion_rate("SOL", "USDT"), Some(5.0));
+        assert_eq!(graph.conversion_rate("DOGE", "USDT"), None);
+    }
+
+    #[test]
+    fn test_finds_triangle_through_mispriced_edge() {
+        let graph = mispriced();
+        let cycles = graph.find_cycles(3, 0.001);
+        assert_eq!(cycles.len(), 1);
+
+        // USDT -> BTC -> ETH -> USDT: 100/100 * 1/0.09 * 10 = 1.111 before fees
+        let cycle = cycles[0].rotate_to("USDT").unwrap();
+        let route: Vec<_> = cycle.legs.iter().map(|leg| leg.to.as_str()).collect();
+        assert_eq!(route, vec!["BTC", "ETH", "USDT"]);
+        assert!((cycle.profit - (1.0 / 0.9 * 0.999_f64.powi(3) - 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fair_prices_and_short_limit_find_nothing() {
+        let mut graph = mispriced();
+        assert!(graph.find_cycles(2, 0.0).is_empty());
+
+        let [sell, buy] =
+            graph.update_quote(&Symbol::new("ETH", "BTC"), 0.0999, 100.0, 0.1001, 100.0);
+        assert!(graph.find_cycles(3, 0.0).is_empty());
+        assert!(graph.find_cycle_through(sell, 3, 0.0).is_none());
+        assert!(graph.find_cycle_through(buy, 3, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_incremental_check_matches_full_scan() {
+        let mut graph = mispriced();
+        let [_, buy] = graph.update_quote(&Symbol::new("ETH", "BTC"), 0.09, 100.0, 0.09, 100.0);
+
+        let cycle = graph.find_cycle_through(buy, 3, 0.001).unwrap();
+        assert_eq!(cycle.legs.len(), 3);
+        assert_eq!(cycle.legs[2].symbol, Symbol::new("ETH", "BTC"));
+        assert_eq!(cycle.legs[2].side, OrderSide::Buy);
+        assert!((cycle.profit - graph.find_cycles(3, 0.001)[0].profit).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_plan_orders_respects_lots_and_sizes() {
+        let graph = mispriced();
+        let cycle = graph.find_cycles(3, 0.001)[0].rotate_to("USDT").unwrap();
+
+        let orders = cycle.plan_orders(250.0, instrument).unwrap();
+        assert_eq!(orders.len(), 3);
+
+        // 250 USDT buys 2.5 BTC, 2.4975 BTC after fees buys 27.75 ETH, and
+        // 27.72225 ETH after fees is sold down to the 0.001 lot
+        assert_eq!(orders[0].side, OrderSide::Buy);
+        assert_eq!(orders[0].symbol, "BTC/USDT");
+        assert_eq!(orders[0].quantity, "2.5".parse().unwrap());
+        assert_eq!(orders[1].side, OrderSide::Buy);
+        assert_eq!(orders[1].symbol, "ETH/BTC");
+        assert_eq!(orders[1].quantity, "27.75".parse().unwrap());
+        assert_eq!(orders[2].side, OrderSide::Sell);
+        assert_eq!(orders[2].quantity, "27.722".parse().unwrap());
+
+        // ETH/BTC shows 100 ETH (9 BTC), capping the start at ~900.9 USDT
+        let capped = cycle.plan_orders(1_000_000.0, instrument).unwrap();
+        assert_eq!(capped[0].quantity, "9.009".parse().unwrap());
+    }
+}
@@ -0,0 +1,136 @@
+// Canonical trading pair symbols and exchange-specific naming
+
+use crate::errors::{DomainError, DomainResult};
+use crate::models::Exchange;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Quote assets recognised when splitting concatenated symbols (e.g. "BTCUSDT")
+///
+/// Ordered so that longer codes win over their suffixes ("FDUSD" before "USD").
+const KNOWN_QUOTES: &[&str] = &[
+    "FDUSD", "USDT", "USDC", "TUSD", "BUSD", "USD", "EUR", "GBP", "TRY", "BTC", "ETH", "BNB",
+];
+
+/// Exchange-agnostic trading pair, written `BASE/QUOTE` (e.g. "BTC/USDT")
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Symbol {
+    pub base: String,
+    pub quote: String,
+}
+
+impl Symbol {
+    pub fn new(base: impl Into<String>, quote: impl Into<String>) -> Self {
+        Self {
+            base: base.into().to_uppercase(),
+            quote: quote.into().to_uppercase(),
+        }
+    }
+
+    /// Native symbol used by an exchange's API
+    ///
+    /// * Binance: `BTCUSDT`
+    /// * OKX: `BTC-USDT`
+    /// * Kraken: `BTC/USDT`
+    pub fn to_exchange(&self, exchange: &Exchange) -> String {
+        match exchange {
+            Exchange::Binance => format!("{}{}", self.base, self.quote),
+            Exchange::OKX => format!("{}-{}", self.base, self.quote),
+            Exchange::Kraken => format!("{}/{}", self.base, self.quote),
+        }
+    }
+
+    /// Parse an exchange-native symbol back into its canonical form
+    ///
+    /// Concatenated symbols (Binance) are split on a known quote asset.
+    pub fn from_exchange(exchange: &Exchange, native: &str) -> DomainResult<Self> {
+        let native = native.to_uppercase();
+        match exchange {
+            Exchange::OKX => split_on(&native, '-'),
+            Exchange::Kraken => split_on(&native, '/'),
+            Exchange::Binance => KNOWN_QUOTES
+                .iter()
+                .find(|quote| native.len() > quote.len() && native.ends_with(*quote))
+                .map(|quote| Self::new(&native[..native.len() - quote.len()], *quote))
+                .ok_or(DomainError::SymbolNotFound { symbol: native }),
+        }
+    }
+}
+
+fn split_on(native: &str, separator: char) -> DomainResult<Symbol> {
+    match native.split_once(separator) {
+        Some((base, quote)) if is_asset(base) && is_asset(quote) => Ok(Symbol::new(base, quote)),
+        _ => Err(DomainError::SymbolNotFound {
+            symbol: native.to_string(),
+        }),
+    }
+}
+
+fn is_asset(code: &str) -> bool {
+    !code.is_empty() && code.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+impl FromStr for Symbol {
+    type Err = DomainError;
+
+    /// Parse a canonical `BASE/QUOTE` symbol
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        split_on(&s.trim().to_uppercase(), '/')
+    }
+}
+
+impl TryFrom<String> for Symbol {
+    type Error = DomainError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.to_string()
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.base, self.quote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_round_trip() {
+        let symbol: Symbol = "btc/usdt".parse().unwrap();
+        assert_eq!(symbol.to_string(), "BTC/USDT");
+
+        for (exchange, native) in [
+            (Exchange::Binance, "BTCUSDT"),
+            (Exchange::OKX, "BTC-USDT"),
+            (Exchange::Kraken, "BTC/USDT"),
+        ] {
+            assert_eq!(symbol.to_exchange(&exchange), native);
+            assert_eq!(Symbol::from_exchange(&exchange, native).unwrap(), symbol);
+        }
+    }
+
+    #[test]
+    fn test_binance_split_prefers_longest_quote() {
+        let symbol = Symbol::from_exchange(&Exchange::Binance, "ethfdusd").unwrap();
+        assert_eq!(symbol, Symbol::new("ETH", "FDUSD"));
+    }
+
+    #[test]
+    fn test_invalid_symbols_rejected() {
+        assert!("BTCUSDT".parse::<Symbol>().is_err());
+        assert!("BTC/".parse::<Symbol>().is_err());
+        assert!("BTC/US DT".parse::<Symbol>().is_err());
+        assert!(Symbol::from_exchange(&Exchange::Binance, "USDT").is_err());
+    }
+}